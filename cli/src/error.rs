@@ -8,6 +8,14 @@ pub enum CliError {
     InvalidPathConversion,
     #[error("can't find Blueprint")]
     MissingBlueprint,
+    #[error("invalid Blueprint")]
+    InvalidBlueprint,
+    #[error("{0} Blueprint file(s) not formatted, run `rush fmt`")]
+    UnformattedBlueprint(usize),
+    #[error("{0} Blueprint file(s) have comments, use --strip-comments to drop them")]
+    CommentedBlueprint(usize),
     #[error("not in a Rush workspace")]
     NotRushWorkspace,
+    #[error("{0} already exists, use --force to overwrite it")]
+    FileExists(String),
 }
@@ -1,4 +1,4 @@
-use crate::{error::*, handlers::CliHandler};
+use crate::{error::*, handlers::CliHandler, utils::load_blueprint};
 use anyhow::{bail, Result};
 use clap::ArgMatches;
 use rush_ecs_manifest::{Chain, Manifest};
@@ -7,16 +7,36 @@ use std::path::Path;
 
 pub struct DeployHandler;
 
+/// Rush Deploy Command
+///
+/// Syncs the World onchain with the Blueprint in the
+/// current workspace. Only the changes are sent, so
+/// deploying again after an edit updates the World
+///
+/// # Arguments
+/// * `--dry-run` - Only show the changes deploying would make
+///
+/// # Examples
+///
+/// ```bash
+/// rush deploy
+///
+/// # Preview the changes without sending them
+/// rush deploy --dry-run
+/// ```
+///
 impl CliHandler for DeployHandler {
-    // TODO: Validate arguments
     async fn handle_matches(matches: &ArgMatches) -> Result<()> {
         if !Path::new("./Rush.toml").exists() {
             bail!(CliError::NotRushWorkspace)
         }
-        if !Path::new("./blueprint/world.toml").exists() {
+        if !Path::new("./blueprint").exists() {
             bail!(CliError::MissingBlueprint)
         }
 
+        // report Blueprint problems before connecting to the chain
+        load_blueprint(Path::new("./blueprint"))?;
+
         let manifest = Manifest::from_toml("./Rush.toml")?;
 
         let Chain::Solana {
@@ -26,14 +46,19 @@ impl CliHandler for DeployHandler {
         } = manifest.chain;
         let mut sdk = BevySDK::new(rpc, &store, "./blueprint", &keypair);
 
-        sdk.migrate()
+        if matches.get_flag("DRY_RUN") {
+            println!("{}", sdk.plan()?);
+            return Ok(());
+        }
+
+        sdk.sync()?;
+
+        Ok(())
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use super::*;
-
     #[test]
     fn test_view() {}
 }
@@ -0,0 +1,45 @@
+use crate::{error::*, handlers::CliHandler, utils::load_blueprint};
+use anyhow::{bail, Result};
+use clap::ArgMatches;
+use std::path::Path;
+
+pub struct DiffHandler;
+
+/// Rush Diff Command
+///
+/// Shows what changed between two Blueprints. Each
+/// Blueprint can be a file or a directory
+///
+/// # Arguments
+/// * `A` - Path to the old Blueprint
+/// * `B` - Path to the new Blueprint
+///
+/// # Examples
+///
+/// ```bash
+/// rush diff ../v1/blueprint ./blueprint
+/// ```
+///
+impl CliHandler for DiffHandler {
+    async fn handle_matches(matches: &ArgMatches) -> Result<()> {
+        let Some(a) = matches.get_one::<String>("A") else {
+            bail!(CliError::MissingArgument("A".to_string()))
+        };
+        let Some(b) = matches.get_one::<String>("B") else {
+            bail!(CliError::MissingArgument("B".to_string()))
+        };
+
+        for path in [a, b] {
+            if !Path::new(path).exists() {
+                bail!(CliError::MissingBlueprint)
+            }
+        }
+
+        let before = load_blueprint(Path::new(a))?;
+        let after = load_blueprint(Path::new(b))?;
+
+        println!("{}", before.diff(&after));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,259 @@
+use crate::{error::*, handlers::CliHandler};
+use anyhow::{bail, Result};
+use clap::ArgMatches;
+use colored::Colorize;
+use rush_ecs_parser::{
+    error::{ParseError, ParseErrors},
+    toml::{self, has_comments, TomlEmitter, TomlParser},
+    utils::{dir_to_paths, is_blueprint_file},
+    Emitter, Parser,
+};
+use std::{
+    fs::{read_to_string, write},
+    path::Path,
+};
+
+pub struct FmtHandler;
+
+/// Rush Fmt Command
+///
+/// Rewrites the TOML Blueprint files in the current
+/// workspace in canonical form
+///
+/// Canonical form has no comments, files with comments
+/// are only rewritten with `--strip-comments`. `--check`
+/// ignores comments of files that are otherwise canonical
+///
+/// # Arguments
+/// * `--check` - Only check if the files are formatted
+/// * `--strip-comments` - Rewrite files even if it drops their comments
+///
+/// # Examples
+///
+/// ```bash
+/// rush fmt
+///
+/// # Fail if a file is not formatted, without rewriting it
+/// rush fmt --check
+///
+/// # Also rewrite files with comments, dropping them
+/// rush fmt --strip-comments
+/// ```
+///
+impl CliHandler for FmtHandler {
+    async fn handle_matches(matches: &ArgMatches) -> Result<()> {
+        if !Path::new("./Rush.toml").exists() {
+            bail!(CliError::NotRushWorkspace)
+        }
+        if !Path::new("./blueprint").exists() {
+            bail!(CliError::MissingBlueprint)
+        }
+
+        fmt_dir(
+            Path::new("./blueprint"),
+            matches.get_flag("CHECK"),
+            matches.get_flag("STRIP_COMMENTS"),
+        )
+    }
+}
+
+/// Format the TOML Blueprint files in a directory
+///
+/// Nothing is written if a file can't be parsed or if a
+/// file to rewrite has comments and `strip_comments` isn't
+/// set
+fn fmt_dir(dir: &Path, check: bool, strip_comments: bool) -> Result<()> {
+    let parser = TomlParser::default();
+    let emitter = TomlEmitter::default();
+
+    let paths = dir_to_paths(dir)
+        .into_iter()
+        .filter(|p| is_blueprint_file(p, parser.extensions()))
+        .collect::<Vec<_>>();
+
+    // format every file before writing any
+    let mut formatted_files = Vec::new();
+    let mut errors: Vec<ParseError> = Vec::new();
+    for path in paths.into_iter() {
+        let source = read_to_string(&path)?;
+
+        let partial = match parser.parse_partial(source.clone()) {
+            Ok(p) => p,
+            Err(e) => match e.downcast::<ParseErrors>() {
+                Ok(ParseErrors(file_errors)) => {
+                    errors.extend(file_errors.into_iter().map(|mut error| {
+                        error.path = Some(path.clone());
+                        error
+                    }));
+                    continue;
+                }
+                Err(e) => return Err(e),
+            },
+        };
+
+        let formatted = emitter.emit_partial(&partial)?;
+        if formatted != source {
+            formatted_files.push((path, source, formatted));
+        }
+    }
+
+    if !errors.is_empty() {
+        eprintln!("{}\n", ParseErrors(errors).render());
+        bail!(CliError::InvalidBlueprint)
+    }
+
+    if check {
+        // comments aside, a commented file may already be canonical
+        formatted_files.retain(|(_, source, formatted)| {
+            !has_comments(source) || toml::strip_comments(source) != *formatted
+        });
+
+        for (path, _, _) in formatted_files.iter() {
+            println!(
+                "[{}] {} is not formatted",
+                "WARNING".yellow().bold(),
+                path.display()
+            );
+        }
+
+        if !formatted_files.is_empty() {
+            bail!(CliError::UnformattedBlueprint(formatted_files.len()))
+        }
+
+        return Ok(());
+    }
+
+    // canonical form has no comments, don't lose them silently
+    let commented = formatted_files
+        .iter()
+        .filter(|(_, source, _)| has_comments(source))
+        .collect::<Vec<_>>();
+    if !commented.is_empty() && !strip_comments {
+        for (path, _, _) in commented.iter() {
+            println!(
+                "[{}] {} has comments, formatting would drop them",
+                "WARNING".yellow().bold(),
+                path.display()
+            );
+        }
+
+        bail!(CliError::CommentedBlueprint(commented.len()))
+    }
+
+    for (path, _, formatted) in formatted_files.into_iter() {
+        write(&path, formatted)?;
+        println!(
+            "[{}] Formatted {}",
+            "SUCCESS".green().bold(),
+            path.display()
+        );
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{
+        env::temp_dir,
+        fs::{create_dir_all, remove_dir_all},
+        path::PathBuf,
+    };
+
+    const UNFORMATTED: &str = r#"
+[entity]
+tree = {  height = "u64" }
+
+[world]
+regions = ["farm"]
+name = "World"
+description = "World"
+
+[farm]
+tree = [{ height = 1 }]
+"#;
+    const FORMATTED: &str = r#"[world]
+name = "World"
+description = "World"
+regions = ["farm"]
+
+[entity]
+tree = { height = "u64" }
+
+[farm]
+tree = [
+    { height = 1 },
+]
+"#;
+
+    /// Returns an empty directory unique to the test
+    fn test_dir(name: &str) -> PathBuf {
+        let dir = temp_dir().join(format!("rush-fmt-{name}-{}", std::process::id()));
+        let _ = remove_dir_all(&dir);
+        create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn test_fmt() {
+        let dir = test_dir("rewrite");
+        let path = dir.join("world.toml");
+        write(&path, UNFORMATTED).unwrap();
+
+        // check doesn't rewrite
+        let err = fmt_dir(&dir, true, false).unwrap_err();
+        assert!(matches!(
+            err.downcast_ref::<CliError>(),
+            Some(CliError::UnformattedBlueprint(1))
+        ));
+        assert_eq!(read_to_string(&path).unwrap(), UNFORMATTED);
+
+        fmt_dir(&dir, false, false).unwrap();
+        assert_eq!(read_to_string(&path).unwrap(), FORMATTED);
+        fmt_dir(&dir, true, false).unwrap();
+
+        remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_fmt_comments() {
+        let dir = test_dir("comments");
+        let path = dir.join("world.toml");
+        let source = format!("# my World\n{UNFORMATTED}");
+        write(&path, &source).unwrap();
+
+        let err = fmt_dir(&dir, false, false).unwrap_err();
+        assert!(matches!(
+            err.downcast_ref::<CliError>(),
+            Some(CliError::CommentedBlueprint(1))
+        ));
+        assert_eq!(read_to_string(&path).unwrap(), source);
+
+        fmt_dir(&dir, false, true).unwrap();
+        assert_eq!(read_to_string(&path).unwrap(), FORMATTED);
+
+        remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_fmt_check_comments() {
+        let dir = test_dir("check_comments");
+        let path = dir.join("world.toml");
+
+        let source = format!("# my World\n{FORMATTED}");
+        write(&path, &source).unwrap();
+        fmt_dir(&dir, true, false).unwrap();
+
+        let source = format!("# my World\n{UNFORMATTED}");
+        write(&path, &source).unwrap();
+        let err = fmt_dir(&dir, true, false).unwrap_err();
+        assert!(matches!(
+            err.downcast_ref::<CliError>(),
+            Some(CliError::UnformattedBlueprint(1))
+        ));
+        assert_eq!(read_to_string(&path).unwrap(), source);
+
+        remove_dir_all(&dir).unwrap();
+    }
+}
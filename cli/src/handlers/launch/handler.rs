@@ -0,0 +1,45 @@
+use crate::{error::*, handlers::CliHandler};
+use anyhow::{bail, Result};
+use clap::ArgMatches;
+use rush_ecs_manifest::{Chain, Manifest};
+use rush_ecs_sdk::bevy::BevySDK;
+use std::path::Path;
+
+pub struct LaunchHandler;
+
+/// Rush Launch Command
+///
+/// Launches the deployed World of the current workspace.
+/// Before launch, only the World authority can spawn,
+/// update and despawn Instances. Also resumes a paused
+/// World
+///
+/// # Examples
+///
+/// ```bash
+/// rush launch
+/// ```
+///
+impl CliHandler for LaunchHandler {
+    async fn handle_matches(_matches: &ArgMatches) -> Result<()> {
+        if !Path::new("./Rush.toml").exists() {
+            bail!(CliError::NotRushWorkspace)
+        }
+        if !Path::new("./blueprint").exists() {
+            bail!(CliError::MissingBlueprint)
+        }
+
+        let manifest = Manifest::from_toml("./Rush.toml")?;
+
+        let Chain::Solana {
+            store,
+            rpc,
+            keypair,
+        } = manifest.chain;
+        let mut sdk = BevySDK::new(rpc, &store, "./blueprint", &keypair);
+
+        sdk.launch()?;
+
+        Ok(())
+    }
+}
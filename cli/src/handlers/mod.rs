@@ -4,8 +4,23 @@ pub use adapter::*;
 mod deploy;
 pub use deploy::*;
 
+mod diff;
+pub use diff::*;
+
+mod fmt;
+pub use fmt::*;
+
+mod launch;
+pub use launch::*;
+
 mod new;
 pub use new::*;
 
+mod pause;
+pub use pause::*;
+
+mod pull;
+pub use pull::*;
+
 mod view;
 pub use view::*;
@@ -2,7 +2,9 @@ use crate::{error::*, handlers::CliHandler, utils::print_happy_peepo};
 use anyhow::{bail, Result};
 use clap::ArgMatches;
 use colored::Colorize;
+use rush_ecs_core::blueprint::{Blueprint, ComponentType, ComponentTypeTree};
 use rush_ecs_manifest::{Chain, Manifest};
+use rush_ecs_parser::{toml::TomlEmitter, Emitter};
 use std::{
     fs::{create_dir, File},
     io::Write,
@@ -77,13 +79,35 @@ fn create_blueprint_files(path: &str, folder_name: &str, filename: &str) -> Resu
     create_dir(folder_path)?;
 
     // create default blueprint file
+    let blueprint_string = TomlEmitter::default().emit(&starter_blueprint())?;
     let mut blueprint_file = File::create(file_path)?;
-    blueprint_file
-        .write_all(b"[world]\nname = \"My Onchain World\"\ndescription = \"My Onchain World Description\"\nentities = [\"player\"]\nregions = [\"base\"]\n\n[entity]\nplayer = { name = \"String\", x =\"f64\", y = \"f64\"}\n\n[base]")?;
+    blueprint_file.write_all(blueprint_string.as_bytes())?;
 
     Ok(())
 }
 
+/// Blueprint of a new project's World
+fn starter_blueprint() -> Blueprint {
+    let region = String::from("base");
+    let entity = String::from("player");
+
+    let mut blueprint = Blueprint::new(
+        "My Onchain World".to_string(),
+        "My Onchain World Description".to_string(),
+    );
+    blueprint.preload(vec![region.clone()], vec![entity.clone()]);
+
+    let mut component_type_tree = ComponentTypeTree::new();
+    component_type_tree.insert("name".to_string(), ComponentType::String);
+    component_type_tree.insert("x".to_string(), ComponentType::Float);
+    component_type_tree.insert("y".to_string(), ComponentType::Float);
+    blueprint.add_entity(entity.clone(), component_type_tree);
+
+    blueprint.add_region(region, vec![entity]);
+
+    blueprint
+}
+
 /// Create project files
 ///
 /// # Examples
@@ -111,7 +135,21 @@ fn create_project_files(manifest: Manifest, path: &str) -> Result<()> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use rush_ecs_parser::{toml::TomlParser, Parser};
 
     #[test]
     fn test_new() {}
+
+    #[test]
+    fn test_starter_blueprint() {
+        let blueprint = starter_blueprint();
+        let blueprint_string = TomlEmitter::default().emit(&blueprint).unwrap();
+
+        let parsed = TomlParser::default()
+            .parse_string(blueprint_string)
+            .unwrap();
+
+        assert_eq!(parsed, blueprint);
+        assert!(parsed.validate().is_empty());
+    }
 }
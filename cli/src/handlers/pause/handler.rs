@@ -0,0 +1,44 @@
+use crate::{error::*, handlers::CliHandler};
+use anyhow::{bail, Result};
+use clap::ArgMatches;
+use rush_ecs_manifest::{Chain, Manifest};
+use rush_ecs_sdk::bevy::BevySDK;
+use std::path::Path;
+
+pub struct PauseHandler;
+
+/// Rush Pause Command
+///
+/// Pauses the deployed World of the current workspace.
+/// Only the World authority can spawn, update and
+/// despawn Instances until `rush launch` resumes it
+///
+/// # Examples
+///
+/// ```bash
+/// rush pause
+/// ```
+///
+impl CliHandler for PauseHandler {
+    async fn handle_matches(_matches: &ArgMatches) -> Result<()> {
+        if !Path::new("./Rush.toml").exists() {
+            bail!(CliError::NotRushWorkspace)
+        }
+        if !Path::new("./blueprint").exists() {
+            bail!(CliError::MissingBlueprint)
+        }
+
+        let manifest = Manifest::from_toml("./Rush.toml")?;
+
+        let Chain::Solana {
+            store,
+            rpc,
+            keypair,
+        } = manifest.chain;
+        let mut sdk = BevySDK::new(rpc, &store, "./blueprint", &keypair);
+
+        sdk.pause()?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,76 @@
+use crate::{error::*, handlers::CliHandler};
+use anyhow::{bail, Result};
+use clap::ArgMatches;
+use colored::Colorize;
+use rush_ecs_manifest::{Chain, Manifest};
+use rush_ecs_parser::{toml::TomlEmitter, Emitter};
+use rush_ecs_sdk::bevy::BevySDK;
+use std::{fs::write, path::Path};
+
+pub struct PullHandler;
+
+/// Rush Pull Command
+///
+/// Rebuilds a deployed World as a Blueprint and writes
+/// it as TOML. Instances keep their onchain nonces and
+/// Component types are inferred from the Instances' values
+///
+/// # Arguments
+/// * `WORLD` - World PDA
+/// * `PATH` - Blueprint file to write
+/// * `--store` - Store program ID, defaults to the one in Rush.toml
+/// * `--rpc` - RPC URL, defaults to the one in Rush.toml
+/// * `--force` - Overwrite `PATH` if it exists
+///
+/// # Examples
+///
+/// ```bash
+/// rush pull <WORLD_PDA> snapshot.toml
+/// ```
+///
+impl CliHandler for PullHandler {
+    async fn handle_matches(matches: &ArgMatches) -> Result<()> {
+        let Some(world) = matches.get_one::<String>("WORLD") else {
+            bail!(CliError::MissingArgument("WORLD".to_string()))
+        };
+        let Some(path) = matches.get_one::<String>("PATH") else {
+            bail!(CliError::MissingArgument("PATH".to_string()))
+        };
+
+        if Path::new(path).exists() && !matches.get_flag("FORCE") {
+            bail!(CliError::FileExists(path.to_string()))
+        }
+
+        // fall back to the workspace's chain settings
+        let workspace_chain = match Path::new("./Rush.toml").exists() {
+            true => Some(Manifest::from_toml("./Rush.toml")?.chain),
+            false => None,
+        };
+        let (store, rpc) = match workspace_chain {
+            Some(Chain::Solana { store, rpc, .. }) => (Some(store), Some(rpc)),
+            None => (None, None),
+        };
+
+        let Some(store) = matches.get_one::<String>("STORE").cloned().or(store) else {
+            bail!(CliError::MissingArgument("--store".to_string()))
+        };
+        let Some(rpc) = matches.get_one::<String>("RPC").cloned().or(rpc) else {
+            bail!(CliError::MissingArgument("--rpc".to_string()))
+        };
+
+        let blueprint = BevySDK::pull(rpc, &store, world)?;
+
+        // Instances are written with their onchain nonces
+        let emitter = TomlEmitter::default();
+        write(path, emitter.emit(&blueprint)?)?;
+
+        println!(
+            "[{}] Pulled world {} into {}",
+            "SUCCESS".green().bold(),
+            world,
+            path
+        );
+
+        Ok(())
+    }
+}
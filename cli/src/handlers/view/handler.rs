@@ -1,7 +1,7 @@
-use crate::{error::*, handlers::CliHandler};
+use crate::{error::*, handlers::CliHandler, utils::load_blueprint};
 use anyhow::{bail, Result};
 use clap::ArgMatches;
-use rush_ecs_parser::{toml::TomlParser, Loader};
+use colored::Colorize;
 use std::path::Path;
 
 pub struct ViewHandler;
@@ -13,24 +13,25 @@ impl CliHandler for ViewHandler {
         if !Path::new("./Rush.toml").exists() {
             bail!(CliError::NotRushWorkspace)
         }
-        if !Path::new("./blueprint/world.toml").exists() {
+        if !Path::new("./blueprint").exists() {
             bail!(CliError::MissingBlueprint)
         }
 
-        let loader = Loader::new(TomlParser {});
-        let blueprint_path = Path::new("./blueprint").canonicalize()?;
-        let blueprint = loader.load_blueprint(&blueprint_path)?;
+        let blueprint = load_blueprint(Path::new("./blueprint"))?;
 
         println!("{blueprint}");
 
+        // errors already failed the load, only warnings remain
+        for warning in blueprint.validate() {
+            println!("[{}] {warning}", "WARNING".yellow().bold());
+        }
+
         Ok(())
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use super::*;
-
     #[test]
     fn test_view() {}
 }
@@ -3,8 +3,11 @@ mod handlers;
 mod utils;
 
 use anyhow::Result;
-use clap::{Arg, Command};
-use handlers::{CliHandler, DeployHandler, NewHandler, ViewHandler};
+use clap::{Arg, ArgAction, Command};
+use handlers::{
+    CliHandler, DeployHandler, DiffHandler, FmtHandler, LaunchHandler, NewHandler, PauseHandler,
+    PullHandler, ViewHandler,
+};
 
 #[tokio::main]
 async fn main() -> Result<()> {
@@ -24,11 +27,41 @@ async fn main() -> Result<()> {
         .subcommand(
             Command::new("deploy")
                 .about("Deploy current Rush project")
+                .arg(Arg::new("DRY_RUN").help("Only show the changes deploying would make.").long("dry-run").action(ArgAction::SetTrue))
+        )
+        .subcommand(
+            Command::new("launch")
+                .about("Launch the deployed World, opening it to players. Also resumes a paused World")
+        )
+        .subcommand(
+            Command::new("pause")
+                .about("Pause the deployed World until it's launched again")
+        )
+        .subcommand(
+            Command::new("pull")
+                .about("Rebuilds a deployed World as a Blueprint TOML file")
+                .arg(Arg::new("WORLD").help("World PDA.").required(true))
+                .arg(Arg::new("PATH").help("Blueprint file to write.").required(true))
+                .arg(Arg::new("STORE").help("Store program ID. Defaults to the one in Rush.toml.").long("store"))
+                .arg(Arg::new("RPC").help("RPC URL. Defaults to the one in Rush.toml.").long("rpc"))
+                .arg(Arg::new("FORCE").help("Overwrite PATH if it exists.").long("force").action(ArgAction::SetTrue))
         )
         .subcommand(
             Command::new("view")
                 .about("Displays the Blueprint in the current workspace")
         )
+        .subcommand(
+            Command::new("diff")
+                .about("Displays what changed between two Blueprints")
+                .arg(Arg::new("A").help("Old Blueprint file or directory.").required(true))
+                .arg(Arg::new("B").help("New Blueprint file or directory.").required(true))
+        )
+        .subcommand(
+            Command::new("fmt")
+                .about("Rewrites the Blueprint files in the current workspace in canonical form")
+                .arg(Arg::new("CHECK").help("Check if the Blueprint files are formatted without rewriting them.").long("check").action(ArgAction::SetTrue))
+                .arg(Arg::new("STRIP_COMMENTS").help("Rewrite Blueprint files with comments, dropping the comments.").long("strip-comments").action(ArgAction::SetTrue))
+        )
         // TODO: Config Subcommand
         // .subcommand(
         //     Command::new("config")
@@ -63,7 +96,12 @@ async fn main() -> Result<()> {
     match top_level_matches.subcommand() {
         Some(("new", sub_matches)) => NewHandler::handle_matches(sub_matches).await,
         Some(("deploy", sub_matches)) => DeployHandler::handle_matches(sub_matches).await,
+        Some(("launch", sub_matches)) => LaunchHandler::handle_matches(sub_matches).await,
+        Some(("pause", sub_matches)) => PauseHandler::handle_matches(sub_matches).await,
+        Some(("pull", sub_matches)) => PullHandler::handle_matches(sub_matches).await,
         Some(("view", sub_matches)) => ViewHandler::handle_matches(sub_matches).await,
+        Some(("diff", sub_matches)) => DiffHandler::handle_matches(sub_matches).await,
+        Some(("fmt", sub_matches)) => FmtHandler::handle_matches(sub_matches).await,
         // Some(("config", sub_matches)) => {}
 
         // impossible to reach due to arg_required_else_help()
@@ -1,4 +1,27 @@
-pub fn print_header() {}
+use crate::error::CliError;
+use anyhow::{bail, Result};
+use rush_ecs_core::blueprint::Blueprint;
+use rush_ecs_parser::{error::ParseErrors, Loader};
+use std::path::Path;
+
+/// Load the Blueprint at `path`
+///
+/// Prints every parse error found in the Blueprint
+/// before failing
+pub fn load_blueprint(path: &Path) -> Result<Blueprint> {
+    let loader = Loader::default();
+
+    match loader.load_blueprint(path) {
+        Ok(blueprint) => Ok(blueprint),
+        Err(e) => match e.downcast_ref::<ParseErrors>() {
+            Some(errors) => {
+                eprintln!("{}\n", errors.render());
+                bail!(CliError::InvalidBlueprint)
+            }
+            None => Err(e),
+        },
+    }
+}
 
 pub fn print_happy_peepo() {
     println!(
@@ -1,10 +1,15 @@
-use crate::error::CoreError;
+use crate::{
+    constraint::ConstraintTree,
+    error::CoreError,
+    numeric::{Fixed, MAX_SCALE},
+};
 use anyhow::{bail, Result};
 use borsh::{BorshDeserialize, BorshSerialize};
 use std::{
     cmp::{Eq, PartialEq},
     collections::BTreeMap,
-    mem::discriminant,
+    fmt::{self, Display},
+    str::FromStr,
 };
 
 // TODO: Consider using structs for Region and Entity
@@ -12,20 +17,312 @@ use std::{
 pub type Region = String;
 pub type Entity = String;
 pub type Component = String;
+/// 1-based identifier of an Instance, matching the onchain nonce
+pub type Nonce = u64;
 pub type ComponentPair = (Component, ComponentValue);
 pub type ComponentTree = BTreeMap<Component, ComponentValue>;
-pub type ComponentType = String;
 pub type ComponentTypeTree = BTreeMap<Component, ComponentType>;
 pub type BlueprintString = String;
 
+/// Enum defining the supported data types of a Component
+/// in an Entity's schema
+///
+/// Each variant maps to a [`ComponentValue`] variant and is
+/// written in the Blueprint as its Rust type name, collections
+/// are written as `[T]`, `Map<String, T>` and `{ field: T }`
+#[derive(Clone, BorshDeserialize, BorshSerialize, Debug, Eq, Hash, PartialEq)]
+pub enum ComponentType {
+    /// `String`
+    String,
+    /// `i64`
+    Integer,
+    /// `f64`
+    Float,
+    /// `bool`
+    Boolean,
+    /// `[T]`
+    List(Box<ComponentType>),
+    /// `Map<String, T>`
+    Map(Box<ComponentType>),
+    /// `{ field: T, ... }`
+    Struct(BTreeMap<String, ComponentType>),
+    /// `u8`
+    U8,
+    /// `u32`
+    U32,
+    /// `u64`
+    U64,
+    /// `Fixed<N>`, a decimal with `N` decimal places
+    Fixed(u8),
+}
+
+impl ComponentType {
+    /// Returns the [`ComponentValue`] holding the type's default
+    ///
+    /// Collections default to empty, Structs to the
+    /// default of each field
+    pub fn default_value(&self) -> ComponentValue {
+        match self {
+            ComponentType::String => ComponentValue::String(String::default()),
+            ComponentType::Integer => ComponentValue::Integer(i64::default()),
+            ComponentType::Float => ComponentValue::Float(f64::default()),
+            ComponentType::Boolean => ComponentValue::Boolean(bool::default()),
+            ComponentType::U8 => ComponentValue::U8(u8::default()),
+            ComponentType::U32 => ComponentValue::U32(u32::default()),
+            ComponentType::U64 => ComponentValue::U64(u64::default()),
+            ComponentType::Fixed(scale) => {
+                ComponentValue::Fixed(Fixed::new(0, *scale).unwrap_or_default())
+            }
+            ComponentType::List(_) => ComponentValue::List(Vec::new()),
+            ComponentType::Map(_) => ComponentValue::Map(BTreeMap::new()),
+            ComponentType::Struct(fields) => ComponentValue::Struct(
+                fields
+                    .iter()
+                    .map(|(k, t)| (k.clone(), t.default_value()))
+                    .collect(),
+            ),
+        }
+    }
+
+    /// Is `true` if the [`ComponentValue`] is of this type
+    ///
+    /// Collections match when every element matches,
+    /// Structs when they have exactly the declared fields
+    pub fn matches(&self, value: &ComponentValue) -> bool {
+        use ComponentType as T;
+        use ComponentValue as V;
+
+        match (self, value) {
+            (T::String, V::String(_))
+            | (T::Integer, V::Integer(_))
+            | (T::Float, V::Float(_))
+            | (T::Boolean, V::Boolean(_))
+            | (T::U8, V::U8(_))
+            | (T::U32, V::U32(_))
+            | (T::U64, V::U64(_)) => true,
+            (T::Fixed(scale), V::Fixed(v)) => v.scale() == *scale,
+            (T::List(t), V::List(values)) => values.iter().all(|v| t.matches(v)),
+            (T::Map(t), V::Map(values)) => values.values().all(|v| t.matches(v)),
+            (T::Struct(fields), V::Struct(values)) => {
+                fields.len() == values.len()
+                    && fields
+                        .iter()
+                        .all(|(k, t)| values.get(k).is_some_and(|v| t.matches(v)))
+            }
+            _ => false,
+        }
+    }
+
+    /// Convert parsed values into the variant this type
+    /// expects, when it can be done exactly
+    ///
+    /// Blueprint formats write Maps and Structs as tables and
+    /// have no unsigned or decimal literals, so parsed values
+    /// are Maps, `i64`s, `f64`s or Strings until they meet
+    /// their schema
+    pub fn conform(&self, value: ComponentValue) -> ComponentValue {
+        match (self, value) {
+            (ComponentType::U8 | ComponentType::U32 | ComponentType::U64, value) => {
+                let parsed = match &value {
+                    // u64 past i64::MAX is written as a string
+                    ComponentValue::String(v) => v.parse::<u64>().ok().map(ComponentValue::U64),
+                    v => Some(v.clone()),
+                };
+                parsed.and_then(|v| v.checked_cast(self)).unwrap_or(value)
+            }
+            (ComponentType::Fixed(scale), value) => {
+                let parsed = match &value {
+                    // decimals are parsed from their shortest representation
+                    ComponentValue::String(v) => Fixed::parse(v, *scale).map(ComponentValue::Fixed),
+                    ComponentValue::Float(v) => {
+                        Fixed::parse(&v.to_string(), *scale).map(ComponentValue::Fixed)
+                    }
+                    v => v.checked_cast(self),
+                };
+                parsed.unwrap_or(value)
+            }
+            (ComponentType::List(t), ComponentValue::List(values)) => {
+                ComponentValue::List(values.into_iter().map(|v| t.conform(v)).collect())
+            }
+            (ComponentType::Map(t), ComponentValue::Map(values)) => {
+                ComponentValue::Map(values.into_iter().map(|(k, v)| (k, t.conform(v))).collect())
+            }
+            (
+                ComponentType::Struct(fields),
+                ComponentValue::Map(values) | ComponentValue::Struct(values),
+            ) => {
+                ComponentValue::Struct(
+                    values
+                        .into_iter()
+                        .map(|(k, v)| match fields.get(&k) {
+                            Some(t) => (k, t.conform(v)),
+                            // undeclared fields are reported by validation
+                            None => (k, v),
+                        })
+                        .collect(),
+                )
+            }
+            (_, value) => value,
+        }
+    }
+}
+
+impl FromStr for ComponentType {
+    type Err = CoreError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        use ComponentType::*;
+
+        let unknown = || CoreError::UnknownComponentType(s.to_string());
+        let s = s.trim();
+
+        match s {
+            "String" => Ok(String),
+            "i64" => Ok(Integer),
+            "f64" => Ok(Float),
+            "bool" => Ok(Boolean),
+            "u8" => Ok(U8),
+            "u32" => Ok(U32),
+            "u64" => Ok(U64),
+
+            // Fixed<N>
+            _ if s.starts_with("Fixed<") && s.ends_with('>') => {
+                match s[6..s.len() - 1].trim().parse::<u8>() {
+                    Ok(scale) if scale <= MAX_SCALE => Ok(Fixed(scale)),
+                    _ => Err(unknown()),
+                }
+            }
+
+            // [T]
+            _ if s.starts_with('[') && s.ends_with(']') => {
+                let element = s[1..s.len() - 1]
+                    .parse::<ComponentType>()
+                    .map_err(|_| unknown())?;
+                Ok(List(Box::new(element)))
+            }
+
+            // Map<String, T>
+            _ if s.starts_with("Map<") && s.ends_with('>') => {
+                match split_top_level(&s[4..s.len() - 1]).as_slice() {
+                    [key, value] if key.trim() == "String" => {
+                        let value = value.parse::<ComponentType>().map_err(|_| unknown())?;
+                        Ok(Map(Box::new(value)))
+                    }
+                    _ => Err(unknown()),
+                }
+            }
+
+            // { field: T, ... }
+            _ if s.starts_with('{') && s.ends_with('}') => {
+                let mut fields = BTreeMap::new();
+                for field in split_top_level(&s[1..s.len() - 1]) {
+                    let Some((name, field_type)) = field.split_once(':') else {
+                        return Err(unknown());
+                    };
+
+                    let name = name.trim();
+                    if name.is_empty() || fields.contains_key(name) {
+                        return Err(unknown());
+                    }
+
+                    let field_type = field_type.parse::<ComponentType>().map_err(|_| unknown())?;
+                    fields.insert(name.to_string(), field_type);
+                }
+
+                if fields.is_empty() {
+                    return Err(unknown());
+                }
+
+                Ok(Struct(fields))
+            }
+
+            _ => Err(unknown()),
+        }
+    }
+}
+
+/// Split a type or value list on the commas that are not
+/// nested in another type or quoted, ignoring a trailing comma
+pub(crate) fn split_top_level(s: &str) -> Vec<&str> {
+    let mut parts = Vec::new();
+    let mut depth = 0;
+    let mut start = 0;
+    let mut in_string = false;
+    let mut escaped = false;
+
+    for (i, c) in s.char_indices() {
+        if in_string {
+            match c {
+                _ if escaped => escaped = false,
+                '\\' => escaped = true,
+                '"' => in_string = false,
+                _ => {}
+            }
+            continue;
+        }
+
+        match c {
+            '"' => in_string = true,
+            '[' | '{' | '<' => depth += 1,
+            ']' | '}' | '>' => depth -= 1,
+            ',' if depth == 0 => {
+                parts.push(&s[start..i]);
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+
+    if !s[start..].trim().is_empty() {
+        parts.push(&s[start..]);
+    }
+
+    parts
+}
+
+impl Display for ComponentType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ComponentType::String => write!(f, "String"),
+            ComponentType::Integer => write!(f, "i64"),
+            ComponentType::Float => write!(f, "f64"),
+            ComponentType::Boolean => write!(f, "bool"),
+            ComponentType::U8 => write!(f, "u8"),
+            ComponentType::U32 => write!(f, "u32"),
+            ComponentType::U64 => write!(f, "u64"),
+            ComponentType::Fixed(scale) => write!(f, "Fixed<{scale}>"),
+            ComponentType::List(t) => write!(f, "[{t}]"),
+            ComponentType::Map(t) => write!(f, "Map<String, {t}>"),
+            ComponentType::Struct(fields) => {
+                let fields = fields
+                    .iter()
+                    .map(|(k, t)| format!("{k}: {t}"))
+                    .collect::<Vec<_>>();
+                write!(f, "{{ {} }}", fields.join(", "))
+            }
+        }
+    }
+}
+
 /// Enum defining the supported dataset in the World
 /// and how it maps with Rust data types
+///
+// @dev
+// new variants are appended to keep the Borsh layout
+// of Instances already stored on-chain
 #[derive(Clone, BorshDeserialize, BorshSerialize, Debug)]
 pub enum ComponentValue {
     String(String),
     Integer(i64),
     Float(f64),
     Boolean(bool),
+    List(Vec<ComponentValue>),
+    Map(BTreeMap<String, ComponentValue>),
+    Struct(BTreeMap<String, ComponentValue>),
+    U8(u8),
+    U32(u32),
+    U64(u64),
+    Fixed(Fixed),
 }
 
 impl ComponentValue {
@@ -80,6 +377,71 @@ impl ComponentValue {
             _ => panic!("Not a Boolean"),
         }
     }
+
+    /// Unwraps Enum to its parameter value
+    ///
+    /// Get Vec inside Enum parameter. Panics if
+    /// the ComponentValue is not a List
+    pub fn unwrap_list(self) -> Vec<ComponentValue> {
+        use ComponentValue::*;
+
+        match self {
+            List(v) => v,
+            _ => panic!("Not a List"),
+        }
+    }
+
+    /// Unwraps Enum to its parameter value
+    ///
+    /// Get BTreeMap inside Enum parameter. Panics if
+    /// the ComponentValue is not a Map
+    pub fn unwrap_map(self) -> BTreeMap<String, ComponentValue> {
+        use ComponentValue::*;
+
+        match self {
+            Map(v) => v,
+            _ => panic!("Not a Map"),
+        }
+    }
+
+    /// Unwraps Enum to its parameter value
+    ///
+    /// Get fields inside Enum parameter. Panics if
+    /// the ComponentValue is not a Struct
+    pub fn unwrap_struct(self) -> BTreeMap<String, ComponentValue> {
+        use ComponentValue::*;
+
+        match self {
+            Struct(v) => v,
+            _ => panic!("Not a Struct"),
+        }
+    }
+}
+
+impl Display for ComponentValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ComponentValue::String(v) => write!(f, "{v}"),
+            ComponentValue::Integer(v) => write!(f, "{v}"),
+            ComponentValue::Float(v) => write!(f, "{v}"),
+            ComponentValue::Boolean(v) => write!(f, "{v}"),
+            ComponentValue::U8(v) => write!(f, "{v}"),
+            ComponentValue::U32(v) => write!(f, "{v}"),
+            ComponentValue::U64(v) => write!(f, "{v}"),
+            ComponentValue::Fixed(v) => write!(f, "{v}"),
+            ComponentValue::List(values) => {
+                let values = values.iter().map(|v| v.to_string()).collect::<Vec<_>>();
+                write!(f, "[{}]", values.join(", "))
+            }
+            ComponentValue::Map(values) | ComponentValue::Struct(values) => {
+                let values = values
+                    .iter()
+                    .map(|(k, v)| format!("{k}: {v}"))
+                    .collect::<Vec<_>>();
+                write!(f, "{{ {} }}", values.join(", "))
+            }
+        }
+    }
 }
 
 impl PartialEq for ComponentValue {
@@ -116,6 +478,62 @@ impl PartialEq for ComponentValue {
                 }
                 is_equal
             }
+
+            ComponentValue::List(v_self) => {
+                let mut is_equal = false;
+                if let ComponentValue::List(v_other) = other {
+                    is_equal = v_self == v_other;
+                }
+                is_equal
+            }
+
+            ComponentValue::Map(v_self) => {
+                let mut is_equal = false;
+                if let ComponentValue::Map(v_other) = other {
+                    is_equal = v_self == v_other;
+                }
+                is_equal
+            }
+
+            ComponentValue::Struct(v_self) => {
+                let mut is_equal = false;
+                if let ComponentValue::Struct(v_other) = other {
+                    is_equal = v_self == v_other;
+                }
+                is_equal
+            }
+
+            ComponentValue::U8(v_self) => {
+                let mut is_equal = false;
+                if let ComponentValue::U8(v_other) = other {
+                    is_equal = v_self == v_other;
+                }
+                is_equal
+            }
+
+            ComponentValue::U32(v_self) => {
+                let mut is_equal = false;
+                if let ComponentValue::U32(v_other) = other {
+                    is_equal = v_self == v_other;
+                }
+                is_equal
+            }
+
+            ComponentValue::U64(v_self) => {
+                let mut is_equal = false;
+                if let ComponentValue::U64(v_other) = other {
+                    is_equal = v_self == v_other;
+                }
+                is_equal
+            }
+
+            ComponentValue::Fixed(v_self) => {
+                let mut is_equal = false;
+                if let ComponentValue::Fixed(v_other) = other {
+                    is_equal = v_self == v_other;
+                }
+                is_equal
+            }
         }
     }
 }
@@ -136,8 +554,20 @@ pub struct Blueprint {
     pub regions: BTreeMap<Region, Vec<Entity>>,
     /// Entity name and Names of its Components
     pub entities: BTreeMap<Entity, ComponentTypeTree>,
+    /// Entity name and the default values declared
+    /// for its Components
+    pub defaults: BTreeMap<Entity, ComponentTree>,
+    /// Entity name and the Constraints declared
+    /// for its Components
+    pub constraints: BTreeMap<Entity, ConstraintTree>,
     /// Instances of different Entities in different Regions
-    pub instances: BTreeMap<Region, BTreeMap<Entity, Vec<ComponentTree>>>,
+    /// keyed by their Nonce
+    pub instances: BTreeMap<Region, BTreeMap<Entity, BTreeMap<Nonce, ComponentTree>>>,
+    /// Last Nonce issued per Region and Entity
+    ///
+    /// Nonces of removed Instances are tombstoned here
+    /// and never issued again, same as onchain
+    pub nonces: BTreeMap<Region, BTreeMap<Entity, Nonce>>,
 }
 
 impl Blueprint {
@@ -146,8 +576,11 @@ impl Blueprint {
             name: world_name,
             description: world_description,
             entities: BTreeMap::new(),
+            defaults: BTreeMap::new(),
+            constraints: BTreeMap::new(),
             regions: BTreeMap::new(),
             instances: BTreeMap::new(),
+            nonces: BTreeMap::new(),
         }
     }
 
@@ -160,11 +593,14 @@ impl Blueprint {
         for region_name in regions.into_iter() {
             // preload Regions
             self.instances.insert(region_name.clone(), BTreeMap::new());
+            self.nonces.insert(region_name.clone(), BTreeMap::new());
 
             // preload Entities
             let region_mut = self.instances.get_mut(&region_name).unwrap();
+            let nonces_mut = self.nonces.get_mut(&region_name).unwrap();
             for entity_name in entities.clone().into_iter() {
-                region_mut.insert(entity_name, Vec::new());
+                region_mut.insert(entity_name.clone(), BTreeMap::new());
+                nonces_mut.insert(entity_name, 0);
             }
         }
     }
@@ -173,16 +609,41 @@ impl Blueprint {
         self.entities.insert(name, component_types);
     }
 
+    /// Declare default values for an Entity's Components
+    ///
+    /// Components without a declared default use the
+    /// default of their [`ComponentType`]
+    pub fn add_defaults(&mut self, entity: Entity, defaults: ComponentTree) {
+        if !defaults.is_empty() {
+            self.defaults.insert(entity, defaults);
+        }
+    }
+
+    /// Declare [`Constraint`](crate::constraint::Constraint)s on an Entity's Components
+    pub fn add_constraints(&mut self, entity: Entity, constraints: ConstraintTree) {
+        let constraints = constraints
+            .into_iter()
+            .filter(|(_, c)| !c.is_empty())
+            .collect::<ConstraintTree>();
+
+        if !constraints.is_empty() {
+            self.constraints.insert(entity, constraints);
+        }
+    }
+
     pub fn add_region(&mut self, name: Region, entities: Vec<Entity>) {
         self.regions.insert(name, entities);
     }
 
+    /// Add Instance under the next Nonce
+    ///
+    /// Returns the Nonce of the Instance
     pub fn add_instance(
         &mut self,
         region: Region,
         entity: Entity,
         component_tree: ComponentTree,
-    ) -> Result<()> {
+    ) -> Result<Nonce> {
         // get mutable region
         let region_mut = match self.instances.get_mut(&region) {
             // instance exists
@@ -200,19 +661,71 @@ impl Blueprint {
         };
 
         // add entity instance to blueprint under region
-        entity_mut.push(component_tree);
+        let nonce = next_nonce(&mut self.nonces, &region, &entity);
+        entity_mut.insert(nonce, component_tree);
+
+        Ok(nonce)
+    }
+
+    /// Add Instance under a known Nonce
+    ///
+    /// Used for Instances that were already issued a Nonce,
+    /// e.g. onchain. Later Nonces are issued after it
+    pub fn insert_instance(
+        &mut self,
+        region: Region,
+        entity: Entity,
+        nonce: Nonce,
+        component_tree: ComponentTree,
+    ) -> Result<()> {
+        // get mutable region
+        let region_mut = match self.instances.get_mut(&region) {
+            Some(e) => e,
+            None => bail!(CoreError::RegionNotFound),
+        };
+
+        // get mutable entity
+        let entity_mut = match region_mut.get_mut(&entity) {
+            Some(e) => e,
+            None => bail!(CoreError::EntityNotFound),
+        };
+
+        // nonces are 1-based
+        if nonce == 0 {
+            bail!(CoreError::InstanceNotFound)
+        }
+        if entity_mut.contains_key(&nonce) {
+            bail!(CoreError::InstanceExists)
+        }
+
+        entity_mut.insert(nonce, component_tree);
+        self.reserve_nonce(region, entity, nonce);
 
         Ok(())
     }
 
+    /// Mark Nonces up to `nonce` as issued for a Region and Entity
+    ///
+    /// Keeps the Nonces of removed Instances tombstoned,
+    /// the last issued Nonce never goes down
+    pub fn reserve_nonce(&mut self, region: Region, entity: Entity, nonce: Nonce) {
+        let last = self
+            .nonces
+            .entry(region)
+            .or_default()
+            .entry(entity)
+            .or_default();
+        *last = (*last).max(nonce);
+    }
+
     // TODO: Add Validations
     /// Add Instance with default values
     ///
     /// Fetches the component tree structure from self.entities
-    /// and creates an Instance with default values according
-    /// to the Entity's ComponentTypeTree
+    /// and creates an Instance with the Entity's declared
+    /// defaults, or the defaults of its ComponentTypeTree
     ///
-    pub fn add_default_instance(&mut self, region: Region, entity: Entity) -> Result<u64> {
+    pub fn add_default_instance(&mut self, region: Region, entity: Entity) -> Result<Nonce> {
         // get mutable region
         let region_mut = match self.instances.get_mut(&region) {
             // instance exists
@@ -229,113 +742,102 @@ impl Blueprint {
             None => bail!(CoreError::EntityNotFound),
         };
 
-        // get entity component type tree
-        let component_type_tree = self.entities.get(&entity).unwrap();
-
         // populate component tree based on type tree
-        let mut component_tree: ComponentTree = BTreeMap::new();
-        for k in component_type_tree.keys() {
-            let v_type = component_type_tree.get(k).unwrap().clone();
-
-            let v: ComponentValue;
-            if v_type == "String" {
-                v = ComponentValue::String(String::default())
-            } else if v_type == "f64" {
-                v = ComponentValue::Float(f64::default())
-            } else if v_type == "i64" {
-                v = ComponentValue::Integer(i64::default())
-            } else if v_type == "bool" {
-                v = ComponentValue::Boolean(bool::default())
-            } else {
-                bail!(CoreError::UnsupportedDataType);
+        let component_tree = match self.entities.get(&entity) {
+            Some(component_type_tree) => {
+                default_component_tree(component_type_tree, self.defaults.get(&entity))
             }
-
-            // push type default into component tree
-            component_tree.insert(k.to_string(), v);
-        }
+            None => bail!(CoreError::EntityNotFound),
+        };
 
         // add instance
-        entity_mut.push(component_tree);
+        let nonce = next_nonce(&mut self.nonces, &region, &entity);
+        entity_mut.insert(nonce, component_tree);
 
-        // return index (nonce) of instance
-        Ok(entity_mut.len() as u64)
+        Ok(nonce)
     }
 
     /// Return Instance with default values
     ///
     /// Fetches the component tree structure from self.entities
-    /// and creates an Instance with default values according
-    /// to the Entity's ComponentTypeTree
+    /// and creates an Instance with the Entity's declared
+    /// defaults, or the defaults of its ComponentTypeTree
     ///
     pub fn get_default_components(&self, entity: &Entity) -> Result<ComponentTree> {
         // get entity component type tree
-        let component_type_tree = self.entities.get(entity).unwrap();
-
-        // populate component tree based on type tree
-        let mut component_tree: ComponentTree = BTreeMap::new();
-        for k in component_type_tree.keys() {
-            let v_type = component_type_tree.get(k).unwrap().clone();
-
-            let v: ComponentValue;
-            if v_type == "String" {
-                v = ComponentValue::String(String::default())
-            } else if v_type == "f64" {
-                v = ComponentValue::Float(f64::default())
-            } else if v_type == "i64" {
-                v = ComponentValue::Integer(i64::default())
-            } else if v_type == "bool" {
-                v = ComponentValue::Boolean(bool::default())
-            } else {
-                bail!(CoreError::UnsupportedDataType);
-            }
-
-            // push type default into component tree
-            component_tree.insert(k.to_string(), v);
-        }
+        let component_type_tree = match self.entities.get(entity) {
+            Some(t) => t,
+            None => bail!(CoreError::EntityNotFound),
+        };
 
-        // return component tree
-        Ok(component_tree)
+        // return component tree populated based on type tree
+        Ok(default_component_tree(
+            component_type_tree,
+            self.defaults.get(entity),
+        ))
     }
 
-    pub fn get_instance(
+    /// Remove an Instance
+    ///
+    /// Its Nonce is tombstoned and is never issued again,
+    /// so the Nonces of the remaining Instances stay stable
+    pub fn remove_instance(
         &mut self,
         region: Region,
         entity: Entity,
-        nonce: u64,
+        nonce: Nonce,
     ) -> Result<ComponentTree> {
         // get mutable region
         let region_mut = match self.instances.get_mut(&region) {
-            // instance exists
             Some(e) => e,
-            // insert and get, if not exists
             None => bail!(CoreError::RegionNotFound),
         };
 
         // get mutable entity
         let entity_mut = match region_mut.get_mut(&entity) {
-            // instance exists
             Some(e) => e,
-            // insert and get, if not exists
             None => bail!(CoreError::EntityNotFound),
         };
 
-        // get instance component tree
-        let instance = entity_mut[nonce as usize].clone();
+        match entity_mut.remove(&nonce) {
+            Some(instance) => Ok(instance),
+            None => bail!(CoreError::InstanceNotFound),
+        }
+    }
 
-        Ok(instance)
+    pub fn get_instance(
+        &self,
+        region: Region,
+        entity: Entity,
+        nonce: Nonce,
+    ) -> Result<ComponentTree> {
+        // get region
+        let region_ref = match self.instances.get(&region) {
+            Some(e) => e,
+            None => bail!(CoreError::RegionNotFound),
+        };
+
+        // get entity
+        let entity_ref = match region_ref.get(&entity) {
+            Some(e) => e,
+            None => bail!(CoreError::EntityNotFound),
+        };
+
+        // get instance component tree
+        match entity_ref.get(&nonce) {
+            Some(instance) => Ok(instance.clone()),
+            None => bail!(CoreError::InstanceNotFound),
+        }
     }
 
     pub fn get_component_value(
-        &mut self,
+        &self,
         region: Region,
         entity: Entity,
-        nonce: u64,
+        nonce: Nonce,
         component: Component,
     ) -> Result<ComponentValue> {
-        let component_tree = match self.get_instance(region, entity, nonce) {
-            Ok(ct) => ct,
-            Err(e) => bail!("{}: {}", CoreError::InstanceNotFound, e),
-        };
+        let component_tree = self.get_instance(region, entity, nonce)?;
 
         let option_value = component_tree.get(&component);
 
@@ -345,14 +847,55 @@ impl Blueprint {
         }
     }
 
+    /// Check a value against the Entity's schema
+    ///
+    /// Fails if the Component is not in the Entity's schema,
+    /// the value is of a different type or it violates the
+    /// Component's [`Constraint`](crate::constraint::Constraint)
+    pub fn check_component_value(
+        &self,
+        entity: &Entity,
+        component: &Component,
+        value: &ComponentValue,
+    ) -> Result<()> {
+        // get the Entity's schema for the component
+        let component_type = match self.entities.get(entity) {
+            Some(component_type_tree) => match component_type_tree.get(component) {
+                Some(t) => t.clone(),
+                None => bail!(CoreError::ComponentNotFound),
+            },
+            None => bail!(CoreError::EntityNotFound),
+        };
+
+        if !component_type.matches(value) {
+            bail!(CoreError::MismatchedDataType)
+        }
+
+        let constraint = self.constraints.get(entity).and_then(|c| c.get(component));
+        if let Some(constraint) = constraint {
+            if let Err(violation) = constraint.check(value) {
+                bail!(CoreError::ConstraintViolation {
+                    entity: entity.clone(),
+                    component: component.clone(),
+                    violation,
+                })
+            }
+        }
+
+        Ok(())
+    }
+
     pub fn set_component_value(
         &mut self,
         region: Region,
         entity: Entity,
-        nonce: u64,
+        nonce: Nonce,
         component: Component,
         value: ComponentValue,
     ) -> Result<()> {
+        // ensure value follows the Entity's schema
+        self.check_component_value(&entity, &component, &value)?;
+
         // get mutable region
         let region_mut = match self.instances.get_mut(&region) {
             // instance exists
@@ -369,23 +912,227 @@ impl Blueprint {
             None => bail!(CoreError::EntityNotFound),
         };
 
-        let component_tree = &mut entity_mut[nonce as usize];
+        let component_tree = match entity_mut.get_mut(&nonce) {
+            Some(ct) => ct,
+            None => bail!(CoreError::InstanceNotFound),
+        };
 
-        let option_value = component_tree.get_mut(&component);
+        let component_value = match component_tree.get_mut(&component) {
+            Some(v) => v,
+            None => bail!(CoreError::ComponentNotFound),
+        };
 
-        if option_value.is_none() {
-            bail!(CoreError::ComponentNotFound);
-        }
+        *component_value = value;
 
-        let component_value = option_value.unwrap();
+        Ok(())
+    }
+}
 
-        // ensure they're the same ComponentValue variant
-        if discriminant(&value) != discriminant(component_value) {
-            bail!(CoreError::MismatchedDataType)
+/// Issue the Nonce after the last one issued for a Region and Entity
+fn next_nonce(
+    nonces: &mut BTreeMap<Region, BTreeMap<Entity, Nonce>>,
+    region: &Region,
+    entity: &Entity,
+) -> Nonce {
+    let last = nonces
+        .entry(region.clone())
+        .or_default()
+        .entry(entity.clone())
+        .or_default();
+    *last += 1;
+    *last
+}
+
+/// Populate a [`ComponentTree`] with the declared defaults,
+/// falling back to the type defaults of a [`ComponentTypeTree`]
+fn default_component_tree(
+    component_type_tree: &ComponentTypeTree,
+    defaults: Option<&ComponentTree>,
+) -> ComponentTree {
+    component_type_tree
+        .iter()
+        .map(|(k, v_type)| {
+            let value = defaults
+                .and_then(|d| d.get(k))
+                .cloned()
+                .unwrap_or_else(|| v_type.default_value());
+            (k.to_string(), value)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_component_type_from_str() {
+        let component_type = "{ str: i64, dex: i64, bag: [Map<String, f64>] }"
+            .parse::<ComponentType>()
+            .unwrap();
+
+        let mut fields = BTreeMap::new();
+        fields.insert("str".to_string(), ComponentType::Integer);
+        fields.insert("dex".to_string(), ComponentType::Integer);
+        fields.insert(
+            "bag".to_string(),
+            ComponentType::List(Box::new(ComponentType::Map(Box::new(ComponentType::Float)))),
+        );
+        assert_eq!(component_type, ComponentType::Struct(fields));
+
+        // canonical form sorts the fields
+        assert_eq!(
+            component_type.to_string(),
+            "{ bag: [Map<String, f64>], dex: i64, str: i64 }"
+        );
+        assert_eq!(
+            component_type.to_string().parse::<ComponentType>().unwrap(),
+            component_type
+        );
+
+        for invalid in [
+            "[]",
+            "[i64",
+            "Map<i64, i64>",
+            "{}",
+            "{ a: i64, a: i64 }",
+            "{ a }",
+        ] {
+            assert!(invalid.parse::<ComponentType>().is_err(), "{invalid}");
         }
+    }
 
-        *component_value = value;
+    #[test]
+    fn test_component_type_matches() {
+        let component_type = "{ hp: i64, tags: [String] }"
+            .parse::<ComponentType>()
+            .unwrap();
 
-        Ok(())
+        let mut values = BTreeMap::new();
+        values.insert("hp".to_string(), ComponentValue::Integer(10));
+        values.insert(
+            "tags".to_string(),
+            ComponentValue::List(vec![ComponentValue::String("boss".to_string())]),
+        );
+
+        // tables are parsed as Maps until they meet their schema
+        let value = ComponentValue::Map(values.clone());
+        assert!(!component_type.matches(&value));
+        let value = component_type.conform(value);
+        assert_eq!(value, ComponentValue::Struct(values.clone()));
+        assert!(component_type.matches(&value));
+
+        values.remove("tags");
+        assert!(!component_type.matches(&ComponentValue::Struct(values)));
+
+        assert_eq!(
+            component_type.default_value().to_string(),
+            "{ hp: 0, tags: [] }"
+        );
+    }
+
+    #[test]
+    fn test_component_value_borsh() {
+        let mut values = BTreeMap::new();
+        values.insert("x".to_string(), ComponentValue::Float(1.5));
+        let value = ComponentValue::List(vec![
+            ComponentValue::Struct(values.clone()),
+            ComponentValue::Map(values),
+        ]);
+
+        let data = borsh::to_vec(&value).unwrap();
+        assert_eq!(ComponentValue::try_from_slice(&data).unwrap(), value);
+
+        // existing variants keep their layout
+        let data = borsh::to_vec(&ComponentValue::Boolean(true)).unwrap();
+        assert_eq!(data, vec![3, 1]);
+    }
+
+    #[test]
+    fn test_instance_nonces() {
+        let region = "region".to_string();
+        let entity = "entity".to_string();
+        let x = "x".to_string();
+
+        let mut blueprint = Blueprint::new("World".to_string(), "Description".to_string());
+        let mut component_type_tree = ComponentTypeTree::new();
+        component_type_tree.insert(x.clone(), ComponentType::Integer);
+        blueprint.add_entity(entity.clone(), component_type_tree);
+        blueprint.preload(vec![region.clone()], vec![entity.clone()]);
+
+        // nonces are 1-based, same as onchain
+        let first = blueprint
+            .add_default_instance(region.clone(), entity.clone())
+            .unwrap();
+        let second = blueprint
+            .add_default_instance(region.clone(), entity.clone())
+            .unwrap();
+        assert_eq!((first, second), (1, 2));
+
+        blueprint
+            .set_component_value(
+                region.clone(),
+                entity.clone(),
+                second,
+                x.clone(),
+                ComponentValue::Integer(7),
+            )
+            .unwrap();
+
+        // removing an instance keeps the nonces of the rest
+        blueprint
+            .remove_instance(region.clone(), entity.clone(), first)
+            .unwrap();
+        assert_eq!(
+            blueprint
+                .get_component_value(region.clone(), entity.clone(), second, x.clone())
+                .unwrap(),
+            ComponentValue::Integer(7)
+        );
+
+        // removed nonces are never issued again
+        let third = blueprint
+            .add_default_instance(region.clone(), entity.clone())
+            .unwrap();
+        assert_eq!(third, 3);
+
+        // unknown, removed and zero nonces are not found
+        for nonce in [0, first, 4] {
+            let err = blueprint
+                .get_instance(region.clone(), entity.clone(), nonce)
+                .unwrap_err();
+            assert!(matches!(
+                err.downcast_ref::<CoreError>(),
+                Some(CoreError::InstanceNotFound)
+            ));
+        }
+        assert!(blueprint
+            .set_component_value(
+                region.clone(),
+                entity.clone(),
+                first,
+                x,
+                ComponentValue::Integer(1),
+            )
+            .is_err());
+        assert!(blueprint
+            .remove_instance(region.clone(), entity.clone(), first)
+            .is_err());
+
+        // known nonces are kept and issued nonces never go down
+        blueprint
+            .insert_instance(region.clone(), entity.clone(), 7, ComponentTree::new())
+            .unwrap();
+        let err = blueprint
+            .insert_instance(region.clone(), entity.clone(), 7, ComponentTree::new())
+            .unwrap_err();
+        assert!(matches!(
+            err.downcast_ref::<CoreError>(),
+            Some(CoreError::InstanceExists)
+        ));
+        blueprint.reserve_nonce(region.clone(), entity.clone(), 5);
+        assert_eq!(blueprint.nonces[&region][&entity], 7);
+        let eighth = blueprint.add_default_instance(region, entity).unwrap();
+        assert_eq!(eighth, 8);
     }
 }
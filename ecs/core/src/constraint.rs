@@ -0,0 +1,165 @@
+//! Component Constraints
+//!
+//! Bounds on the values a Component can hold, declared
+//! alongside its type in the Entity's schema
+
+use crate::{
+    blueprint::{Component, ComponentValue},
+    numeric::compare,
+};
+use std::collections::BTreeMap;
+use thiserror::Error;
+
+pub type ConstraintTree = BTreeMap<Component, Constraint>;
+
+/// Bounds on the values of a Component
+///
+/// Every bound is optional, a value is valid when it
+/// satisfies all the declared ones
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Constraint {
+    /// Smallest allowed value of a numeric Component
+    pub min: Option<ComponentValue>,
+    /// Largest allowed value of a numeric Component
+    pub max: Option<ComponentValue>,
+    /// Allowed values
+    pub one_of: Option<Vec<ComponentValue>>,
+    /// Largest allowed length of a `String` in characters,
+    /// or of a List in elements
+    pub max_len: Option<usize>,
+}
+
+/// Bound of a [`Constraint`] a value does not satisfy
+#[derive(Clone, Debug, Error, Eq, PartialEq)]
+pub enum ConstraintViolation {
+    #[error("value {value} is less than min {min}")]
+    BelowMin {
+        value: ComponentValue,
+        min: ComponentValue,
+    },
+
+    #[error("value {value} is greater than max {max}")]
+    AboveMax {
+        value: ComponentValue,
+        max: ComponentValue,
+    },
+
+    #[error("value {value} is not one of {}", render_values(.one_of))]
+    NotOneOf {
+        value: ComponentValue,
+        one_of: Vec<ComponentValue>,
+    },
+
+    #[error("length {len} is greater than max_len {max_len}")]
+    TooLong { len: usize, max_len: usize },
+}
+
+fn render_values(values: &[ComponentValue]) -> String {
+    values
+        .iter()
+        .map(|v| v.to_string())
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+impl Constraint {
+    /// Is `true` if no bound is declared
+    pub fn is_empty(&self) -> bool {
+        self == &Constraint::default()
+    }
+
+    /// Check a value against every declared bound
+    pub fn check(&self, value: &ComponentValue) -> Result<(), ConstraintViolation> {
+        if let Some(min) = &self.min {
+            if compare(value, min).is_some_and(|o| o.is_lt()) {
+                return Err(ConstraintViolation::BelowMin {
+                    value: value.clone(),
+                    min: min.clone(),
+                });
+            }
+        }
+
+        if let Some(max) = &self.max {
+            if compare(value, max).is_some_and(|o| o.is_gt()) {
+                return Err(ConstraintViolation::AboveMax {
+                    value: value.clone(),
+                    max: max.clone(),
+                });
+            }
+        }
+
+        if let Some(one_of) = &self.one_of {
+            if !one_of.contains(value) {
+                return Err(ConstraintViolation::NotOneOf {
+                    value: value.clone(),
+                    one_of: one_of.clone(),
+                });
+            }
+        }
+
+        if let Some(max_len) = self.max_len {
+            let len = match value {
+                ComponentValue::String(v) => v.chars().count(),
+                ComponentValue::List(v) => v.len(),
+                _ => 0,
+            };
+
+            if len > max_len {
+                return Err(ConstraintViolation::TooLong { len, max_len });
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_constraint_check() {
+        let constraint = Constraint {
+            min: Some(ComponentValue::Float(0.0)),
+            max: Some(ComponentValue::Float(10.0)),
+            ..Default::default()
+        };
+
+        assert!(constraint.check(&ComponentValue::Float(10.0)).is_ok());
+        assert_eq!(
+            constraint.check(&ComponentValue::Float(10.5)),
+            Err(ConstraintViolation::AboveMax {
+                value: ComponentValue::Float(10.5),
+                max: ComponentValue::Float(10.0),
+            })
+        );
+        assert!(matches!(
+            constraint.check(&ComponentValue::Float(-1.0)),
+            Err(ConstraintViolation::BelowMin { .. })
+        ));
+
+        let constraint = Constraint {
+            one_of: Some(vec![
+                ComponentValue::String("warrior".to_string()),
+                ComponentValue::String("mage".to_string()),
+            ]),
+            max_len: Some(5),
+            ..Default::default()
+        };
+
+        assert!(constraint
+            .check(&ComponentValue::String("mage".to_string()))
+            .is_ok());
+        assert_eq!(
+            constraint
+                .check(&ComponentValue::String("rogue".to_string()))
+                .unwrap_err()
+                .to_string(),
+            "value rogue is not one of warrior, mage"
+        );
+        assert_eq!(
+            constraint.check(&ComponentValue::String("warrior".to_string())),
+            Err(ConstraintViolation::TooLong { len: 7, max_len: 5 })
+        );
+    }
+}
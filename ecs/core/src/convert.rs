@@ -0,0 +1,493 @@
+//! Component Value Conversions
+//!
+//! Fallible conversions between [`ComponentValue`]s and Rust
+//! types, and parsing values from text (e.g. CLI arguments)
+
+use crate::{
+    blueprint::{split_top_level, ComponentType, ComponentValue},
+    error::CoreError,
+    numeric::Fixed,
+};
+use std::{collections::BTreeMap, str::FromStr};
+
+impl ComponentValue {
+    /// Returns the name of the value's type (e.g. `i64`)
+    ///
+    /// Collections are named by their kind (e.g. `list`),
+    /// the type of their elements is not known
+    pub fn type_name(&self) -> &'static str {
+        match self {
+            ComponentValue::String(_) => "String",
+            ComponentValue::Integer(_) => "i64",
+            ComponentValue::Float(_) => "f64",
+            ComponentValue::Boolean(_) => "bool",
+            ComponentValue::List(_) => "list",
+            ComponentValue::Map(_) => "map",
+            ComponentValue::Struct(_) => "struct",
+            ComponentValue::U8(_) => "u8",
+            ComponentValue::U32(_) => "u32",
+            ComponentValue::U64(_) => "u64",
+            ComponentValue::Fixed(_) => "Fixed",
+        }
+    }
+
+    /// Returns the [`ComponentType`] the value was stored as
+    ///
+    /// `None` if a collection is empty, its elements
+    /// could be of any type
+    pub fn infer_type(&self) -> Option<ComponentType> {
+        let component_type = match self {
+            ComponentValue::String(_) => ComponentType::String,
+            ComponentValue::Integer(_) => ComponentType::Integer,
+            ComponentValue::Float(_) => ComponentType::Float,
+            ComponentValue::Boolean(_) => ComponentType::Boolean,
+            ComponentValue::List(v) => ComponentType::List(Box::new(v.first()?.infer_type()?)),
+            ComponentValue::Map(v) => {
+                ComponentType::Map(Box::new(v.values().next()?.infer_type()?))
+            }
+            ComponentValue::Struct(v) => ComponentType::Struct(
+                v.iter()
+                    .map(|(k, v)| Some((k.clone(), v.infer_type()?)))
+                    .collect::<Option<_>>()?,
+            ),
+            ComponentValue::U8(_) => ComponentType::U8,
+            ComponentValue::U32(_) => ComponentType::U32,
+            ComponentValue::U64(_) => ComponentType::U64,
+            ComponentValue::Fixed(v) => ComponentType::Fixed(v.scale()),
+        };
+
+        Some(component_type)
+    }
+
+    /// Returns the `&str` of a String value
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            ComponentValue::String(v) => Some(v),
+            _ => None,
+        }
+    }
+
+    /// Returns the `i64` of an Integer value
+    pub fn as_int(&self) -> Option<i64> {
+        match self {
+            ComponentValue::Integer(v) => Some(*v),
+            _ => None,
+        }
+    }
+
+    /// Returns the `f64` of a Float value
+    pub fn as_float(&self) -> Option<f64> {
+        match self {
+            ComponentValue::Float(v) => Some(*v),
+            _ => None,
+        }
+    }
+
+    /// Returns the `bool` of a Boolean value
+    pub fn as_bool(&self) -> Option<bool> {
+        match self {
+            ComponentValue::Boolean(v) => Some(*v),
+            _ => None,
+        }
+    }
+
+    /// Returns the `u8` of a U8 value
+    pub fn as_u8(&self) -> Option<u8> {
+        match self {
+            ComponentValue::U8(v) => Some(*v),
+            _ => None,
+        }
+    }
+
+    /// Returns the `u32` of a U32 value
+    pub fn as_u32(&self) -> Option<u32> {
+        match self {
+            ComponentValue::U32(v) => Some(*v),
+            _ => None,
+        }
+    }
+
+    /// Returns the `u64` of a U64 value
+    pub fn as_u64(&self) -> Option<u64> {
+        match self {
+            ComponentValue::U64(v) => Some(*v),
+            _ => None,
+        }
+    }
+
+    /// Returns the [`Fixed`] of a Fixed value
+    pub fn as_fixed(&self) -> Option<Fixed> {
+        match self {
+            ComponentValue::Fixed(v) => Some(*v),
+            _ => None,
+        }
+    }
+
+    /// Returns the elements of a List value
+    pub fn as_list(&self) -> Option<&[ComponentValue]> {
+        match self {
+            ComponentValue::List(v) => Some(v),
+            _ => None,
+        }
+    }
+
+    /// Returns the entries of a Map value
+    pub fn as_map(&self) -> Option<&BTreeMap<String, ComponentValue>> {
+        match self {
+            ComponentValue::Map(v) => Some(v),
+            _ => None,
+        }
+    }
+
+    /// Returns the fields of a Struct value
+    pub fn as_struct(&self) -> Option<&BTreeMap<String, ComponentValue>> {
+        match self {
+            ComponentValue::Struct(v) => Some(v),
+            _ => None,
+        }
+    }
+}
+
+/// Implements `From<T> for ComponentValue` and
+/// `TryFrom<ComponentValue> for T`
+macro_rules! impl_conversions {
+    ($($variant:ident($t:ty) => $name:literal,)*) => {
+        $(
+            impl From<$t> for ComponentValue {
+                fn from(value: $t) -> Self {
+                    ComponentValue::$variant(value)
+                }
+            }
+
+            impl TryFrom<ComponentValue> for $t {
+                type Error = CoreError;
+
+                fn try_from(value: ComponentValue) -> Result<Self, Self::Error> {
+                    match value {
+                        ComponentValue::$variant(v) => Ok(v),
+                        v => Err(CoreError::UnexpectedValue {
+                            expected: $name,
+                            found: v.type_name(),
+                        }),
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl_conversions! {
+    String(String) => "String",
+    Integer(i64) => "i64",
+    Float(f64) => "f64",
+    Boolean(bool) => "bool",
+    List(Vec<ComponentValue>) => "list",
+    U8(u8) => "u8",
+    U32(u32) => "u32",
+    U64(u64) => "u64",
+    Fixed(Fixed) => "Fixed",
+}
+
+impl From<&str> for ComponentValue {
+    fn from(value: &str) -> Self {
+        ComponentValue::String(value.to_string())
+    }
+}
+
+impl From<BTreeMap<String, ComponentValue>> for ComponentValue {
+    /// Creates a Map, it becomes a Struct when conformed
+    /// to a Struct schema
+    fn from(value: BTreeMap<String, ComponentValue>) -> Self {
+        ComponentValue::Map(value)
+    }
+}
+
+impl TryFrom<ComponentValue> for BTreeMap<String, ComponentValue> {
+    type Error = CoreError;
+
+    /// Returns the entries of a Map or the fields of a Struct
+    fn try_from(value: ComponentValue) -> Result<Self, Self::Error> {
+        match value {
+            ComponentValue::Map(v) | ComponentValue::Struct(v) => Ok(v),
+            v => Err(CoreError::UnexpectedValue {
+                expected: "map",
+                found: v.type_name(),
+            }),
+        }
+    }
+}
+
+impl FromStr for ComponentValue {
+    type Err = CoreError;
+
+    /// Parse a value, inferring its type
+    ///
+    /// - `true` and `false` are `bool`s
+    /// - integers are `i64`s, or `u64`s past `i64::MAX`
+    /// - decimals are `f64`s
+    /// - `[a, b]` are lists and `{ k: v }` maps
+    /// - quoted or any other text is a String
+    ///
+    /// Use [`ComponentType::parse_value`] to parse against
+    /// a schema type instead
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        let invalid = || CoreError::InvalidValue {
+            expected: "value".to_string(),
+            value: s.to_string(),
+        };
+
+        if let Ok(v) = s.parse::<bool>() {
+            return Ok(ComponentValue::Boolean(v));
+        }
+        if let Ok(v) = s.parse::<i64>() {
+            return Ok(ComponentValue::Integer(v));
+        }
+        if let Ok(v) = s.parse::<u64>() {
+            return Ok(ComponentValue::U64(v));
+        }
+        // skip words f64 parses (e.g. inf, NaN)
+        if s.starts_with(|c: char| c.is_ascii_digit() || c == '-' || c == '+' || c == '.') {
+            if let Ok(v) = s.parse::<f64>() {
+                return match v.is_finite() {
+                    true => Ok(ComponentValue::Float(v)),
+                    false => Err(invalid()),
+                };
+            }
+        }
+
+        if s.starts_with('"') {
+            return unquote(s).map(ComponentValue::String).ok_or_else(invalid);
+        }
+
+        if let Some(inner) = s.strip_prefix('[').and_then(|s| s.strip_suffix(']')) {
+            let values = split_top_level(inner)
+                .into_iter()
+                .map(|v| v.parse::<ComponentValue>())
+                .collect::<Result<Vec<_>, _>>()?;
+            return Ok(ComponentValue::List(values));
+        }
+
+        if let Some(inner) = s.strip_prefix('{').and_then(|s| s.strip_suffix('}')) {
+            let mut values = BTreeMap::new();
+            for (key, value) in parse_entries(inner).ok_or_else(invalid)? {
+                values.insert(key, value.parse::<ComponentValue>()?);
+            }
+            return Ok(ComponentValue::Map(values));
+        }
+
+        Ok(ComponentValue::String(s.to_string()))
+    }
+}
+
+impl ComponentType {
+    /// Parse a value of this type (e.g. `42`, `1.5`, `"bob"`)
+    ///
+    /// Strings may be written without quotes, collections
+    /// as `[a, b]` and `{ k: v }`
+    pub fn parse_value(&self, s: &str) -> Result<ComponentValue, CoreError> {
+        let s = s.trim();
+        let invalid = || CoreError::InvalidValue {
+            expected: self.to_string(),
+            value: s.to_string(),
+        };
+
+        let value = match self {
+            ComponentType::String => match s.starts_with('"') {
+                true => unquote(s).map(ComponentValue::String),
+                false => Some(ComponentValue::String(s.to_string())),
+            },
+            ComponentType::Integer => s.parse().ok().map(ComponentValue::Integer),
+            ComponentType::Float => s.parse().ok().map(ComponentValue::Float),
+            ComponentType::Boolean => s.parse().ok().map(ComponentValue::Boolean),
+            ComponentType::U8 => s.parse().ok().map(ComponentValue::U8),
+            ComponentType::U32 => s.parse().ok().map(ComponentValue::U32),
+            ComponentType::U64 => s.parse().ok().map(ComponentValue::U64),
+            ComponentType::Fixed(scale) => {
+                let s = unquote(s).unwrap_or(s.to_string());
+                Fixed::parse(&s, *scale).map(ComponentValue::Fixed)
+            }
+            ComponentType::List(t) => {
+                let inner = s
+                    .strip_prefix('[')
+                    .and_then(|s| s.strip_suffix(']'))
+                    .ok_or_else(invalid)?;
+                let values = split_top_level(inner)
+                    .into_iter()
+                    .map(|v| t.parse_value(v))
+                    .collect::<Result<Vec<_>, _>>()?;
+                Some(ComponentValue::List(values))
+            }
+            ComponentType::Map(t) => Some(self.parse_table(s, |_| Some(t.as_ref()))?),
+            ComponentType::Struct(fields) => Some(self.parse_table(s, |k| fields.get(k))?),
+        };
+
+        match value {
+            Some(v) if self.matches(&v) => Ok(v),
+            _ => Err(invalid()),
+        }
+    }
+}
+
+impl ComponentType {
+    /// Parse a `{ k: v }` table of this Map or Struct type,
+    /// each value is parsed as the type of its key
+    fn parse_table<'a>(
+        &self,
+        s: &str,
+        value_type: impl Fn(&str) -> Option<&'a ComponentType>,
+    ) -> Result<ComponentValue, CoreError> {
+        let invalid = || CoreError::InvalidValue {
+            expected: self.to_string(),
+            value: s.to_string(),
+        };
+
+        let inner = s
+            .strip_prefix('{')
+            .and_then(|s| s.strip_suffix('}'))
+            .ok_or_else(invalid)?;
+
+        let mut values = BTreeMap::new();
+        for (key, value) in parse_entries(inner).ok_or_else(invalid)? {
+            let value_type = value_type(&key).ok_or_else(invalid)?;
+            values.insert(key, value_type.parse_value(value)?);
+        }
+
+        Ok(self.conform(ComponentValue::Map(values)))
+    }
+}
+
+/// Split `k: v, ...` into its entries, keys may be quoted
+fn parse_entries(s: &str) -> Option<Vec<(String, &str)>> {
+    split_top_level(s)
+        .into_iter()
+        .map(|entry| {
+            let (key, value) = entry.split_once(':')?;
+            let key = key.trim();
+            let key = match key.starts_with('"') {
+                true => unquote(key)?,
+                false => key.to_string(),
+            };
+            Some((key, value))
+        })
+        .collect()
+}
+
+/// Returns the contents of a double quoted string,
+/// with `\"` and `\\` unescaped
+fn unquote(s: &str) -> Option<String> {
+    let inner = s.strip_prefix('"')?.strip_suffix('"')?;
+
+    let mut unquoted = String::with_capacity(inner.len());
+    let mut chars = inner.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => unquoted.push(chars.next()?),
+            '"' => return None,
+            c => unquoted.push(c),
+        }
+    }
+
+    Some(unquoted)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_try_from() {
+        let hp: i64 = ComponentValue::Integer(100).try_into().unwrap();
+        assert_eq!(hp, 100);
+
+        let err = f64::try_from(ComponentValue::from("fast")).unwrap_err();
+        assert_eq!(err.to_string(), "expected f64 value, found String");
+
+        assert_eq!(ComponentValue::from(7_u8), ComponentValue::U8(7));
+        assert_eq!(ComponentValue::from(7_u8).as_u8(), Some(7));
+        assert_eq!(ComponentValue::from(7_u8).as_int(), None);
+        assert_eq!(ComponentValue::from(true).type_name(), "bool");
+    }
+
+    #[test]
+    fn test_infer_type() {
+        let value = "[{ x: 1.5 }]".parse::<ComponentValue>().unwrap();
+        assert_eq!(
+            value.infer_type(),
+            Some("[Map<String, f64>]".parse().unwrap())
+        );
+
+        let mut fields = BTreeMap::new();
+        fields.insert("hp".to_string(), ComponentValue::U8(3));
+        fields.insert("price".to_string(), Fixed::new(125, 2).unwrap().into());
+        assert_eq!(
+            ComponentValue::Struct(fields).infer_type(),
+            Some("{ hp: u8, price: Fixed<2> }".parse().unwrap())
+        );
+
+        // element type of empty collections is unknown
+        assert_eq!(ComponentValue::List(vec![]).infer_type(), None);
+        assert_eq!("[[]]".parse::<ComponentValue>().unwrap().infer_type(), None);
+    }
+
+    #[test]
+    fn test_from_str() {
+        assert_eq!(
+            "42".parse::<ComponentValue>().unwrap(),
+            ComponentValue::Integer(42)
+        );
+        assert_eq!(
+            "1.5".parse::<ComponentValue>().unwrap(),
+            ComponentValue::Float(1.5)
+        );
+        assert_eq!(
+            r#""bob""#.parse::<ComponentValue>().unwrap(),
+            ComponentValue::from("bob")
+        );
+        assert_eq!(
+            "NaN".parse::<ComponentValue>().unwrap(),
+            ComponentValue::from("NaN")
+        );
+        assert_eq!(
+            r#"[1, "a, b"]"#.parse::<ComponentValue>().unwrap(),
+            ComponentValue::List(vec![
+                ComponentValue::Integer(1),
+                ComponentValue::from("a, b")
+            ])
+        );
+        assert!(r#""bob"#.parse::<ComponentValue>().is_err());
+    }
+
+    #[test]
+    fn test_parse_value() {
+        assert_eq!(
+            ComponentType::Float.parse_value("42").unwrap(),
+            ComponentValue::Float(42.0)
+        );
+        assert_eq!(
+            ComponentType::String.parse_value("42").unwrap(),
+            ComponentValue::from("42")
+        );
+        assert_eq!(
+            ComponentType::Fixed(2)
+                .parse_value("1.5")
+                .unwrap()
+                .to_string(),
+            "1.50"
+        );
+
+        let stats = "{ str: i64, dex: i64 }".parse::<ComponentType>().unwrap();
+        let value = stats.parse_value("{ str: 3, dex: 5 }").unwrap();
+        assert_eq!(
+            value.as_struct().unwrap()["dex"],
+            ComponentValue::Integer(5)
+        );
+        // Display output parses back
+        assert_eq!(stats.parse_value(&value.to_string()).unwrap(), value);
+
+        let err = ComponentType::U8.parse_value("256").unwrap_err();
+        assert_eq!(err.to_string(), "invalid u8 value: 256");
+        assert!(stats.parse_value("{ str: 3 }").is_err());
+        assert!(stats.parse_value("{ str: 3, dex: 5, luck: 1 }").is_err());
+    }
+}
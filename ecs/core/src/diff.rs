@@ -0,0 +1,354 @@
+//! Blueprint Diff
+//!
+//! Lists what changed between two versions of a World
+
+use crate::blueprint::{
+    Blueprint, Component, ComponentTree, ComponentType, ComponentValue, Entity, Nonce, Region,
+};
+use std::collections::{BTreeMap, BTreeSet};
+
+/// Change to a Component in an Entity's schema
+#[derive(Clone, Debug, PartialEq)]
+pub enum SchemaChange {
+    Added {
+        entity: Entity,
+        component: Component,
+        component_type: ComponentType,
+    },
+    Removed {
+        entity: Entity,
+        component: Component,
+        component_type: ComponentType,
+    },
+    Retyped {
+        entity: Entity,
+        component: Component,
+        from: ComponentType,
+        to: ComponentType,
+    },
+    /// Declared default changed, `None` if not declared
+    Default {
+        entity: Entity,
+        component: Component,
+        from: Option<ComponentValue>,
+        to: Option<ComponentValue>,
+    },
+}
+
+/// Change to an Instance, identified by its nonce
+#[derive(Clone, Debug, PartialEq)]
+pub enum InstanceChange {
+    Added {
+        region: Region,
+        entity: Entity,
+        nonce: Nonce,
+        components: ComponentTree,
+    },
+    Removed {
+        region: Region,
+        entity: Entity,
+        nonce: Nonce,
+        components: ComponentTree,
+    },
+    /// Component value changed, `None` if the
+    /// Instance doesn't have the Component
+    Updated {
+        region: Region,
+        entity: Entity,
+        nonce: Nonce,
+        component: Component,
+        from: Option<ComponentValue>,
+        to: Option<ComponentValue>,
+    },
+}
+
+/// Changes from one [`Blueprint`] to another
+///
+/// Returned by [`Blueprint::diff`]
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct BlueprintDiff {
+    /// World name before and after, if it changed
+    pub name: Option<(String, String)>,
+    /// World description before and after, if it changed
+    pub description: Option<(String, String)>,
+    pub added_regions: Vec<Region>,
+    pub removed_regions: Vec<Region>,
+    pub added_entities: Vec<Entity>,
+    pub removed_entities: Vec<Entity>,
+    pub schema_changes: Vec<SchemaChange>,
+    pub instance_changes: Vec<InstanceChange>,
+}
+
+impl BlueprintDiff {
+    /// Is `true` if both Blueprints are the same World
+    pub fn is_empty(&self) -> bool {
+        self.name.is_none()
+            && self.description.is_none()
+            && self.added_regions.is_empty()
+            && self.removed_regions.is_empty()
+            && self.added_entities.is_empty()
+            && self.removed_entities.is_empty()
+            && self.schema_changes.is_empty()
+            && self.instance_changes.is_empty()
+    }
+}
+
+impl Blueprint {
+    /// Compare with a newer version of the Blueprint
+    ///
+    /// Instances are matched by Region, Entity and nonce
+    pub fn diff(&self, other: &Blueprint) -> BlueprintDiff {
+        let mut diff = BlueprintDiff::default();
+
+        if self.name != other.name {
+            diff.name = Some((self.name.clone(), other.name.clone()));
+        }
+        if self.description != other.description {
+            diff.description = Some((self.description.clone(), other.description.clone()));
+        }
+
+        // REGIONS AND ENTITIES
+
+        (diff.added_regions, diff.removed_regions) = added_removed(&self.regions, &other.regions);
+        (diff.added_entities, diff.removed_entities) =
+            added_removed(&self.entities, &other.entities);
+
+        // SCHEMAS
+
+        for entity in union(self.entities.keys(), other.entities.keys()) {
+            let empty = BTreeMap::new();
+            let before = self.entities.get(entity).unwrap_or(&empty);
+            let after = other.entities.get(entity).unwrap_or(&empty);
+            let before_defaults = self.defaults.get(entity);
+            let after_defaults = other.defaults.get(entity);
+
+            for component in union(before.keys(), after.keys()) {
+                match (before.get(component), after.get(component)) {
+                    (Some(from), None) => diff.schema_changes.push(SchemaChange::Removed {
+                        entity: entity.clone(),
+                        component: component.clone(),
+                        component_type: from.clone(),
+                    }),
+                    (None, Some(to)) => diff.schema_changes.push(SchemaChange::Added {
+                        entity: entity.clone(),
+                        component: component.clone(),
+                        component_type: to.clone(),
+                    }),
+                    (Some(from), Some(to)) => {
+                        if from != to {
+                            diff.schema_changes.push(SchemaChange::Retyped {
+                                entity: entity.clone(),
+                                component: component.clone(),
+                                from: from.clone(),
+                                to: to.clone(),
+                            })
+                        }
+
+                        let from = before_defaults.and_then(|d| d.get(component));
+                        let to = after_defaults.and_then(|d| d.get(component));
+                        if from != to {
+                            diff.schema_changes.push(SchemaChange::Default {
+                                entity: entity.clone(),
+                                component: component.clone(),
+                                from: from.cloned(),
+                                to: to.cloned(),
+                            })
+                        }
+                    }
+                    (None, None) => {}
+                }
+            }
+        }
+
+        // INSTANCES
+
+        let empty = BTreeMap::new();
+        for region in union(self.instances.keys(), other.instances.keys()) {
+            let before = self.instances.get(region).unwrap_or(&empty);
+            let after = other.instances.get(region).unwrap_or(&empty);
+
+            for entity in union(before.keys(), after.keys()) {
+                let empty = BTreeMap::new();
+                let before = before.get(entity).unwrap_or(&empty);
+                let after = after.get(entity).unwrap_or(&empty);
+
+                for nonce in union(before.keys(), after.keys()) {
+                    match (before.get(nonce), after.get(nonce)) {
+                        (Some(components), None) => {
+                            diff.instance_changes.push(InstanceChange::Removed {
+                                region: region.clone(),
+                                entity: entity.clone(),
+                                nonce: *nonce,
+                                components: components.clone(),
+                            })
+                        }
+                        (None, Some(components)) => {
+                            diff.instance_changes.push(InstanceChange::Added {
+                                region: region.clone(),
+                                entity: entity.clone(),
+                                nonce: *nonce,
+                                components: components.clone(),
+                            })
+                        }
+                        (Some(from), Some(to)) => {
+                            for component in union(from.keys(), to.keys()) {
+                                let (from, to) = (from.get(component), to.get(component));
+                                if from != to {
+                                    diff.instance_changes.push(InstanceChange::Updated {
+                                        region: region.clone(),
+                                        entity: entity.clone(),
+                                        nonce: *nonce,
+                                        component: component.clone(),
+                                        from: from.cloned(),
+                                        to: to.cloned(),
+                                    })
+                                }
+                            }
+                        }
+                        (None, None) => {}
+                    }
+                }
+            }
+        }
+
+        diff
+    }
+}
+
+/// Returns the sorted union of two sets of keys
+fn union<'a, K: Ord + 'a>(
+    a: impl Iterator<Item = &'a K>,
+    b: impl Iterator<Item = &'a K>,
+) -> BTreeSet<&'a K> {
+    a.chain(b).collect()
+}
+
+/// Returns the keys only in `after` and the keys only in `before`
+fn added_removed<K: Clone + Ord, V>(
+    before: &BTreeMap<K, V>,
+    after: &BTreeMap<K, V>,
+) -> (Vec<K>, Vec<K>) {
+    let added = after
+        .keys()
+        .filter(|k| !before.contains_key(k))
+        .cloned()
+        .collect();
+    let removed = before
+        .keys()
+        .filter(|k| !after.contains_key(k))
+        .cloned()
+        .collect();
+
+    (added, removed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::blueprint::ComponentTypeTree;
+
+    fn get_sample_blueprint() -> Blueprint {
+        let mut blueprint = Blueprint::new("World".to_string(), "Description".to_string());
+
+        let mut component_type_tree = ComponentTypeTree::new();
+        component_type_tree.insert("name".to_string(), ComponentType::String);
+        component_type_tree.insert("hp".to_string(), ComponentType::Integer);
+        blueprint.add_entity("player".to_string(), component_type_tree);
+        blueprint.add_region("farm".to_string(), vec!["player".to_string()]);
+        blueprint.preload(vec!["farm".to_string()], vec!["player".to_string()]);
+
+        for name in ["alice", "bob"] {
+            let mut component_tree = ComponentTree::new();
+            component_tree.insert("name".to_string(), name.into());
+            component_tree.insert("hp".to_string(), ComponentValue::Integer(10));
+            blueprint
+                .add_instance("farm".to_string(), "player".to_string(), component_tree)
+                .unwrap();
+        }
+
+        blueprint
+    }
+
+    #[test]
+    fn test_blueprint_diff() {
+        let before = get_sample_blueprint();
+        assert!(before.diff(&before.clone()).is_empty());
+
+        let mut after = before.clone();
+        let (farm, player) = ("farm".to_string(), "player".to_string());
+
+        // regions and entities
+        after.add_region("house".to_string(), vec![]);
+        after.add_entity("apple".to_string(), ComponentTypeTree::new());
+
+        // schema
+        let player_schema = after.entities.get_mut(&player).unwrap();
+        player_schema.insert("hp".to_string(), ComponentType::U32);
+        player_schema.remove("name");
+
+        // instances
+        after
+            .remove_instance(farm.clone(), player.clone(), 1)
+            .unwrap();
+        after
+            .instances
+            .get_mut(&farm)
+            .unwrap()
+            .get_mut(&player)
+            .unwrap()
+            .get_mut(&2)
+            .unwrap()
+            .insert("hp".to_string(), ComponentValue::U32(5));
+        let nonce = after
+            .add_default_instance(farm.clone(), player.clone())
+            .unwrap();
+
+        let diff = before.diff(&after);
+        assert_eq!(diff.name, None);
+        assert_eq!(diff.added_regions, vec!["house".to_string()]);
+        assert!(diff.removed_regions.is_empty());
+        assert_eq!(diff.added_entities, vec!["apple".to_string()]);
+        assert_eq!(
+            diff.schema_changes,
+            vec![
+                SchemaChange::Retyped {
+                    entity: player.clone(),
+                    component: "hp".to_string(),
+                    from: ComponentType::Integer,
+                    to: ComponentType::U32,
+                },
+                SchemaChange::Removed {
+                    entity: player.clone(),
+                    component: "name".to_string(),
+                    component_type: ComponentType::String,
+                },
+            ]
+        );
+
+        assert_eq!(diff.instance_changes.len(), 3);
+        assert!(matches!(
+            &diff.instance_changes[0],
+            InstanceChange::Removed { nonce: 1, .. }
+        ));
+        assert_eq!(
+            diff.instance_changes[1],
+            InstanceChange::Updated {
+                region: farm.clone(),
+                entity: player.clone(),
+                nonce: 2,
+                component: "hp".to_string(),
+                from: Some(ComponentValue::Integer(10)),
+                to: Some(ComponentValue::U32(5)),
+            }
+        );
+        assert!(matches!(
+            &diff.instance_changes[2],
+            InstanceChange::Added { nonce: n, .. } if *n == nonce
+        ));
+
+        // reversed, additions become removals
+        let diff = after.diff(&before);
+        assert_eq!(diff.removed_regions, vec!["house".to_string()]);
+        assert_eq!(diff.removed_entities, vec!["apple".to_string()]);
+    }
+}
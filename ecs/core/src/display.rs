@@ -1,6 +1,9 @@
 //! Rush Core Utilities
 
-use super::blueprint::{Blueprint, ComponentValue, Entity, Region};
+use super::{
+    blueprint::{Blueprint, ComponentTree, ComponentValue, Entity, Region},
+    diff::{BlueprintDiff, InstanceChange, SchemaChange},
+};
 use comfy_table::{modifiers::UTF8_ROUND_CORNERS, presets::UTF8_FULL, Table, *};
 use std::fmt::Display;
 
@@ -174,11 +177,18 @@ pub fn get_entity_table_display(entity: &Entity, blueprint: &Blueprint) -> Table
             Cell::new(entity),
         ]);
 
+    let defaults = blueprint.defaults.get(entity);
     if let Some(component_tree) = blueprint.entities.get(entity) {
         for (k, v) in component_tree {
+            // show declared default next to the type
+            let type_string = match defaults.and_then(|d| d.get(k)) {
+                Some(default) => format!("{v} (default: {default})"),
+                None => format!("{v}"),
+            };
+
             entity_table.add_row(vec![
                 Cell::new(k).add_attribute(Attribute::Bold),
-                Cell::new(v),
+                Cell::new(type_string),
             ]);
         }
     }
@@ -216,22 +226,16 @@ pub fn get_instances_table_display(
     if let Some(entities_in_region) = blueprint.instances.get(region) {
         // get instances in certain Entity, in Region
         if let Some(instances) = entities_in_region.get(entity) {
-            for (idx, instance) in instances.iter().enumerate() {
+            for (nonce, instance) in instances.iter() {
                 // get all string pairs for combining later
                 let mut instance_string_pairs: Vec<String> = Vec::new();
                 // c_value = ComponentValue
                 for (k, c_value) in instance {
-                    let value_string = match c_value {
-                        ComponentValue::String(v) => v.to_string(), // already a String
-                        ComponentValue::Integer(v) => format!("{v}"),
-                        ComponentValue::Float(v) => format!("{v}"),
-                        ComponentValue::Boolean(v) => format!("{v}"),
-                    };
-                    instance_string_pairs.push(format!("{k} = {value_string}"));
+                    instance_string_pairs.push(format!("{k} = {c_value}"));
                 }
                 // add row to instances_table
                 instances_table.add_row(vec![
-                    Cell::new(idx).add_attribute(Attribute::Bold),
+                    Cell::new(nonce).add_attribute(Attribute::Bold),
                     // combine instance string pairs into 1 long string
                     Cell::new(instance_string_pairs.join(", ")),
                 ]);
@@ -242,11 +246,267 @@ pub fn get_instances_table_display(
     instances_table
 }
 
+// implement Display trait for BlueprintDiff
+impl Display for BlueprintDiff {
+    ///
+    /// Displays the changes into human-readable format via
+    /// comfy CLI tables, one per kind of change
+    ///
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.is_empty() {
+            return write!(f, "No changes");
+        }
+
+        let tables = get_diff_tables_display(self)
+            .iter()
+            .map(|t| format!("{t}"))
+            .collect::<Vec<_>>()
+            .join("\n\n");
+
+        write!(f, "{tables}")
+    }
+}
+
+/// Get the printable tables from [`BlueprintDiff`]
+///
+/// Constructs a table for each kind of change in the
+/// [`BlueprintDiff`], skipping the kinds without changes.
+/// Mainly used for BlueprintDiff's Display trait
+///
+pub fn get_diff_tables_display(diff: &BlueprintDiff) -> Vec<Table> {
+    let mut tables: Vec<Table> = Vec::new();
+
+    // World table
+    if diff.name.is_some() || diff.description.is_some() {
+        let mut world_table = diff_table("World");
+        if let Some((from, to)) = &diff.name {
+            world_table.add_row(vec![
+                change_cell('~'),
+                Cell::new("Name").add_attribute(Attribute::Bold),
+                Cell::new(format!("{from} -> {to}")),
+            ]);
+        }
+        if let Some((from, to)) = &diff.description {
+            world_table.add_row(vec![
+                change_cell('~'),
+                Cell::new("Description").add_attribute(Attribute::Bold),
+                Cell::new(format!("{from} -> {to}")),
+            ]);
+        }
+        tables.push(world_table);
+    }
+
+    // Regions and Entities tables
+    for (title, added, removed) in [
+        ("Regions", &diff.added_regions, &diff.removed_regions),
+        ("Entities", &diff.added_entities, &diff.removed_entities),
+    ] {
+        if added.is_empty() && removed.is_empty() {
+            continue;
+        }
+
+        let mut table = diff_table(title);
+        for (sign, names) in [('+', added), ('-', removed)] {
+            for name in names.iter() {
+                table.add_row(vec![
+                    change_cell(sign),
+                    Cell::new(name).add_attribute(Attribute::Bold),
+                    Cell::new(""),
+                ]);
+            }
+        }
+        tables.push(table);
+    }
+
+    // Schema table
+    if !diff.schema_changes.is_empty() {
+        let mut schema_table = diff_table("Schema");
+        for change in diff.schema_changes.iter() {
+            let (sign, entity, component, detail) = match change {
+                SchemaChange::Added {
+                    entity,
+                    component,
+                    component_type,
+                } => ('+', entity, component, format!("{component_type}")),
+                SchemaChange::Removed {
+                    entity,
+                    component,
+                    component_type,
+                } => ('-', entity, component, format!("{component_type}")),
+                SchemaChange::Retyped {
+                    entity,
+                    component,
+                    from,
+                    to,
+                } => ('~', entity, component, format!("{from} -> {to}")),
+                SchemaChange::Default {
+                    entity,
+                    component,
+                    from,
+                    to,
+                } => (
+                    '~',
+                    entity,
+                    component,
+                    format!(
+                        "default: {} -> {}",
+                        optional_value_string(from),
+                        optional_value_string(to)
+                    ),
+                ),
+            };
+
+            schema_table.add_row(vec![
+                change_cell(sign),
+                Cell::new(format!("{entity}.{component}")).add_attribute(Attribute::Bold),
+                Cell::new(detail),
+            ]);
+        }
+        tables.push(schema_table);
+    }
+
+    // Instances table
+    if !diff.instance_changes.is_empty() {
+        let mut instances_table = diff_table("Instances");
+        for change in diff.instance_changes.iter() {
+            let (sign, region, entity, nonce, detail) = match change {
+                InstanceChange::Added {
+                    region,
+                    entity,
+                    nonce,
+                    components,
+                } => (
+                    '+',
+                    region,
+                    entity,
+                    nonce,
+                    component_tree_string(components),
+                ),
+                InstanceChange::Removed {
+                    region,
+                    entity,
+                    nonce,
+                    components,
+                } => (
+                    '-',
+                    region,
+                    entity,
+                    nonce,
+                    component_tree_string(components),
+                ),
+                InstanceChange::Updated {
+                    region,
+                    entity,
+                    nonce,
+                    component,
+                    from,
+                    to,
+                } => (
+                    '~',
+                    region,
+                    entity,
+                    nonce,
+                    format!(
+                        "{component}: {} -> {}",
+                        optional_value_string(from),
+                        optional_value_string(to)
+                    ),
+                ),
+            };
+
+            instances_table.add_row(vec![
+                change_cell(sign),
+                Cell::new(format!("{region}, {entity} #{nonce}")).add_attribute(Attribute::Bold),
+                Cell::new(detail),
+            ]);
+        }
+        tables.push(instances_table);
+    }
+
+    tables
+}
+
+/// Empty diff table with a title
+fn diff_table(title: &str) -> Table {
+    let mut table = Table::new();
+    table
+        .load_preset(UTF8_FULL)
+        .apply_modifier(UTF8_ROUND_CORNERS)
+        .set_header(vec![
+            Cell::new(title)
+                .fg(Color::Green)
+                .add_attribute(Attribute::Bold),
+            Cell::new(""),
+            Cell::new(""),
+        ]);
+
+    table
+}
+
+/// Colored cell for an added (+), removed (-) or changed (~) row
+fn change_cell(sign: char) -> Cell {
+    let color = match sign {
+        '+' => Color::Green,
+        '-' => Color::Red,
+        _ => Color::Yellow,
+    };
+
+    Cell::new(sign).fg(color).add_attribute(Attribute::Bold)
+}
+
+fn component_tree_string(component_tree: &ComponentTree) -> String {
+    component_tree
+        .iter()
+        .map(|(k, v)| format!("{k} = {v}"))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn optional_value_string(value: &Option<ComponentValue>) -> String {
+    match value {
+        Some(v) => format!("{v}"),
+        None => "(none)".to_string(),
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::blueprint::{Blueprint, ComponentTree, ComponentTypeTree, ComponentValue};
+    use crate::{
+        blueprint::{Blueprint, ComponentTree, ComponentType, ComponentTypeTree, ComponentValue},
+        diff::{BlueprintDiff, InstanceChange, SchemaChange},
+    };
     use std::collections::btree_map::BTreeMap;
 
+    /// Cells of each table row, without escape codes and borders
+    fn table_rows(rendered: &str) -> Vec<Vec<String>> {
+        let mut plain = String::new();
+        let mut chars = rendered.chars();
+        while let Some(c) = chars.next() {
+            match c {
+                // skip escape codes, e.g. `\x1b[1m`
+                '\x1b' => {
+                    for c in chars.by_ref() {
+                        if c.is_ascii_alphabetic() {
+                            break;
+                        }
+                    }
+                }
+                c => plain.push(c),
+            }
+        }
+
+        plain
+            .lines()
+            .filter(|l| l.starts_with('│'))
+            .map(|l| {
+                l.trim_matches('│')
+                    .split(['│', '┆'])
+                    .map(|c| c.trim().to_string())
+                    .collect()
+            })
+            .collect()
+    }
+
     // TODO: Strip escape codes from string and match
     #[test]
     fn test_blueprint_display() {
@@ -260,18 +520,24 @@ mod tests {
         let entity1 = String::from("entity1");
         let entity2 = String::from("entity2");
 
+        // preload Region and Entity keys
+        blueprint.preload(
+            vec![region1.clone(), region2.clone()],
+            vec![entity1.clone(), entity2.clone()],
+        );
+
         // load mock regions
         blueprint.add_region(region1.clone(), vec![entity1.clone()]);
         blueprint.add_region(region2.clone(), vec![entity2.clone()]);
         // load mock entity1
         let mut component_type_tree1: ComponentTypeTree = BTreeMap::new();
-        component_type_tree1.insert("x".to_string(), "int".to_string());
-        component_type_tree1.insert("y".to_string(), "int".to_string());
+        component_type_tree1.insert("x".to_string(), ComponentType::Integer);
+        component_type_tree1.insert("y".to_string(), ComponentType::Integer);
         blueprint.add_entity(entity1.clone(), component_type_tree1);
         // load mock entity2
         let mut component_type_tree2: ComponentTypeTree = BTreeMap::new();
-        component_type_tree2.insert("w".to_string(), "float".to_string());
-        component_type_tree2.insert("h".to_string(), "float".to_string());
+        component_type_tree2.insert("w".to_string(), ComponentType::Float);
+        component_type_tree2.insert("h".to_string(), ComponentType::Float);
         blueprint.add_entity(entity2.clone(), component_type_tree2);
         // load mock instances1
         let mut component_tree1: ComponentTree = BTreeMap::new();
@@ -289,5 +555,62 @@ mod tests {
             .unwrap();
 
         println!("{blueprint}");
+
+        // TODO: Strip escape codes from string and match
+        let mut other = blueprint.clone();
+        other.remove_instance(region1, entity1, 1).unwrap();
+        println!("{}", blueprint.diff(&other));
+        assert_eq!(blueprint.diff(&blueprint).to_string(), "No changes");
+    }
+
+    #[test]
+    fn test_blueprint_diff_display() {
+        let diff = BlueprintDiff {
+            name: Some(("Old World".to_string(), "New World".to_string())),
+            added_regions: vec!["house".to_string()],
+            removed_entities: vec!["tree".to_string()],
+            schema_changes: vec![SchemaChange::Retyped {
+                entity: "apple".to_string(),
+                component: "x".to_string(),
+                from: ComponentType::Integer,
+                to: ComponentType::Float,
+            }],
+            instance_changes: vec![
+                InstanceChange::Removed {
+                    region: "farm".to_string(),
+                    entity: "apple".to_string(),
+                    nonce: 1,
+                    components: BTreeMap::from([("x".to_string(), ComponentValue::Integer(1))]),
+                },
+                InstanceChange::Updated {
+                    region: "farm".to_string(),
+                    entity: "apple".to_string(),
+                    nonce: 2,
+                    component: "x".to_string(),
+                    from: Some(ComponentValue::Integer(1)),
+                    to: None,
+                },
+            ],
+            ..Default::default()
+        };
+
+        // a table per kind of change, in order
+        let rows = table_rows(&diff.to_string());
+        let expected = [
+            ["World", "", ""],
+            ["~", "Name", "Old World -> New World"],
+            ["Regions", "", ""],
+            ["+", "house", ""],
+            ["Entities", "", ""],
+            ["-", "tree", ""],
+            ["Schema", "", ""],
+            ["~", "apple.x", "i64 -> f64"],
+            ["Instances", "", ""],
+            ["-", "farm, apple #1", "x = 1"],
+            ["~", "farm, apple #2", "x: 1 -> (none)"],
+        ];
+        assert_eq!(rows, expected);
+
+        assert_eq!(BlueprintDiff::default().to_string(), "No changes");
     }
 }
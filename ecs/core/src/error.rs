@@ -1,3 +1,7 @@
+use crate::{
+    blueprint::{Component, Entity},
+    constraint::ConstraintViolation,
+};
 use thiserror::Error;
 
 #[derive(Error, Debug)]
@@ -11,6 +15,9 @@ pub enum CoreError {
     #[error("instance not found in world")]
     InstanceNotFound,
 
+    #[error("instance already exists in world")]
+    InstanceExists,
+
     #[error("component not in entity's tree")]
     ComponentNotFound,
 
@@ -19,4 +26,26 @@ pub enum CoreError {
 
     #[error("unsupported component data type")]
     UnsupportedDataType,
+
+    #[error("unknown component type: {0}")]
+    UnknownComponentType(String),
+
+    #[error("expected {expected} value, found {found}")]
+    UnexpectedValue {
+        expected: &'static str,
+        found: &'static str,
+    },
+
+    #[error("invalid {expected} value: {value}")]
+    InvalidValue { expected: String, value: String },
+
+    #[error("{entity} component {component}: {violation}")]
+    ConstraintViolation {
+        entity: Entity,
+        component: Component,
+        violation: ConstraintViolation,
+    },
+
+    #[error("operation on component {0} is out of range")]
+    InvalidOperation(Component),
 }
@@ -1,5 +1,11 @@
 pub mod blueprint;
+pub mod constraint;
+pub mod convert;
+pub mod diff;
 pub mod error;
+pub mod numeric;
+pub mod query;
+pub mod validation;
 
 #[cfg(not(target_os = "solana"))]
 mod display;
@@ -0,0 +1,690 @@
+//! Numeric Components
+//!
+//! Fixed-point decimals and checked arithmetic on numeric
+//! [`ComponentValue`]s. Everything except `f64` is integer
+//! math, so clients and programs get the same bits
+//!
+//! [`ComponentOp`]s are the atomic updates the store
+//! program applies to numeric Components
+
+use crate::blueprint::{ComponentType, ComponentValue};
+use borsh::{BorshDeserialize, BorshSerialize};
+use std::{
+    cmp::Ordering,
+    fmt::{self, Display},
+};
+
+/// Largest number of decimal places of a [`Fixed`]
+pub const MAX_SCALE: u8 = 18;
+
+/// Fixed-point decimal
+///
+/// Stores `raw / 10^scale` exactly, e.g. `1.25` with a
+/// scale of `2` is stored as `125`
+#[derive(Clone, Copy, BorshDeserialize, BorshSerialize, Debug, Default, Eq, Hash, PartialEq)]
+pub struct Fixed {
+    raw: i64,
+    scale: u8,
+}
+
+impl Fixed {
+    /// Create a Fixed from its raw value, `None` if the
+    /// scale is greater than [`MAX_SCALE`]
+    pub fn new(raw: i64, scale: u8) -> Option<Self> {
+        (scale <= MAX_SCALE).then_some(Self { raw, scale })
+    }
+
+    /// Create a Fixed holding an integer
+    pub fn from_int(value: i64, scale: u8) -> Option<Self> {
+        Self::new(value.checked_mul(pow10(scale)?)?, scale)
+    }
+
+    /// Parse a decimal (e.g. `-1.25`) exactly
+    ///
+    /// `None` if it has more decimal places than the scale
+    pub fn parse(s: &str, scale: u8) -> Option<Self> {
+        let (negative, digits) = match s.strip_prefix('-') {
+            Some(digits) => (true, digits),
+            None => (false, s.strip_prefix('+').unwrap_or(s)),
+        };
+
+        let (int, frac) = digits.split_once('.').unwrap_or((digits, ""));
+        let is_digits = |s: &str| s.chars().all(|c| c.is_ascii_digit());
+        if int.is_empty() || !is_digits(int) || !is_digits(frac) {
+            return None;
+        }
+
+        // trailing zeros don't need to fit the scale
+        let frac = frac.trim_end_matches('0');
+        if frac.len() > scale as usize {
+            return None;
+        }
+
+        let frac = format!("{frac:0<width$}", width = scale as usize);
+        let raw = format!("{int}{frac}").parse::<i128>().ok()?;
+        let raw = i64::try_from(if negative { -raw } else { raw }).ok()?;
+
+        Self::new(raw, scale)
+    }
+
+    /// Raw value, the decimal times `10^scale`
+    pub fn raw(&self) -> i64 {
+        self.raw
+    }
+
+    /// Number of decimal places
+    pub fn scale(&self) -> u8 {
+        self.scale
+    }
+
+    /// Convert to another scale, `None` if it would lose
+    /// decimal places or overflow
+    pub fn rescale(&self, scale: u8) -> Option<Self> {
+        let raw = rescale_raw(self.raw as i128, self.scale, scale)?;
+        Self::new(i64::try_from(raw).ok()?, scale)
+    }
+
+    /// Integer value, `None` if it has a fractional part
+    pub fn to_int(&self) -> Option<i64> {
+        let factor = pow10(self.scale)?;
+        (self.raw % factor == 0).then_some(self.raw / factor)
+    }
+
+    /// Approximate value as an `f64`, for display only
+    pub fn to_f64(&self) -> f64 {
+        self.to_string().parse().unwrap_or(f64::NAN)
+    }
+
+    /// Add Fixeds of the same scale
+    pub fn checked_add(&self, rhs: &Self) -> Option<Self> {
+        self.same_scale(rhs)?;
+        Self::new(self.raw.checked_add(rhs.raw)?, self.scale)
+    }
+
+    /// Subtract Fixeds of the same scale
+    pub fn checked_sub(&self, rhs: &Self) -> Option<Self> {
+        self.same_scale(rhs)?;
+        Self::new(self.raw.checked_sub(rhs.raw)?, self.scale)
+    }
+
+    /// Multiply Fixeds of the same scale, rounding
+    /// toward zero
+    pub fn checked_mul(&self, rhs: &Self) -> Option<Self> {
+        self.same_scale(rhs)?;
+        let raw = (self.raw as i128 * rhs.raw as i128) / pow10(self.scale)? as i128;
+        Self::new(i64::try_from(raw).ok()?, self.scale)
+    }
+
+    /// Divide Fixeds of the same scale, rounding
+    /// toward zero
+    pub fn checked_div(&self, rhs: &Self) -> Option<Self> {
+        self.same_scale(rhs)?;
+        let raw = (self.raw as i128 * pow10(self.scale)? as i128).checked_div(rhs.raw as i128)?;
+        Self::new(i64::try_from(raw).ok()?, self.scale)
+    }
+
+    fn same_scale(&self, rhs: &Self) -> Option<()> {
+        (self.scale == rhs.scale).then_some(())
+    }
+}
+
+impl PartialOrd for Fixed {
+    /// Fixeds compare exactly, across scales
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        let scale = self.scale.max(other.scale);
+        let lhs = rescale_raw(self.raw as i128, self.scale, scale)?;
+        let rhs = rescale_raw(other.raw as i128, other.scale, scale)?;
+        Some(lhs.cmp(&rhs))
+    }
+}
+
+impl Display for Fixed {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let sign = if self.raw < 0 { "-" } else { "" };
+        let digits = format!(
+            "{:0>width$}",
+            self.raw.unsigned_abs(),
+            width = self.scale as usize + 1
+        );
+        let (int, frac) = digits.split_at(digits.len() - self.scale as usize);
+
+        match frac.is_empty() {
+            true => write!(f, "{sign}{int}"),
+            false => write!(f, "{sign}{int}.{frac}"),
+        }
+    }
+}
+
+/// Returns `10^scale`, `None` past [`MAX_SCALE`]
+fn pow10(scale: u8) -> Option<i64> {
+    (scale <= MAX_SCALE).then(|| 10_i64.pow(scale as u32))
+}
+
+/// Convert a raw value between scales, `None` if it
+/// would lose decimal places
+fn rescale_raw(raw: i128, from: u8, to: u8) -> Option<i128> {
+    match from.cmp(&to) {
+        Ordering::Equal => Some(raw),
+        Ordering::Less => raw.checked_mul(pow10(to - from)? as i128),
+        Ordering::Greater => {
+            let factor = pow10(from - to)? as i128;
+            (raw % factor == 0).then_some(raw / factor)
+        }
+    }
+}
+
+impl ComponentValue {
+    /// Returns the value of an `i64` or unsigned Component
+    fn as_i128(&self) -> Option<i128> {
+        match self {
+            ComponentValue::Integer(v) => Some(*v as i128),
+            ComponentValue::U8(v) => Some(*v as i128),
+            ComponentValue::U32(v) => Some(*v as i128),
+            ComponentValue::U64(v) => Some(*v as i128),
+            _ => None,
+        }
+    }
+
+    /// Convert to another numeric type without losing
+    /// precision, e.g. `Integer(7)` to `U8(7)`
+    ///
+    /// `None` if the value is out of range for the type,
+    /// would lose decimal places or isn't numeric. `f64`
+    /// only converts to itself
+    pub fn checked_cast(&self, component_type: &ComponentType) -> Option<ComponentValue> {
+        if component_type.matches(self) {
+            return Some(self.clone());
+        }
+
+        let value = match self {
+            ComponentValue::Fixed(v) if v.to_int().is_some() => v.to_int()? as i128,
+            ComponentValue::Fixed(v) => match component_type {
+                ComponentType::Fixed(scale) => return v.rescale(*scale).map(ComponentValue::Fixed),
+                _ => return None,
+            },
+            v => v.as_i128()?,
+        };
+
+        from_i128(value, component_type)
+    }
+
+    /// Add values of the same numeric type
+    ///
+    /// `None` on overflow or mismatched types
+    pub fn checked_add(&self, rhs: &ComponentValue) -> Option<ComponentValue> {
+        use ComponentValue as V;
+
+        match (self, rhs) {
+            (V::Integer(a), V::Integer(b)) => a.checked_add(*b).map(V::Integer),
+            (V::U8(a), V::U8(b)) => a.checked_add(*b).map(V::U8),
+            (V::U32(a), V::U32(b)) => a.checked_add(*b).map(V::U32),
+            (V::U64(a), V::U64(b)) => a.checked_add(*b).map(V::U64),
+            (V::Fixed(a), V::Fixed(b)) => a.checked_add(b).map(V::Fixed),
+            (V::Float(a), V::Float(b)) => finite(a + b),
+            _ => None,
+        }
+    }
+
+    /// Subtract values of the same numeric type
+    ///
+    /// `None` on overflow or mismatched types
+    pub fn checked_sub(&self, rhs: &ComponentValue) -> Option<ComponentValue> {
+        use ComponentValue as V;
+
+        match (self, rhs) {
+            (V::Integer(a), V::Integer(b)) => a.checked_sub(*b).map(V::Integer),
+            (V::U8(a), V::U8(b)) => a.checked_sub(*b).map(V::U8),
+            (V::U32(a), V::U32(b)) => a.checked_sub(*b).map(V::U32),
+            (V::U64(a), V::U64(b)) => a.checked_sub(*b).map(V::U64),
+            (V::Fixed(a), V::Fixed(b)) => a.checked_sub(b).map(V::Fixed),
+            (V::Float(a), V::Float(b)) => finite(a - b),
+            _ => None,
+        }
+    }
+
+    /// Multiply values of the same numeric type
+    ///
+    /// `None` on overflow or mismatched types
+    pub fn checked_mul(&self, rhs: &ComponentValue) -> Option<ComponentValue> {
+        use ComponentValue as V;
+
+        match (self, rhs) {
+            (V::Integer(a), V::Integer(b)) => a.checked_mul(*b).map(V::Integer),
+            (V::U8(a), V::U8(b)) => a.checked_mul(*b).map(V::U8),
+            (V::U32(a), V::U32(b)) => a.checked_mul(*b).map(V::U32),
+            (V::U64(a), V::U64(b)) => a.checked_mul(*b).map(V::U64),
+            (V::Fixed(a), V::Fixed(b)) => a.checked_mul(b).map(V::Fixed),
+            (V::Float(a), V::Float(b)) => finite(a * b),
+            _ => None,
+        }
+    }
+
+    /// Divide values of the same numeric type, integers
+    /// round toward zero
+    ///
+    /// `None` on division by zero, overflow or
+    /// mismatched types
+    pub fn checked_div(&self, rhs: &ComponentValue) -> Option<ComponentValue> {
+        use ComponentValue as V;
+
+        match (self, rhs) {
+            (V::Integer(a), V::Integer(b)) => a.checked_div(*b).map(V::Integer),
+            (V::U8(a), V::U8(b)) => a.checked_div(*b).map(V::U8),
+            (V::U32(a), V::U32(b)) => a.checked_div(*b).map(V::U32),
+            (V::U64(a), V::U64(b)) => a.checked_div(*b).map(V::U64),
+            (V::Fixed(a), V::Fixed(b)) => a.checked_div(b).map(V::Fixed),
+            (V::Float(a), V::Float(b)) => finite(a / b),
+            _ => None,
+        }
+    }
+}
+
+/// Atomic update of a numeric Component
+///
+/// Applied with [`ComponentValue::apply`]. Deltas are
+/// signed, see [`ComponentType::delta_type`], bounds are
+/// of the Component's type
+///
+// @dev
+// new variants are appended to keep the Borsh layout
+// of instructions
+#[derive(Clone, BorshDeserialize, BorshSerialize, Debug, Eq, PartialEq)]
+pub enum ComponentOp {
+    /// Add the delta, fails if the result is out of range
+    Add(ComponentValue),
+    /// Subtract the delta, fails if the result is out of
+    /// range
+    Sub(ComponentValue),
+    /// Keep the smaller value
+    Min(ComponentValue),
+    /// Keep the larger value
+    Max(ComponentValue),
+    /// Add `delta` and clamp into `min..=max`, saturates
+    /// at the bounds instead of failing on overflow
+    Clamp {
+        delta: ComponentValue,
+        min: ComponentValue,
+        max: ComponentValue,
+    },
+}
+
+impl ComponentType {
+    /// Returns the type of the deltas added to a numeric
+    /// Component
+    ///
+    /// Integer and unsigned Components move by `i64`
+    /// deltas so unsigned values can go down, Fixed and
+    /// Float Components by their own type. `None` if the
+    /// type isn't numeric
+    pub fn delta_type(&self) -> Option<ComponentType> {
+        match self {
+            ComponentType::Integer
+            | ComponentType::U8
+            | ComponentType::U32
+            | ComponentType::U64 => Some(ComponentType::Integer),
+            ComponentType::Fixed(_) | ComponentType::Float => Some(self.clone()),
+            _ => None,
+        }
+    }
+}
+
+impl ComponentOp {
+    /// Is `true` if the deltas are of the Component's
+    /// delta type and the bounds of the Component's type
+    pub fn matches(&self, component_type: &ComponentType) -> bool {
+        let Some(delta_type) = component_type.delta_type() else {
+            return false;
+        };
+
+        match self {
+            ComponentOp::Add(delta) | ComponentOp::Sub(delta) => delta_type.matches(delta),
+            ComponentOp::Min(v) | ComponentOp::Max(v) => component_type.matches(v),
+            ComponentOp::Clamp { delta, min, max } => {
+                delta_type.matches(delta)
+                    && component_type.matches(min)
+                    && component_type.matches(max)
+            }
+        }
+    }
+
+    /// Convert the deltas to the Component's delta type
+    /// and the bounds to its type, see
+    /// [`ComponentValue::checked_cast`]
+    pub fn checked_cast(&self, component_type: &ComponentType) -> Option<ComponentOp> {
+        let delta_type = component_type.delta_type()?;
+        let delta = |v: &ComponentValue| v.checked_cast(&delta_type);
+        let bound = |v: &ComponentValue| v.checked_cast(component_type);
+
+        let op = match self {
+            ComponentOp::Add(v) => ComponentOp::Add(delta(v)?),
+            ComponentOp::Sub(v) => ComponentOp::Sub(delta(v)?),
+            ComponentOp::Min(v) => ComponentOp::Min(bound(v)?),
+            ComponentOp::Max(v) => ComponentOp::Max(bound(v)?),
+            ComponentOp::Clamp { delta: d, min, max } => ComponentOp::Clamp {
+                delta: delta(d)?,
+                min: bound(min)?,
+                max: bound(max)?,
+            },
+        };
+
+        Some(op)
+    }
+}
+
+impl ComponentValue {
+    /// Is `true` for integer, unsigned, Fixed and Float values
+    pub fn is_numeric(&self) -> bool {
+        matches!(self, ComponentValue::Float(_) | ComponentValue::Fixed(_))
+            || self.as_i128().is_some()
+    }
+
+    /// Returns the value after applying the operation
+    ///
+    /// `None` if the value isn't numeric, an operand isn't
+    /// of the expected type (see [`ComponentOp::matches`]),
+    /// if the result is out of range or if `min > max`
+    pub fn apply(&self, op: &ComponentOp) -> Option<ComponentValue> {
+        let component_type = self.infer_type()?;
+        if !op.matches(&component_type) {
+            return None;
+        }
+
+        match op {
+            ComponentOp::Add(delta) => match (self.as_i128(), delta) {
+                (Some(a), ComponentValue::Integer(d)) => from_i128(a + *d as i128, &component_type),
+                _ => self.checked_add(delta),
+            },
+            ComponentOp::Sub(delta) => match (self.as_i128(), delta) {
+                (Some(a), ComponentValue::Integer(d)) => from_i128(a - *d as i128, &component_type),
+                _ => self.checked_sub(delta),
+            },
+            ComponentOp::Min(v) => match compare(self, v)? {
+                Ordering::Greater => Some(v.clone()),
+                _ => Some(self.clone()),
+            },
+            ComponentOp::Max(v) => match compare(self, v)? {
+                Ordering::Less => Some(v.clone()),
+                _ => Some(self.clone()),
+            },
+            ComponentOp::Clamp { delta, min, max } => {
+                if compare(min, max)? == Ordering::Greater {
+                    return None;
+                }
+
+                // integers can't overflow as i128, bound them
+                // before converting back to the Component's type
+                if let (Some(a), ComponentValue::Integer(d)) = (self.as_i128(), delta) {
+                    let value = (a + *d as i128).clamp(min.as_i128()?, max.as_i128()?);
+                    return from_i128(value, &component_type);
+                }
+
+                let value = match self.checked_add(delta) {
+                    Some(value) => value,
+                    // overflowed past the bound in the direction of delta
+                    None => match compare(delta, &ComponentValue::Integer(0))? {
+                        Ordering::Less => return Some(min.clone()),
+                        _ => return Some(max.clone()),
+                    },
+                };
+
+                if compare(&value, min)? == Ordering::Less {
+                    Some(min.clone())
+                } else if compare(&value, max)? == Ordering::Greater {
+                    Some(max.clone())
+                } else {
+                    Some(value)
+                }
+            }
+        }
+    }
+}
+
+/// Returns an integer as a value of the type, `None` if
+/// it's out of range
+fn from_i128(value: i128, component_type: &ComponentType) -> Option<ComponentValue> {
+    match component_type {
+        ComponentType::Integer => i64::try_from(value).ok().map(ComponentValue::Integer),
+        ComponentType::U8 => u8::try_from(value).ok().map(ComponentValue::U8),
+        ComponentType::U32 => u32::try_from(value).ok().map(ComponentValue::U32),
+        ComponentType::U64 => u64::try_from(value).ok().map(ComponentValue::U64),
+        ComponentType::Fixed(scale) => {
+            Fixed::from_int(i64::try_from(value).ok()?, *scale).map(ComponentValue::Fixed)
+        }
+        _ => None,
+    }
+}
+
+/// Returns the Float, `None` if it isn't finite
+fn finite(v: f64) -> Option<ComponentValue> {
+    v.is_finite().then_some(ComponentValue::Float(v))
+}
+
+/// Compare numeric values of any type
+///
+/// Integers and Fixeds compare exactly, values
+/// mixed with Floats compare as `f64`
+pub fn compare(value: &ComponentValue, other: &ComponentValue) -> Option<Ordering> {
+    use ComponentValue as V;
+
+    match (value, other) {
+        (V::Fixed(a), V::Fixed(b)) => a.partial_cmp(b),
+        (V::Fixed(a), b) => a.partial_cmp(&Fixed::from_int(b.as_i128()?.try_into().ok()?, 0)?),
+        (a, V::Fixed(b)) => Fixed::from_int(a.as_i128()?.try_into().ok()?, 0)?.partial_cmp(b),
+        (V::Float(a), b) => a.partial_cmp(&as_f64(b)?),
+        (a, V::Float(b)) => as_f64(a)?.partial_cmp(b),
+        (a, b) => Some(a.as_i128()?.cmp(&b.as_i128()?)),
+    }
+}
+
+/// Returns a numeric value as an `f64`
+fn as_f64(value: &ComponentValue) -> Option<f64> {
+    match value {
+        ComponentValue::Float(v) => Some(*v),
+        ComponentValue::Fixed(v) => Some(v.to_f64()),
+        v => v.as_i128().map(|v| v as f64),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fixed() {
+        let price = Fixed::parse("1.25", 2).unwrap();
+        assert_eq!(price.raw(), 125);
+        assert_eq!(price.to_string(), "1.25");
+        assert_eq!(Fixed::parse("-0.5", 3).unwrap().to_string(), "-0.500");
+        assert_eq!(Fixed::parse("7", 0).unwrap().to_string(), "7");
+
+        // not exact
+        assert_eq!(Fixed::parse("1.255", 2), None);
+        assert_eq!(Fixed::parse("1.2.5", 2), None);
+        assert_eq!(Fixed::parse("", 2), None);
+
+        let two = Fixed::from_int(2, 2).unwrap();
+        assert_eq!(price.checked_mul(&two).unwrap().to_string(), "2.50");
+        assert_eq!(price.checked_div(&two).unwrap().to_string(), "0.62");
+        assert_eq!(price.checked_sub(&two).unwrap().to_string(), "-0.75");
+        assert_eq!(price.checked_div(&Fixed::new(0, 2).unwrap()), None);
+        assert_eq!(price.checked_add(&Fixed::new(1, 3).unwrap()), None);
+        assert_eq!(Fixed::new(i64::MAX, 2).unwrap().checked_add(&price), None);
+
+        assert_eq!(
+            Fixed::parse("1.5", 1).partial_cmp(&Fixed::parse("1.50", 2)),
+            Some(Ordering::Equal)
+        );
+    }
+
+    #[test]
+    fn test_checked_cast() {
+        assert_eq!(
+            ComponentValue::Integer(200).checked_cast(&ComponentType::U8),
+            Some(ComponentValue::U8(200))
+        );
+        assert_eq!(
+            ComponentValue::Integer(300).checked_cast(&ComponentType::U8),
+            None
+        );
+        assert_eq!(
+            ComponentValue::Integer(-1).checked_cast(&ComponentType::U64),
+            None
+        );
+        assert_eq!(
+            ComponentValue::U64(u64::MAX).checked_cast(&ComponentType::Integer),
+            None
+        );
+        assert_eq!(
+            ComponentValue::U8(3).checked_cast(&ComponentType::Fixed(2)),
+            Some(ComponentValue::Fixed(Fixed::new(300, 2).unwrap()))
+        );
+        assert_eq!(
+            ComponentValue::Fixed(Fixed::new(150, 2).unwrap()).checked_cast(&ComponentType::U8),
+            None
+        );
+        assert_eq!(
+            ComponentValue::Float(1.0).checked_cast(&ComponentType::Integer),
+            None
+        );
+    }
+
+    #[test]
+    fn test_checked_arithmetic() {
+        assert_eq!(
+            ComponentValue::U8(250).checked_add(&ComponentValue::U8(5)),
+            Some(ComponentValue::U8(255))
+        );
+        assert_eq!(
+            ComponentValue::U8(250).checked_add(&ComponentValue::U8(6)),
+            None
+        );
+        assert_eq!(
+            ComponentValue::U32(1).checked_sub(&ComponentValue::U32(2)),
+            None
+        );
+        assert_eq!(
+            ComponentValue::Integer(1).checked_add(&ComponentValue::U8(1)),
+            None
+        );
+        assert_eq!(
+            ComponentValue::Float(1.0).checked_div(&ComponentValue::Float(0.0)),
+            None
+        );
+
+        assert_eq!(
+            compare(
+                &ComponentValue::U64(u64::MAX),
+                &ComponentValue::Integer(i64::MAX)
+            ),
+            Some(Ordering::Greater)
+        );
+        assert_eq!(
+            compare(
+                &ComponentValue::Fixed(Fixed::new(150, 2).unwrap()),
+                &ComponentValue::U8(1)
+            ),
+            Some(Ordering::Greater)
+        );
+    }
+
+    #[test]
+    fn test_apply() {
+        use ComponentValue as V;
+
+        let hp = V::U32(10);
+        assert_eq!(hp.apply(&ComponentOp::Add(V::Integer(5))), Some(V::U32(15)));
+        assert_eq!(hp.apply(&ComponentOp::Sub(V::Integer(11))), None);
+        assert_eq!(hp.apply(&ComponentOp::Min(V::U32(3))), Some(V::U32(3)));
+        assert_eq!(hp.apply(&ComponentOp::Max(V::U32(3))), Some(V::U32(10)));
+        // unsigned values move down by negative deltas
+        assert_eq!(hp.apply(&ComponentOp::Add(V::Integer(-4))), Some(V::U32(6)));
+        assert_eq!(
+            hp.apply(&ComponentOp::Sub(V::Integer(-4))),
+            Some(V::U32(14))
+        );
+        assert_eq!(hp.apply(&ComponentOp::Add(V::Integer(-11))), None);
+        assert_eq!(V::U8(250).apply(&ComponentOp::Add(V::Integer(6))), None);
+        assert_eq!(
+            V::U64(u64::MAX).apply(&ComponentOp::Sub(V::Integer(i64::MIN))),
+            None
+        );
+        // deltas are signed, bounds of the Component's type
+        assert_eq!(hp.apply(&ComponentOp::Add(V::U32(5))), None);
+        assert_eq!(hp.apply(&ComponentOp::Min(V::Integer(3))), None);
+        assert_eq!(
+            V::Boolean(true).apply(&ComponentOp::Max(V::Boolean(false))),
+            None
+        );
+
+        let clamp = |delta, min, max| ComponentOp::Clamp { delta, min, max };
+        assert_eq!(
+            hp.apply(&clamp(V::Integer(95), V::U32(0), V::U32(100))),
+            Some(V::U32(100))
+        );
+        assert_eq!(
+            hp.apply(&clamp(V::Integer(i64::MAX), V::U32(0), V::U32(100))),
+            Some(V::U32(100))
+        );
+        assert_eq!(
+            hp.apply(&clamp(V::Integer(-7), V::U32(0), V::U32(100))),
+            Some(V::U32(3))
+        );
+        assert_eq!(
+            hp.apply(&clamp(V::Integer(-15), V::U32(2), V::U32(100))),
+            Some(V::U32(2))
+        );
+        assert_eq!(
+            V::U64(5).apply(&clamp(V::Integer(i64::MIN), V::U64(0), V::U64(u64::MAX))),
+            Some(V::U64(0))
+        );
+        assert_eq!(
+            V::Integer(-5).apply(&clamp(
+                V::Integer(i64::MIN),
+                V::Integer(-10),
+                V::Integer(10)
+            )),
+            Some(V::Integer(-10))
+        );
+        assert_eq!(
+            V::Float(0.5).apply(&clamp(V::Float(0.25), V::Float(0.0), V::Float(1.0))),
+            Some(V::Float(0.75))
+        );
+        assert_eq!(
+            V::Fixed(Fixed::new(50, 2).unwrap()).apply(&clamp(
+                V::Fixed(Fixed::new(-75, 2).unwrap()),
+                V::Fixed(Fixed::new(0, 2).unwrap()),
+                V::Fixed(Fixed::new(100, 2).unwrap())
+            )),
+            Some(V::Fixed(Fixed::new(0, 2).unwrap()))
+        );
+        assert_eq!(hp.apply(&clamp(V::Integer(1), V::U32(5), V::U32(1))), None);
+
+        // deltas are cast to the delta type, bounds to the Component's type
+        let op = ComponentOp::Add(V::Integer(2)).checked_cast(&ComponentType::Fixed(2));
+        assert_eq!(
+            op,
+            Some(ComponentOp::Add(V::Fixed(Fixed::new(200, 2).unwrap())))
+        );
+        assert_eq!(
+            ComponentOp::Sub(V::U8(1)).checked_cast(&ComponentType::U8),
+            Some(ComponentOp::Sub(V::Integer(1)))
+        );
+        assert_eq!(
+            ComponentOp::Clamp {
+                delta: V::Integer(-1),
+                min: V::Integer(0),
+                max: V::Integer(10)
+            }
+            .checked_cast(&ComponentType::U8),
+            Some(clamp(V::Integer(-1), V::U8(0), V::U8(10)))
+        );
+        assert_eq!(
+            ComponentOp::Max(V::Integer(-1)).checked_cast(&ComponentType::U8),
+            None
+        );
+        assert_eq!(
+            ComponentOp::Add(V::U64(u64::MAX)).checked_cast(&ComponentType::U64),
+            None
+        );
+    }
+}
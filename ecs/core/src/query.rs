@@ -0,0 +1,302 @@
+//! Instance Queries
+//!
+//! Selects Instances by Region, Entity and Component
+//! predicates, and projects the Components of each match
+//!
+//! ```ignore
+//! let dead = Query::new()
+//!     .region("farm")
+//!     .entity("player")
+//!     .le("hp", 0i64)
+//!     .select(["name"])
+//!     .run(&blueprint);
+//! ```
+
+use crate::{
+    blueprint::{Blueprint, Component, ComponentTree, ComponentValue, Entity, Nonce, Region},
+    numeric::compare,
+};
+use std::cmp::Ordering;
+
+/// Comparison applied by a [`Predicate`]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Comparison {
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+}
+
+/// Condition on a Component's value
+///
+/// Instances without the Component never match
+#[derive(Clone, Debug, PartialEq)]
+pub struct Predicate {
+    pub component: Component,
+    pub comparison: Comparison,
+    pub value: ComponentValue,
+}
+
+impl Predicate {
+    pub fn new(
+        component: impl Into<Component>,
+        comparison: Comparison,
+        value: impl Into<ComponentValue>,
+    ) -> Self {
+        Self {
+            component: component.into(),
+            comparison,
+            value: value.into(),
+        }
+    }
+
+    /// Is `true` if the Component tree satisfies the Predicate
+    ///
+    /// Numeric values are compared across types, Strings
+    /// lexicographically, and values that can't be ordered
+    /// only satisfy [`Comparison::Ne`]
+    pub fn matches(&self, component_tree: &ComponentTree) -> bool {
+        let Some(value) = component_tree.get(&self.component) else {
+            return false;
+        };
+
+        let ordering = match (value, &self.value) {
+            (ComponentValue::String(a), ComponentValue::String(b)) => Some(a.cmp(b)),
+            (a, b) => compare(a, b).or_else(|| (a == b).then_some(Ordering::Equal)),
+        };
+
+        match (self.comparison, ordering) {
+            (Comparison::Ne, o) => o != Some(Ordering::Equal),
+            (_, None) => false,
+            (Comparison::Eq, Some(o)) => o.is_eq(),
+            (Comparison::Lt, Some(o)) => o.is_lt(),
+            (Comparison::Le, Some(o)) => o.is_le(),
+            (Comparison::Gt, Some(o)) => o.is_gt(),
+            (Comparison::Ge, Some(o)) => o.is_ge(),
+        }
+    }
+}
+
+/// Instance matched by a [`Query`]
+#[derive(Clone, Debug, PartialEq)]
+pub struct QueryRow {
+    pub region: Region,
+    pub entity: Entity,
+    pub nonce: Nonce,
+    /// Projected Components of the Instance
+    pub components: ComponentTree,
+}
+
+/// Query over the Instances of a World
+///
+/// An empty Query matches every Instance and
+/// returns all of its Components
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Query {
+    pub region: Option<Region>,
+    pub entity: Option<Entity>,
+    pub predicates: Vec<Predicate>,
+    /// Components returned for each match, all if `None`
+    pub projection: Option<Vec<Component>>,
+}
+
+impl Query {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Only match Instances in a Region
+    pub fn region(mut self, region: impl Into<Region>) -> Self {
+        self.region = Some(region.into());
+        self
+    }
+
+    /// Only match Instances of an Entity
+    pub fn entity(mut self, entity: impl Into<Entity>) -> Self {
+        self.entity = Some(entity.into());
+        self
+    }
+
+    /// Only match Instances satisfying a [`Predicate`]
+    pub fn filter(mut self, predicate: Predicate) -> Self {
+        self.predicates.push(predicate);
+        self
+    }
+
+    pub fn eq(self, component: impl Into<Component>, value: impl Into<ComponentValue>) -> Self {
+        self.filter(Predicate::new(component, Comparison::Eq, value))
+    }
+
+    pub fn ne(self, component: impl Into<Component>, value: impl Into<ComponentValue>) -> Self {
+        self.filter(Predicate::new(component, Comparison::Ne, value))
+    }
+
+    pub fn lt(self, component: impl Into<Component>, value: impl Into<ComponentValue>) -> Self {
+        self.filter(Predicate::new(component, Comparison::Lt, value))
+    }
+
+    pub fn le(self, component: impl Into<Component>, value: impl Into<ComponentValue>) -> Self {
+        self.filter(Predicate::new(component, Comparison::Le, value))
+    }
+
+    pub fn gt(self, component: impl Into<Component>, value: impl Into<ComponentValue>) -> Self {
+        self.filter(Predicate::new(component, Comparison::Gt, value))
+    }
+
+    pub fn ge(self, component: impl Into<Component>, value: impl Into<ComponentValue>) -> Self {
+        self.filter(Predicate::new(component, Comparison::Ge, value))
+    }
+
+    /// Only return these Components for each match
+    pub fn select<I, C>(mut self, components: I) -> Self
+    where
+        I: IntoIterator<Item = C>,
+        C: Into<Component>,
+    {
+        self.projection = Some(components.into_iter().map(Into::into).collect());
+        self
+    }
+
+    /// Is `true` if the Query covers a Region and Entity pair
+    pub fn matches_key(&self, region: &Region, entity: &Entity) -> bool {
+        self.region.as_ref().map_or(true, |r| r == region)
+            && self.entity.as_ref().map_or(true, |e| e == entity)
+    }
+
+    /// Returns the projected row if the Instance matches
+    ///
+    /// Storage backends fetch the Instances under the
+    /// matching keys and filter them through here
+    pub fn apply(
+        &self,
+        region: &Region,
+        entity: &Entity,
+        nonce: Nonce,
+        component_tree: &ComponentTree,
+    ) -> Option<QueryRow> {
+        if !self.matches_key(region, entity)
+            || !self.predicates.iter().all(|p| p.matches(component_tree))
+        {
+            return None;
+        }
+
+        let components = match &self.projection {
+            Some(projection) => component_tree
+                .iter()
+                .filter(|(c, _)| projection.contains(c))
+                .map(|(c, v)| (c.clone(), v.clone()))
+                .collect(),
+            None => component_tree.clone(),
+        };
+
+        Some(QueryRow {
+            region: region.clone(),
+            entity: entity.clone(),
+            nonce,
+            components,
+        })
+    }
+
+    /// Run the Query over the Instances of a [`Blueprint`]
+    ///
+    /// Rows are ordered by Region, Entity and Nonce
+    pub fn run(&self, blueprint: &Blueprint) -> Vec<QueryRow> {
+        blueprint
+            .instances
+            .iter()
+            .flat_map(|(region, entities)| {
+                entities.iter().flat_map(move |(entity, instances)| {
+                    instances
+                        .iter()
+                        .filter_map(move |(nonce, tree)| self.apply(region, entity, *nonce, tree))
+                })
+            })
+            .collect()
+    }
+}
+
+impl Blueprint {
+    /// Run a [`Query`] over the Blueprint's Instances
+    pub fn query(&self, query: &Query) -> Vec<QueryRow> {
+        query.run(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::blueprint::{ComponentType, ComponentTypeTree};
+
+    fn get_sample_blueprint() -> Blueprint {
+        let mut blueprint = Blueprint::new("World".to_string(), "Description".to_string());
+
+        let mut component_type_tree = ComponentTypeTree::new();
+        component_type_tree.insert("name".to_string(), ComponentType::String);
+        component_type_tree.insert("hp".to_string(), ComponentType::Integer);
+        blueprint.add_entity("player".to_string(), component_type_tree);
+
+        let regions = vec!["farm".to_string(), "house".to_string()];
+        blueprint.preload(regions, vec!["player".to_string()]);
+
+        for (region, name, hp) in [
+            ("farm", "alice", 10),
+            ("farm", "bob", 0),
+            ("farm", "carol", -5),
+            ("house", "dave", 0),
+        ] {
+            let mut component_tree = ComponentTree::new();
+            component_tree.insert("name".to_string(), name.into());
+            component_tree.insert("hp".to_string(), ComponentValue::Integer(hp));
+            blueprint
+                .add_instance(region.to_string(), "player".to_string(), component_tree)
+                .unwrap();
+        }
+
+        blueprint
+    }
+
+    #[test]
+    fn test_query() {
+        let mut blueprint = get_sample_blueprint();
+
+        let rows = Query::new()
+            .region("farm")
+            .entity("player")
+            .le("hp", 0i64)
+            .select(["name"])
+            .run(&blueprint);
+
+        let names = rows
+            .iter()
+            .map(|r| (r.nonce, r.components["name"].to_string()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            names,
+            vec![(2, "bob".to_string()), (3, "carol".to_string())]
+        );
+        assert!(rows.iter().all(|r| r.components.len() == 1));
+
+        // every region, compared across numeric types
+        let rows = blueprint.query(&Query::new().eq("hp", 0u8));
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[1].region, "house");
+        assert_eq!(rows[1].components.len(), 2);
+
+        // removed instances no longer match
+        blueprint
+            .remove_instance("farm".to_string(), "player".to_string(), 2)
+            .unwrap();
+        let rows = Query::new()
+            .region("farm")
+            .ne("name", "alice")
+            .run(&blueprint);
+        assert_eq!(rows.len(), 1);
+        assert_eq!(rows[0].nonce, 3);
+
+        // missing components and unordered values never match
+        assert!(Query::new().gt("mana", 0i64).run(&blueprint).is_empty());
+        assert!(Query::new().gt("name", 0i64).run(&blueprint).is_empty());
+    }
+}
@@ -0,0 +1,638 @@
+//! Blueprint Validation
+//!
+//! Checks that the Instances in a [`Blueprint`] follow the
+//! schema of their Entity and that Regions and Entities
+//! reference each other properly
+
+use crate::{
+    blueprint::{
+        Blueprint, Component, ComponentTree, ComponentType, ComponentTypeTree, ComponentValue,
+        Entity, Nonce, Region,
+    },
+    constraint::ConstraintViolation,
+};
+use anyhow::{bail, Result};
+use thiserror::Error;
+
+/// Severity of a [`Diagnostic`]
+///
+/// Only [`Severity::Error`] fails a Blueprint load
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Severity {
+    Error,
+    Warning,
+}
+
+/// Problem found when validating a [`Blueprint`]
+#[derive(Clone, Debug, Error, Eq, PartialEq)]
+pub enum Diagnostic {
+    #[error("{entity} #{nonce} in {region} is missing component {component}")]
+    MissingComponent {
+        region: Region,
+        entity: Entity,
+        nonce: Nonce,
+        component: Component,
+    },
+
+    #[error("{entity} #{nonce} in {region} has undeclared component {component}")]
+    ExtraComponent {
+        region: Region,
+        entity: Entity,
+        nonce: Nonce,
+        component: Component,
+    },
+
+    #[error(
+        "{entity} #{nonce} in {region} component {component} expected {expected}, found {found}"
+    )]
+    TypeMismatch {
+        region: Region,
+        entity: Entity,
+        nonce: Nonce,
+        component: Component,
+        expected: ComponentType,
+        found: ComponentValue,
+    },
+
+    #[error("entity {entity} default for {component} expected {expected}, found {found}")]
+    DefaultTypeMismatch {
+        entity: Entity,
+        component: Component,
+        expected: ComponentType,
+        found: ComponentValue,
+    },
+
+    #[error("{entity} #{nonce} in {region} component {component}: {violation}")]
+    ConstraintViolation {
+        region: Region,
+        entity: Entity,
+        nonce: Nonce,
+        component: Component,
+        violation: ConstraintViolation,
+    },
+
+    #[error("entity {entity} default for {component}: {violation}")]
+    DefaultConstraintViolation {
+        entity: Entity,
+        component: Component,
+        violation: ConstraintViolation,
+    },
+
+    #[error("entity {entity} declares a default for undeclared component {component}")]
+    UndeclaredDefault {
+        entity: Entity,
+        component: Component,
+    },
+
+    #[error("region {region} references undeclared entity {entity}")]
+    UndeclaredEntity { region: Region, entity: Entity },
+
+    #[error("entity {entity} is never placed in a region")]
+    UnplacedEntity { entity: Entity },
+}
+
+impl Diagnostic {
+    /// Returns the [`Severity`] of the Diagnostic
+    pub fn severity(&self) -> Severity {
+        match self {
+            Diagnostic::UnplacedEntity { .. } => Severity::Warning,
+            _ => Severity::Error,
+        }
+    }
+
+    /// Is `true` if the Diagnostic fails validation
+    pub fn is_error(&self) -> bool {
+        self.severity() == Severity::Error
+    }
+}
+
+/// Error returned when a [`Blueprint`] has [`Severity::Error`] Diagnostics
+#[derive(Clone, Debug, Error, Eq, PartialEq)]
+#[error("invalid blueprint:\n{}", render_diagnostics(.0))]
+pub struct ValidationError(pub Vec<Diagnostic>);
+
+fn render_diagnostics(diagnostics: &[Diagnostic]) -> String {
+    diagnostics
+        .iter()
+        .map(|d| format!("  - {d}"))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Options for [`Blueprint::validate_with`]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct ValidationOptions {
+    /// Convert Integer values into Floats where the
+    /// Entity's schema expects an `f64` instead of
+    /// reporting a [`Diagnostic::TypeMismatch`]
+    pub coerce_int_to_float: bool,
+}
+
+impl Blueprint {
+    /// Validate the Blueprint against its Entity schemas
+    ///
+    /// Returns every [`Diagnostic`] found, in Region, Entity
+    /// and Instance order. An empty list means the Blueprint
+    /// is valid
+    pub fn validate(&self) -> Vec<Diagnostic> {
+        let mut diagnostics = Vec::new();
+
+        // regions must only reference declared entities
+        for (region, entities) in self.regions.iter() {
+            for entity in entities.iter() {
+                if !self.entities.contains_key(entity) {
+                    diagnostics.push(Diagnostic::UndeclaredEntity {
+                        region: region.clone(),
+                        entity: entity.clone(),
+                    });
+                }
+            }
+        }
+
+        // declared defaults must follow their entity's schema
+        for (entity, defaults) in self.defaults.iter() {
+            let Some(component_type_tree) = self.entities.get(entity) else {
+                continue;
+            };
+
+            for (component, value) in defaults.iter() {
+                match component_type_tree.get(component) {
+                    None => diagnostics.push(Diagnostic::UndeclaredDefault {
+                        entity: entity.clone(),
+                        component: component.clone(),
+                    }),
+                    Some(component_type) if !component_type.matches(value) => {
+                        diagnostics.push(Diagnostic::DefaultTypeMismatch {
+                            entity: entity.clone(),
+                            component: component.clone(),
+                            expected: component_type.clone(),
+                            found: value.clone(),
+                        })
+                    }
+                    _ => {
+                        if let Err(violation) = self.check_constraint(entity, component, value) {
+                            diagnostics.push(Diagnostic::DefaultConstraintViolation {
+                                entity: entity.clone(),
+                                component: component.clone(),
+                                violation,
+                            })
+                        }
+                    }
+                }
+            }
+        }
+
+        // instances must follow their entity's schema
+        for (region, entities) in self.instances.iter() {
+            for (entity, instances) in entities.iter() {
+                let Some(component_type_tree) = self.entities.get(entity) else {
+                    continue;
+                };
+
+                for (nonce, instance) in instances.iter() {
+                    for (component, component_type) in component_type_tree.iter() {
+                        match instance.get(component) {
+                            None => diagnostics.push(Diagnostic::MissingComponent {
+                                region: region.clone(),
+                                entity: entity.clone(),
+                                nonce: *nonce,
+                                component: component.clone(),
+                            }),
+                            Some(value) if !component_type.matches(value) => {
+                                diagnostics.push(Diagnostic::TypeMismatch {
+                                    region: region.clone(),
+                                    entity: entity.clone(),
+                                    nonce: *nonce,
+                                    component: component.clone(),
+                                    expected: component_type.clone(),
+                                    found: value.clone(),
+                                })
+                            }
+                            Some(value) => {
+                                if let Err(violation) =
+                                    self.check_constraint(entity, component, value)
+                                {
+                                    diagnostics.push(Diagnostic::ConstraintViolation {
+                                        region: region.clone(),
+                                        entity: entity.clone(),
+                                        nonce: *nonce,
+                                        component: component.clone(),
+                                        violation,
+                                    })
+                                }
+                            }
+                        }
+                    }
+
+                    for component in instance.keys() {
+                        if !component_type_tree.contains_key(component) {
+                            diagnostics.push(Diagnostic::ExtraComponent {
+                                region: region.clone(),
+                                entity: entity.clone(),
+                                nonce: *nonce,
+                                component: component.clone(),
+                            });
+                        }
+                    }
+                }
+            }
+        }
+
+        // entities should be placed in at least one region
+        for entity in self.entities.keys() {
+            let is_placed = self.regions.values().any(|e| e.contains(entity));
+            if !is_placed {
+                diagnostics.push(Diagnostic::UnplacedEntity {
+                    entity: entity.clone(),
+                });
+            }
+        }
+
+        diagnostics
+    }
+
+    /// Validate the Blueprint with [`ValidationOptions`]
+    ///
+    /// Conforms table values to their Struct schema and
+    /// applies the opted-in coercions to the Instances
+    /// before validating
+    pub fn validate_with(&mut self, options: ValidationOptions) -> Vec<Diagnostic> {
+        self.map_values(|t, v| t.conform(v));
+
+        if options.coerce_int_to_float {
+            self.map_values(coerce_int_to_float);
+        }
+
+        self.validate()
+    }
+
+    /// Validate the Blueprint and fail on errors
+    ///
+    /// Returns the remaining [`Severity::Warning`] Diagnostics
+    /// or a [`ValidationError`] holding every error found
+    pub fn ensure_valid(&mut self, options: ValidationOptions) -> Result<Vec<Diagnostic>> {
+        let (errors, warnings): (Vec<_>, Vec<_>) = self
+            .validate_with(options)
+            .into_iter()
+            .partition(Diagnostic::is_error);
+
+        if !errors.is_empty() {
+            bail!(ValidationError(errors));
+        }
+
+        Ok(warnings)
+    }
+
+    /// Check a value against the Component's declared
+    /// [`Constraint`](crate::constraint::Constraint), if any
+    fn check_constraint(
+        &self,
+        entity: &Entity,
+        component: &Component,
+        value: &ComponentValue,
+    ) -> Result<(), ConstraintViolation> {
+        match self.constraints.get(entity).and_then(|c| c.get(component)) {
+            Some(constraint) => constraint.check(value),
+            None => Ok(()),
+        }
+    }
+
+    /// Replace every default and Instance value with the
+    /// result of `f` on the value and its schema type
+    fn map_values(&mut self, f: fn(&ComponentType, ComponentValue) -> ComponentValue) {
+        for (entity, defaults) in self.defaults.iter_mut() {
+            let Some(component_type_tree) = self.entities.get(entity) else {
+                continue;
+            };
+
+            map_component_tree(component_type_tree, defaults, f);
+        }
+
+        for entities in self.instances.values_mut() {
+            for (entity, instances) in entities.iter_mut() {
+                let Some(component_type_tree) = self.entities.get(entity) else {
+                    continue;
+                };
+
+                for instance in instances.values_mut() {
+                    map_component_tree(component_type_tree, instance, f);
+                }
+            }
+        }
+    }
+}
+
+/// Replace the values of a [`ComponentTree`] declared in
+/// the [`ComponentTypeTree`] with the result of `f`
+fn map_component_tree(
+    component_type_tree: &ComponentTypeTree,
+    component_tree: &mut ComponentTree,
+    f: fn(&ComponentType, ComponentValue) -> ComponentValue,
+) {
+    for (component, value) in component_tree.iter_mut() {
+        if let Some(component_type) = component_type_tree.get(component) {
+            *value = f(component_type, value.clone());
+        }
+    }
+}
+
+/// Convert Integer values into Floats where the
+/// [`ComponentType`] expects an `f64`, including
+/// the elements of collections
+fn coerce_int_to_float(component_type: &ComponentType, value: ComponentValue) -> ComponentValue {
+    match (component_type, value) {
+        (ComponentType::Float, ComponentValue::Integer(v)) => ComponentValue::Float(v as f64),
+        (ComponentType::List(t), ComponentValue::List(values)) => ComponentValue::List(
+            values
+                .into_iter()
+                .map(|v| coerce_int_to_float(t, v))
+                .collect(),
+        ),
+        (ComponentType::Map(t), ComponentValue::Map(values)) => ComponentValue::Map(
+            values
+                .into_iter()
+                .map(|(k, v)| (k, coerce_int_to_float(t, v)))
+                .collect(),
+        ),
+        (ComponentType::Struct(fields), ComponentValue::Struct(values)) => ComponentValue::Struct(
+            values
+                .into_iter()
+                .map(|(k, v)| match fields.get(&k) {
+                    Some(t) => (k, coerce_int_to_float(t, v)),
+                    None => (k, v),
+                })
+                .collect(),
+        ),
+        (_, value) => value,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::constraint::{Constraint, ConstraintTree};
+    use std::collections::BTreeMap;
+
+    fn get_sample_blueprint(instance: ComponentTree) -> Blueprint {
+        let mut blueprint = Blueprint::new("Test World".to_string(), "Test".to_string());
+        let region = String::from("region");
+        let entity = String::from("entity");
+
+        blueprint.preload(vec![region.clone()], vec![entity.clone()]);
+        blueprint.add_region(region.clone(), vec![entity.clone()]);
+
+        let mut component_type_tree: ComponentTypeTree = BTreeMap::new();
+        component_type_tree.insert("x".to_string(), ComponentType::Float);
+        component_type_tree.insert("name".to_string(), ComponentType::String);
+        blueprint.add_entity(entity.clone(), component_type_tree);
+
+        blueprint.add_instance(region, entity, instance).unwrap();
+
+        blueprint
+    }
+
+    #[test]
+    fn test_validate_instances() {
+        let mut instance: ComponentTree = BTreeMap::new();
+        instance.insert("x".to_string(), ComponentValue::Integer(0));
+        instance.insert("y".to_string(), ComponentValue::Integer(0));
+        let blueprint = get_sample_blueprint(instance);
+
+        let diagnostics = blueprint.validate();
+
+        assert_eq!(
+            diagnostics,
+            vec![
+                Diagnostic::MissingComponent {
+                    region: "region".to_string(),
+                    entity: "entity".to_string(),
+                    nonce: 1,
+                    component: "name".to_string(),
+                },
+                Diagnostic::TypeMismatch {
+                    region: "region".to_string(),
+                    entity: "entity".to_string(),
+                    nonce: 1,
+                    component: "x".to_string(),
+                    expected: ComponentType::Float,
+                    found: ComponentValue::Integer(0),
+                },
+                Diagnostic::ExtraComponent {
+                    region: "region".to_string(),
+                    entity: "entity".to_string(),
+                    nonce: 1,
+                    component: "y".to_string(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_validate_regions_and_entities() {
+        let mut instance: ComponentTree = BTreeMap::new();
+        instance.insert("x".to_string(), ComponentValue::Float(0.0));
+        instance.insert(
+            "name".to_string(),
+            ComponentValue::String("npc".to_string()),
+        );
+        let mut blueprint = get_sample_blueprint(instance);
+
+        blueprint.add_region("region".to_string(), vec!["ghost".to_string()]);
+
+        let diagnostics = blueprint.validate();
+
+        assert_eq!(
+            diagnostics,
+            vec![
+                Diagnostic::UndeclaredEntity {
+                    region: "region".to_string(),
+                    entity: "ghost".to_string(),
+                },
+                Diagnostic::UnplacedEntity {
+                    entity: "entity".to_string(),
+                },
+            ]
+        );
+        assert_eq!(diagnostics[1].severity(), Severity::Warning);
+    }
+
+    #[test]
+    fn test_ensure_valid_coerce_int_to_float() {
+        let mut instance: ComponentTree = BTreeMap::new();
+        instance.insert("x".to_string(), ComponentValue::Integer(7));
+        instance.insert(
+            "name".to_string(),
+            ComponentValue::String("npc".to_string()),
+        );
+        let mut blueprint = get_sample_blueprint(instance);
+
+        let err = blueprint
+            .clone()
+            .ensure_valid(ValidationOptions::default())
+            .unwrap_err();
+        assert!(err.downcast_ref::<ValidationError>().is_some());
+
+        let options = ValidationOptions {
+            coerce_int_to_float: true,
+        };
+        let warnings = blueprint.ensure_valid(options).unwrap();
+
+        assert!(warnings.is_empty());
+        assert_eq!(
+            blueprint.instances["region"]["entity"][&1]["x"],
+            ComponentValue::Float(7.0)
+        );
+    }
+
+    #[test]
+    fn test_validate_defaults() {
+        let mut instance: ComponentTree = BTreeMap::new();
+        instance.insert("x".to_string(), ComponentValue::Float(0.0));
+        instance.insert(
+            "name".to_string(),
+            ComponentValue::String("npc".to_string()),
+        );
+        let mut blueprint = get_sample_blueprint(instance);
+
+        let mut defaults: ComponentTree = BTreeMap::new();
+        defaults.insert("x".to_string(), ComponentValue::Integer(1));
+        defaults.insert("hp".to_string(), ComponentValue::Integer(100));
+        blueprint.add_defaults("entity".to_string(), defaults);
+
+        assert_eq!(
+            blueprint.validate(),
+            vec![
+                Diagnostic::UndeclaredDefault {
+                    entity: "entity".to_string(),
+                    component: "hp".to_string(),
+                },
+                Diagnostic::DefaultTypeMismatch {
+                    entity: "entity".to_string(),
+                    component: "x".to_string(),
+                    expected: ComponentType::Float,
+                    found: ComponentValue::Integer(1),
+                },
+            ]
+        );
+
+        let options = ValidationOptions {
+            coerce_int_to_float: true,
+        };
+        blueprint.validate_with(options);
+
+        assert_eq!(
+            blueprint.defaults["entity"]["x"],
+            ComponentValue::Float(1.0)
+        );
+    }
+
+    #[test]
+    fn test_validate_constraints() {
+        let mut instance: ComponentTree = BTreeMap::new();
+        instance.insert("x".to_string(), ComponentValue::Float(-1.0));
+        instance.insert(
+            "name".to_string(),
+            ComponentValue::String("npc".to_string()),
+        );
+        let mut blueprint = get_sample_blueprint(instance);
+
+        let mut constraints = ConstraintTree::new();
+        constraints.insert(
+            "x".to_string(),
+            Constraint {
+                min: Some(ComponentValue::Float(0.0)),
+                ..Default::default()
+            },
+        );
+        constraints.insert(
+            "name".to_string(),
+            Constraint {
+                max_len: Some(2),
+                ..Default::default()
+            },
+        );
+        blueprint.add_constraints("entity".to_string(), constraints);
+
+        let mut defaults: ComponentTree = BTreeMap::new();
+        defaults.insert("x".to_string(), ComponentValue::Float(-5.0));
+        blueprint.add_defaults("entity".to_string(), defaults);
+
+        assert_eq!(
+            blueprint.validate(),
+            vec![
+                Diagnostic::DefaultConstraintViolation {
+                    entity: "entity".to_string(),
+                    component: "x".to_string(),
+                    violation: ConstraintViolation::BelowMin {
+                        value: ComponentValue::Float(-5.0),
+                        min: ComponentValue::Float(0.0),
+                    },
+                },
+                Diagnostic::ConstraintViolation {
+                    region: "region".to_string(),
+                    entity: "entity".to_string(),
+                    nonce: 1,
+                    component: "name".to_string(),
+                    violation: ConstraintViolation::TooLong { len: 3, max_len: 2 },
+                },
+                Diagnostic::ConstraintViolation {
+                    region: "region".to_string(),
+                    entity: "entity".to_string(),
+                    nonce: 1,
+                    component: "x".to_string(),
+                    violation: ConstraintViolation::BelowMin {
+                        value: ComponentValue::Float(-1.0),
+                        min: ComponentValue::Float(0.0),
+                    },
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_set_component_value_constraints() {
+        let mut instance: ComponentTree = BTreeMap::new();
+        instance.insert("x".to_string(), ComponentValue::Float(0.0));
+        instance.insert(
+            "name".to_string(),
+            ComponentValue::String("npc".to_string()),
+        );
+        let mut blueprint = get_sample_blueprint(instance);
+
+        let mut constraints = ConstraintTree::new();
+        constraints.insert(
+            "x".to_string(),
+            Constraint {
+                max: Some(ComponentValue::Float(10.0)),
+                ..Default::default()
+            },
+        );
+        blueprint.add_constraints("entity".to_string(), constraints);
+
+        let region = "region".to_string();
+        let entity = "entity".to_string();
+        let x = "x".to_string();
+
+        blueprint
+            .set_component_value(
+                region.clone(),
+                entity.clone(),
+                1,
+                x.clone(),
+                ComponentValue::Float(10.0),
+            )
+            .unwrap();
+
+        let err = blueprint
+            .set_component_value(region, entity, 1, x, ComponentValue::Float(11.0))
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "entity component x: value 11 is greater than max 10"
+        );
+        assert_eq!(
+            blueprint.instances["region"]["entity"][&1]["x"],
+            ComponentValue::Float(10.0)
+        );
+    }
+}
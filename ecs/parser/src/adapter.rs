@@ -1,10 +1,16 @@
 //! Parser Adapter
 //!
 //! Used for parsing Blueprint from the following
-//! supported formats: `TOML`
+//! supported formats: `TOML`, `JSON`
+//!
+//! and emitting Blueprint back to: `TOML`
 
+use crate::partial::{merge_partials, PartialBlueprint};
 use anyhow::Result;
-use rush_ecs_core::blueprint::{Blueprint, BlueprintString};
+use rush_ecs_core::{
+    blueprint::{Blueprint, BlueprintString},
+    validation::ValidationOptions,
+};
 
 /// Parser Trait
 ///
@@ -16,10 +22,50 @@ use rush_ecs_core::blueprint::{Blueprint, BlueprintString};
 // Parser is Send + Sync to enable concurrent parsing
 // Parser is 'static for dynamic dispatch with Box
 pub trait Parser: Send + Sync + 'static {
+    /// File extensions of the Blueprint sources this
+    /// Parser reads (e.g. `toml`)
+    fn extensions(&self) -> &'static [&'static str];
+
+    /// Parse [`String`] holding part of a Blueprint
+    /// to a [`PartialBlueprint`]
+    fn parse_partial(&self, blueprint_string: BlueprintString) -> Result<PartialBlueprint>;
+
+    /// Parse [`String`] to [`Blueprint`] without validating
+    /// its Instances against the Entity schemas
+    fn parse_unvalidated(&self, blueprint_string: BlueprintString) -> Result<Blueprint> {
+        merge_partials(vec![self.parse_partial(blueprint_string)?])
+    }
+
     /// Parse [`String`] to [`Blueprint`]
-    fn parse_string(&self, blueprint_string: BlueprintString) -> Result<Blueprint>;
+    ///
+    /// Fails if the [`Blueprint`] does not pass
+    /// [`Blueprint::validate`]
+    fn parse_string(&self, blueprint_string: BlueprintString) -> Result<Blueprint> {
+        let mut blueprint = self.parse_unvalidated(blueprint_string)?;
+        blueprint.ensure_valid(ValidationOptions::default())?;
+        Ok(blueprint)
+    }
 
     // TODO: Implement
     // Parse [`str`] to [`Blueprint`]
     // fn parse_str(path: &Path) -> Result<Blueprint>;
 }
+
+/// Emitter Trait
+///
+/// Counterpart of the [`Parser`] trait, writes a
+/// [`Blueprint`] back out in a Blueprint file format
+///
+/// Output is canonical: the same [`Blueprint`] always
+/// emits the same [`String`], and parsing it back with the
+/// format's [`Parser`] gives an equal [`Blueprint`]
+///
+pub trait Emitter: Send + Sync + 'static {
+    /// Emit a [`PartialBlueprint`] to [`String`]
+    fn emit_partial(&self, partial: &PartialBlueprint) -> Result<BlueprintString>;
+
+    /// Emit a [`Blueprint`] to [`String`]
+    fn emit(&self, blueprint: &Blueprint) -> Result<BlueprintString> {
+        self.emit_partial(&PartialBlueprint::from(blueprint))
+    }
+}
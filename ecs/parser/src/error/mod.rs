@@ -1 +1,213 @@
-pub mod utils;
+//! Parser Errors
+//!
+//! Problems found while parsing a Blueprint, located
+//! by file, line and column
+
+use std::{
+    fmt::{self, Display},
+    ops::Range,
+    path::PathBuf,
+};
+use thiserror::Error;
+
+/// Location of a [`ParseError`] in the Blueprint source
+///
+/// Lines and columns are 1-based
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Span {
+    pub line: usize,
+    pub column: usize,
+    /// Length of the span in characters, clamped
+    /// to the end of `line`
+    pub len: usize,
+    /// Source text of `line`, used for rendering
+    pub source_line: String,
+}
+
+impl Span {
+    /// Locate a byte range in the source text
+    pub fn from_range(source: &str, range: Range<usize>) -> Self {
+        let start = range.start.min(source.len());
+        let end = range.end.clamp(start, source.len());
+
+        let line_start = source[..start].rfind('\n').map_or(0, |i| i + 1);
+        let line_end = source[start..]
+            .find('\n')
+            .map_or(source.len(), |i| start + i);
+
+        let line = source[..start].matches('\n').count() + 1;
+        let column = source[line_start..start].chars().count() + 1;
+        let len = source[start..end.min(line_end)].chars().count().max(1);
+        let source_line = source[line_start..line_end].trim_end_matches('\r');
+
+        Self {
+            line,
+            column,
+            len,
+            source_line: source_line.to_string(),
+        }
+    }
+
+    /// Locate a 1-based line and column in the source text
+    pub fn at(source: &str, line: usize, column: usize) -> Self {
+        let line_start = source
+            .split_inclusive('\n')
+            .take(line.saturating_sub(1))
+            .map(str::len)
+            .sum::<usize>();
+
+        // column counts characters, not bytes
+        let start = source[line_start..]
+            .char_indices()
+            .nth(column.saturating_sub(1))
+            .map_or(source.len(), |(i, _)| line_start + i);
+
+        Self::from_range(source, start..start + 1)
+    }
+}
+
+/// Problem found while parsing a Blueprint
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ParseError {
+    /// File the Blueprint was loaded from, if any
+    pub path: Option<PathBuf>,
+    /// Where the problem is, if it can be pinned down
+    pub span: Option<Span>,
+    /// Human-readable description of the problem
+    pub message: String,
+}
+
+impl ParseError {
+    /// Create a [`ParseError`] for a byte range in the source text
+    pub fn new(message: impl Into<String>, source: &str, range: Option<Range<usize>>) -> Self {
+        Self {
+            path: None,
+            span: range.map(|r| Span::from_range(source, r)),
+            message: message.into(),
+        }
+    }
+
+    /// Render the error in the style of `rustc`
+    ///
+    /// ```text
+    /// error: World name must be a string
+    ///  --> blueprint/world.toml:2:8
+    ///   |
+    /// 2 | name = 143
+    ///   |        ^^^
+    /// ```
+    pub fn render(&self) -> String {
+        let mut rendered = format!("error: {}", self.message);
+
+        let path = self
+            .path
+            .as_ref()
+            .map_or("<blueprint>".to_string(), |p| p.display().to_string());
+
+        match &self.span {
+            Some(span) => {
+                let gutter = " ".repeat(span.line.to_string().len());
+                rendered += &format!(
+                    "\n{gutter}--> {path}:{}:{}\n{gutter} |\n{} | {}\n{gutter} | {}{}",
+                    span.line,
+                    span.column,
+                    span.line,
+                    span.source_line,
+                    " ".repeat(span.column - 1),
+                    "^".repeat(span.len),
+                );
+            }
+            None if self.path.is_some() => rendered += &format!("\n --> {path}"),
+            None => {}
+        }
+
+        rendered
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(path) = &self.path {
+            write!(f, "{}:", path.display())?;
+        }
+
+        if let Some(span) = &self.span {
+            write!(f, "{}:{}:", span.line, span.column)?;
+        }
+
+        if self.path.is_some() || self.span.is_some() {
+            write!(f, " ")?;
+        }
+
+        write!(f, "{}", self.message)
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// Every [`ParseError`] found in a Blueprint
+#[derive(Clone, Debug, Error, Eq, PartialEq)]
+#[error("{}", .0.iter().map(|e| e.to_string()).collect::<Vec<_>>().join("\n"))]
+pub struct ParseErrors(pub Vec<ParseError>);
+
+impl ParseErrors {
+    /// Render every error in the style of `rustc`,
+    /// followed by a summary line
+    pub fn render(&self) -> String {
+        let count = self.0.len();
+        let plural = if count == 1 { "" } else { "s" };
+
+        let mut rendered = self
+            .0
+            .iter()
+            .map(ParseError::render)
+            .collect::<Vec<_>>()
+            .join("\n\n");
+
+        rendered +=
+            &format!("\n\nerror: could not parse blueprint due to {count} previous error{plural}");
+
+        rendered
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_span_from_range() {
+        let source = "[world]\nname = 143\n";
+        let start = source.find("143").unwrap();
+        let span = Span::from_range(source, start..start + 3);
+
+        assert_eq!(span.line, 2);
+        assert_eq!(span.column, 8);
+        assert_eq!(span.len, 3);
+        assert_eq!(span.source_line, "name = 143");
+
+        let span = Span::at(source, 2, 8);
+        assert_eq!((span.line, span.column, span.len), (2, 8, 1));
+    }
+
+    #[test]
+    fn test_parse_error_render() {
+        let source = "[world]\nname = 143\n";
+        let start = source.find("143").unwrap();
+        let mut error = ParseError::new(
+            "World name must be a string",
+            source,
+            Some(start..start + 3),
+        );
+        error.path = Some(PathBuf::from("blueprint/world.toml"));
+
+        assert_eq!(
+            error.render(),
+            "error: World name must be a string\n --> blueprint/world.toml:2:8\n  |\n2 | name = 143\n  |        ^^^"
+        );
+        assert_eq!(
+            error.to_string(),
+            "blueprint/world.toml:2:8: World name must be a string"
+        );
+    }
+}
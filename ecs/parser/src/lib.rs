@@ -1,6 +1,7 @@
 mod adapter;
 pub mod error;
 pub mod loader;
+pub mod partial;
 mod ports;
 pub mod utils;
 
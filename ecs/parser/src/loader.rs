@@ -1,9 +1,15 @@
 use super::adapter::Parser;
-use crate::utils::{dir_to_string, file_to_string};
-use anyhow::Result;
-use rush_ecs_core::blueprint::Blueprint;
+use crate::{
+    error::ParseErrors,
+    json::JsonParser,
+    partial::merge_partials,
+    toml::TomlParser,
+    utils::{dir_to_paths, is_blueprint_file},
+};
+use anyhow::{bail, Result};
+use rush_ecs_core::{blueprint::Blueprint, validation::ValidationOptions};
 use std::{
-    fs::{canonicalize, metadata},
+    fs::{canonicalize, metadata, read_to_string},
     path::Path,
 };
 
@@ -11,34 +17,246 @@ use std::{
 ///
 /// Load [`Blueprint`] from file or directory
 ///
+/// Each file is read by the [`Parser`] for its extension.
+/// The default Loader reads `.toml` and `.json` files
+///
+/// Loaded Blueprints are validated with the default
+/// [`ValidationOptions`] unless configured otherwise
+///
 pub struct Loader {
-    parser: Box<dyn Parser>,
+    parsers: Vec<Box<dyn Parser>>,
+    validation: Option<ValidationOptions>,
+}
+
+impl Default for Loader {
+    fn default() -> Self {
+        Self::new(TomlParser::default()).with_parser(JsonParser::default())
+    }
 }
 
 impl Loader {
     /// Create a new [`Blueprint`] [`Loader`]
+    ///
+    /// Files with an extension no [`Parser`] claims
+    /// are read with the first one
     pub fn new(parser: impl Parser) -> Self {
         Self {
-            parser: Box::new(parser),
+            parsers: vec![Box::new(parser)],
+            validation: Some(ValidationOptions::default()),
         }
     }
 
+    /// Read files with the [`Parser`]'s extensions using it
+    pub fn with_parser(mut self, parser: impl Parser) -> Self {
+        self.parsers.push(Box::new(parser));
+        self
+    }
+
+    /// Validate loaded Blueprints with the given [`ValidationOptions`]
+    pub fn with_validation(mut self, options: ValidationOptions) -> Self {
+        self.validation = Some(options);
+        self
+    }
+
+    /// Skip validation of loaded Blueprints
+    pub fn without_validation(mut self) -> Self {
+        self.validation = None;
+        self
+    }
+
+    /// Returns the [`Parser`] for the file's extension
+    fn parser_for(&self, path: &Path) -> &dyn Parser {
+        let extension = path.extension().and_then(|e| e.to_str());
+
+        self.parsers
+            .iter()
+            .find(|p| extension.is_some_and(|e| p.extensions().contains(&e)))
+            .unwrap_or(&self.parsers[0])
+            .as_ref()
+    }
+
     /// Load Blueprint
     ///
     /// Loads [`Blueprint`] from a specific [`Path`]
     ///
     /// [`Path`] can be a **file** or **directory**;
+    ///
+    /// Each Blueprint source in a directory is parsed on its
+    /// own, in file name order, and merged. Files no
+    /// [`Parser`] reads (e.g. READMEs, swap files) are skipped
     pub fn load_blueprint(&self, path: &Path) -> Result<Blueprint> {
         // expecting a valid path
         let abs_path = canonicalize(path)?;
-        let md = metadata(abs_path).expect("invalid path");
+        let md = metadata(abs_path)?;
+
+        // get blueprint sources from file or directory
+        let extensions = self
+            .parsers
+            .iter()
+            .flat_map(|p| p.extensions().iter().copied())
+            .collect::<Vec<_>>();
+        let paths = match md.is_dir() {
+            true => dir_to_paths(path)
+                .into_iter()
+                .filter(|p| is_blueprint_file(p, &extensions))
+                .collect(),
+            false => vec![path.to_path_buf()],
+        };
+
+        // parse every source, collecting errors across files
+        let mut partials = Vec::with_capacity(paths.len());
+        let mut errors = Vec::new();
+        for filepath in paths.into_iter() {
+            let blueprint_string = read_to_string(&filepath)?;
+
+            match self.parser_for(&filepath).parse_partial(blueprint_string) {
+                Ok(mut partial) => {
+                    partial.path = Some(filepath);
+                    partials.push(partial);
+                }
+                Err(e) => match e.downcast::<ParseErrors>() {
+                    Ok(ParseErrors(file_errors)) => {
+                        errors.extend(file_errors.into_iter().map(|mut error| {
+                            error.path = Some(filepath.clone());
+                            error
+                        }))
+                    }
+                    Err(e) => return Err(e),
+                },
+            }
+        }
+
+        if !errors.is_empty() {
+            bail!(ParseErrors(errors));
+        }
 
-        // get blueprint string from file or directory
-        let blueprint_string = match md.is_dir() {
-            true => dir_to_string(path),
-            false => file_to_string(path),
+        let mut blueprint = match merge_partials(partials) {
+            Ok(b) => b,
+            Err(e) => match e.downcast::<ParseErrors>() {
+                // errors not pinned to a file belong to the whole Blueprint
+                Ok(ParseErrors(mut errors)) => {
+                    for error in errors.iter_mut() {
+                        error.path.get_or_insert_with(|| path.to_path_buf());
+                    }
+                    bail!(ParseErrors(errors))
+                }
+                Err(e) => return Err(e),
+            },
         };
 
-        self.parser.parse_string(blueprint_string)
+        if let Some(options) = self.validation {
+            blueprint.ensure_valid(options)?;
+        }
+
+        Ok(blueprint)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rush_ecs_core::blueprint::ComponentValue;
+
+    #[test]
+    fn test_load_blueprint_coerce_int_to_float() {
+        let path = Path::new("mock/fixtures/loader/int_to_float.toml");
+
+        let loader = Loader::new(TomlParser::default());
+        assert!(loader.load_blueprint(path).is_err());
+
+        let loader = Loader::new(TomlParser::default()).with_validation(ValidationOptions {
+            coerce_int_to_float: true,
+        });
+        let blueprint = loader.load_blueprint(path).unwrap();
+
+        assert_eq!(
+            blueprint.instances["farm"]["apple"][&1]["x"],
+            ComponentValue::Float(0.0)
+        );
+    }
+
+    #[test]
+    fn test_load_blueprint_locates_errors_in_directory() {
+        let path = Path::new("mock/fixtures/loader/invalid_directory");
+
+        let loader = Loader::new(TomlParser::default());
+        let err = loader.load_blueprint(path).unwrap_err();
+        let ParseErrors(errors) = err.downcast_ref::<ParseErrors>().unwrap();
+
+        assert_eq!(errors.len(), 2);
+
+        assert_eq!(errors[0].path, Some(path.join("entity.toml")),);
+        assert_eq!(errors[0].span.as_ref().unwrap().line, 2);
+
+        assert_eq!(errors[1].path, Some(path.join("farm.toml")));
+        assert_eq!(errors[1].span.as_ref().unwrap().line, 2);
+        assert_eq!(
+            errors[1].span.as_ref().unwrap().source_line,
+            "apple = [{ x = 1979-05-27 }]"
+        );
+    }
+
+    #[test]
+    fn test_load_blueprint_merges_directory() {
+        let path = Path::new("mock/fixtures/loader/split_directory");
+
+        let loader = Loader::new(TomlParser::default());
+        let blueprint = loader.load_blueprint(path).unwrap();
+
+        assert_eq!(blueprint.name, "Split World");
+        assert_eq!(
+            blueprint.entities.keys().collect::<Vec<_>>(),
+            vec!["apple", "chair"]
+        );
+        assert_eq!(blueprint.instances["farm"]["apple"].len(), 2);
+        assert_eq!(blueprint.instances["house"]["chair"].len(), 1);
+    }
+
+    #[test]
+    fn test_load_blueprint_duplicate_definitions() {
+        let path = Path::new("mock/fixtures/loader/duplicate_directory");
+
+        let loader = Loader::new(TomlParser::default());
+        let err = loader.load_blueprint(path).unwrap_err();
+        let ParseErrors(errors) = err.downcast_ref::<ParseErrors>().unwrap();
+
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].path, Some(path.join("house.toml")));
+        assert_eq!(
+            errors[0].message,
+            format!(
+                "Entity apple is defined in both {} and {}",
+                path.join("farm.toml").display(),
+                path.join("house.toml").display()
+            )
+        );
+    }
+
+    #[test]
+    fn test_load_blueprint_autodetect_extension() {
+        let loader = Loader::default();
+
+        let toml_blueprint = loader
+            .load_blueprint(Path::new("mock/blueprint/file.toml"))
+            .unwrap();
+        let json_blueprint = loader
+            .load_blueprint(Path::new("mock/blueprint/file.json"))
+            .unwrap();
+
+        assert_eq!(toml_blueprint, json_blueprint);
+    }
+
+    #[test]
+    fn test_load_blueprint_mixed_directory() {
+        let path = Path::new("mock/fixtures/loader/mixed_directory");
+
+        let loader = Loader::default();
+        let blueprint = loader.load_blueprint(path).unwrap();
+
+        assert_eq!(blueprint.name, "Mixed World");
+        assert_eq!(
+            blueprint.instances["farm"]["apple"][&1]["y"],
+            ComponentValue::Float(2.0)
+        );
     }
 }
@@ -0,0 +1,646 @@
+//! Partial Blueprint
+//!
+//! A Blueprint source may hold only part of a World, like
+//! a directory Blueprint with a file per Region. Each source
+//! is parsed on its own into a [`PartialBlueprint`] and the
+//! partials are merged into a single [`Blueprint`]
+
+use crate::error::{ParseError, ParseErrors, Span};
+use anyhow::{bail, Result};
+use rush_ecs_core::{
+    blueprint::{
+        Blueprint, ComponentTree, ComponentType, ComponentTypeTree, ComponentValue, Entity, Nonce,
+        Region,
+    },
+    constraint::ConstraintTree,
+};
+use std::{collections::BTreeMap, path::PathBuf};
+
+/// Value defined in a Blueprint source and where it was defined
+#[derive(Clone, Debug, PartialEq)]
+pub struct Defined<T> {
+    pub value: T,
+    pub span: Option<Span>,
+}
+
+impl<T> Defined<T> {
+    pub fn new(value: T, span: Option<Span>) -> Self {
+        Self { value, span }
+    }
+}
+
+/// Schema of an Entity in an Entity table
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct PartialEntity {
+    pub component_types: ComponentTypeTree,
+    /// Declared default values of the Components
+    pub defaults: ComponentTree,
+    /// Declared Constraints of the Components
+    pub constraints: ConstraintTree,
+}
+
+/// Component types accepted in an Entity's schema
+pub const COMPONENT_TYPES: &str =
+    "String, i64, f64, bool, u8, u32, u64, Fixed<N>, [T], Map<String, T>, { field: T }";
+
+/// Properties of a Component declared in its long form
+pub const COMPONENT_PROPERTIES: [&str; 6] = ["type", "default", "min", "max", "one_of", "max_len"];
+
+/// Check a `min` or `max` bound declared for a Component
+///
+/// Bounds are only allowed on numeric Components, Integer
+/// bounds of an `f64` Component are converted
+pub fn constraint_bound(
+    key: &str,
+    component_type: &ComponentType,
+    value: ComponentValue,
+) -> Result<ComponentValue, String> {
+    use ComponentType::*;
+
+    if !matches!(component_type, Integer | Float | U8 | U32 | U64 | Fixed(_)) {
+        return Err(format!(
+            "{key} is only allowed on numeric components, found {component_type}"
+        ));
+    }
+
+    match (component_type, component_type.conform(value)) {
+        (Float, ComponentValue::Integer(v)) => Ok(ComponentValue::Float(v as f64)),
+        (t, v) if t.matches(&v) => Ok(v),
+        (t, v) => Err(format!("{key} must be a {t}, found {v}")),
+    }
+}
+
+/// Check a `one_of` value declared for a Component
+///
+/// Integer values of an `f64` Component are converted
+pub fn constraint_option(
+    component_type: &ComponentType,
+    value: ComponentValue,
+) -> Result<ComponentValue, String> {
+    match (component_type, component_type.conform(value)) {
+        (ComponentType::Float, ComponentValue::Integer(v)) => Ok(ComponentValue::Float(v as f64)),
+        (t, v) if t.matches(&v) => Ok(v),
+        (t, v) => Err(format!("one_of values must be a {t}, found {v}")),
+    }
+}
+
+/// Check a `max_len` declared for a Component
+///
+/// Only allowed on `String` and List Components
+pub fn constraint_max_len(
+    component_type: &ComponentType,
+    value: ComponentValue,
+) -> Result<usize, String> {
+    if !matches!(
+        component_type,
+        ComponentType::String | ComponentType::List(_)
+    ) {
+        return Err(format!(
+            "max_len is only allowed on String and list components, found {component_type}"
+        ));
+    }
+
+    match value {
+        ComponentValue::Integer(v) if v >= 0 => Ok(v as usize),
+        _ => Err("max_len must be a non-negative integer".to_string()),
+    }
+}
+
+/// Key of an Instance's Nonce in its table, Entities
+/// can't have a Component with this name
+pub const NONCE_KEY: &str = "nonce";
+
+/// Check the `nonce` declared for an Instance
+///
+/// Nonces are 1-based, same as onchain
+pub fn instance_nonce(value: ComponentValue) -> Result<Nonce, String> {
+    match value {
+        ComponentValue::Integer(v) if v > 0 => Ok(v as Nonce),
+        ComponentValue::U64(v) if v > 0 => Ok(v),
+        _ => Err("nonce must be a positive integer".to_string()),
+    }
+}
+
+/// Check the `nonces` declared in a World table, the last
+/// Nonce issued per Region and Entity
+pub fn world_nonces(value: ComponentValue) -> Result<PartialNonces, String> {
+    let error = || "World nonces must be a table of Regions, Entities and their last nonce";
+
+    let ComponentValue::Map(regions) = value else {
+        return Err(error().to_string());
+    };
+
+    let mut nonces = PartialNonces::new();
+    for (region, entities) in regions.into_iter() {
+        let ComponentValue::Map(entities) = entities else {
+            return Err(error().to_string());
+        };
+
+        for (entity, nonce) in entities.into_iter() {
+            let nonce = match nonce {
+                ComponentValue::Integer(v) if v >= 0 => v as Nonce,
+                ComponentValue::U64(v) => v,
+                _ => return Err(error().to_string()),
+            };
+            nonces
+                .entry(region.clone())
+                .or_default()
+                .insert(entity, nonce);
+        }
+    }
+
+    Ok(nonces)
+}
+
+/// Entity schemas in an Entity table
+pub type PartialEntities = Vec<(Defined<Entity>, PartialEntity)>;
+
+/// Instance in a Region table
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct PartialInstance {
+    /// Declared Nonce, the next one is issued if `None`
+    pub nonce: Option<Nonce>,
+    pub components: ComponentTree,
+}
+
+/// Instances of each Entity in a Region table
+pub type PartialRegion = Vec<(Defined<Entity>, Vec<PartialInstance>)>;
+
+/// Last Nonce issued per Region and Entity
+pub type PartialNonces = BTreeMap<Region, BTreeMap<Entity, Nonce>>;
+
+/// Properties of a World table
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct PartialWorld {
+    pub name: Option<Defined<String>>,
+    pub description: Option<Defined<String>>,
+    pub regions: Option<Defined<Vec<Defined<Region>>>>,
+    /// Last Nonces issued past the last Instance,
+    /// keeps the Nonces of removed Instances tombstoned
+    pub nonces: Option<Defined<PartialNonces>>,
+}
+
+/// Part of a [`Blueprint`] parsed from a single source
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct PartialBlueprint {
+    /// File the partial was parsed from, if any
+    pub path: Option<PathBuf>,
+    /// World table, if the source has one
+    pub world: Option<Defined<PartialWorld>>,
+    /// Entity table, if the source has one
+    pub entities: Option<Defined<PartialEntities>>,
+    /// Region tables with the Instances of each Entity
+    pub regions: Vec<(Region, PartialRegion)>,
+}
+
+impl From<&Blueprint> for PartialBlueprint {
+    /// Split a [`Blueprint`] back into its World, Entity
+    /// and Region tables
+    fn from(blueprint: &Blueprint) -> Self {
+        let mut world = PartialWorld {
+            name: Some(Defined::new(blueprint.name.clone(), None)),
+            description: Some(Defined::new(blueprint.description.clone(), None)),
+            regions: Some(Defined::new(
+                blueprint
+                    .regions
+                    .keys()
+                    .map(|r| Defined::new(r.clone(), None))
+                    .collect(),
+                None,
+            )),
+            nonces: None,
+        };
+
+        let entities = blueprint
+            .entities
+            .iter()
+            .map(|(e, tree)| {
+                let entity = PartialEntity {
+                    component_types: tree.clone(),
+                    defaults: blueprint.defaults.get(e).cloned().unwrap_or_default(),
+                    constraints: blueprint.constraints.get(e).cloned().unwrap_or_default(),
+                };
+                (Defined::new(e.clone(), None), entity)
+            })
+            .collect();
+
+        // a Region table lists the Region's Entities,
+        // even when they have no Instances
+        let regions = blueprint
+            .regions
+            .iter()
+            .map(|(region, region_entities)| {
+                let instances = blueprint.instances.get(region);
+
+                let mut entity_names = region_entities.iter().collect::<Vec<_>>();
+                if let Some(instances) = instances {
+                    entity_names.extend(
+                        instances
+                            .iter()
+                            .filter(|(_, i)| !i.is_empty())
+                            .map(|(e, _)| e),
+                    );
+                }
+                entity_names.sort();
+                entity_names.dedup();
+
+                let entities = entity_names
+                    .into_iter()
+                    .map(|e| {
+                        // Instances are listed in Nonce order
+                        let instances = instances
+                            .and_then(|i| i.get(e))
+                            .map(|i| {
+                                i.iter()
+                                    .map(|(nonce, tree)| PartialInstance {
+                                        nonce: Some(*nonce),
+                                        components: tree.clone(),
+                                    })
+                                    .collect()
+                            })
+                            .unwrap_or_default();
+                        (Defined::new(e.clone(), None), instances)
+                    })
+                    .collect();
+
+                (region.clone(), entities)
+            })
+            .collect();
+
+        // only Nonces issued past the last Instance are
+        // lost without being written
+        let mut nonces = PartialNonces::new();
+        for (region, entities) in blueprint.nonces.iter() {
+            for (entity, last) in entities.iter() {
+                let last_instance = blueprint
+                    .instances
+                    .get(region)
+                    .and_then(|i| i.get(entity))
+                    .and_then(|i| i.keys().next_back())
+                    .copied()
+                    .unwrap_or_default();
+
+                if *last > last_instance {
+                    nonces
+                        .entry(region.clone())
+                        .or_default()
+                        .insert(entity.clone(), *last);
+                }
+            }
+        }
+
+        if !nonces.is_empty() {
+            world.nonces = Some(Defined::new(nonces, None));
+        }
+
+        Self {
+            path: None,
+            world: Some(Defined::new(world, None)),
+            entities: Some(Defined::new(entities, None)),
+            regions,
+        }
+    }
+}
+
+/// First definition of a merged property
+struct Origin {
+    path: Option<PathBuf>,
+    span: Option<Span>,
+}
+
+/// Merges [`PartialBlueprint`]s into a [`Blueprint`]
+///
+/// Tables are a union of the properties defined in each
+/// partial. A property defined in more than one partial
+/// is an error naming both files
+#[derive(Default)]
+struct Merger {
+    name: Option<(String, Origin)>,
+    description: Option<(String, Origin)>,
+    regions: Option<(Vec<Defined<Region>>, Origin)>,
+    nonces: Option<(PartialNonces, Origin)>,
+    entities: BTreeMap<Entity, (PartialEntity, Origin)>,
+    instances: BTreeMap<Region, BTreeMap<Entity, (Vec<PartialInstance>, Origin)>>,
+    /// First World table found, for errors about missing properties
+    world: Option<Origin>,
+    /// First Entity table found, for errors about missing Entities
+    entity_table: Option<Origin>,
+    errors: Vec<ParseError>,
+}
+
+impl Merger {
+    /// Record an error at a span in the given file
+    fn error(&mut self, message: impl Into<String>, path: &Option<PathBuf>, span: Option<Span>) {
+        self.errors.push(ParseError {
+            path: path.clone(),
+            span,
+            message: message.into(),
+        });
+    }
+
+    fn merge(&mut self, partial: PartialBlueprint) {
+        let path = partial.path;
+
+        // WORLD
+
+        if let Some(world) = partial.world {
+            if self.world.is_none() {
+                self.world = Some(Origin {
+                    path: path.clone(),
+                    span: world.span,
+                });
+            }
+
+            let PartialWorld {
+                name,
+                description,
+                regions,
+                nonces,
+            } = world.value;
+
+            if let Some(name) = name {
+                let existing = self.name.as_ref().map(|(_, o)| o);
+                match define("World name".to_string(), existing, &path, name.span) {
+                    Ok(origin) => {
+                        self.name = Some((name.value, origin));
+                    }
+                    Err(e) => self.errors.push(e),
+                }
+            }
+
+            if let Some(description) = description {
+                let existing = self.description.as_ref().map(|(_, o)| o);
+                match define(
+                    "World description".to_string(),
+                    existing,
+                    &path,
+                    description.span,
+                ) {
+                    Ok(origin) => {
+                        self.description = Some((description.value, origin));
+                    }
+                    Err(e) => self.errors.push(e),
+                }
+            }
+
+            if let Some(regions) = regions {
+                let existing = self.regions.as_ref().map(|(_, o)| o);
+                match define("World regions".to_string(), existing, &path, regions.span) {
+                    Ok(origin) => {
+                        self.regions = Some((regions.value, origin));
+                    }
+                    Err(e) => self.errors.push(e),
+                }
+            }
+
+            if let Some(nonces) = nonces {
+                let existing = self.nonces.as_ref().map(|(_, o)| o);
+                match define("World nonces".to_string(), existing, &path, nonces.span) {
+                    Ok(origin) => {
+                        self.nonces = Some((nonces.value, origin));
+                    }
+                    Err(e) => self.errors.push(e),
+                }
+            }
+        }
+
+        // ENTITY
+
+        if let Some(entities) = partial.entities {
+            if self.entity_table.is_none() {
+                self.entity_table = Some(Origin {
+                    path: path.clone(),
+                    span: entities.span,
+                });
+            }
+
+            for (entity, partial_entity) in entities.value.into_iter() {
+                let existing = self.entities.get(&entity.value).map(|(_, o)| o);
+                let what = format!("Entity {}", entity.value);
+                match define(what, existing, &path, entity.span) {
+                    Ok(origin) => {
+                        self.entities.insert(entity.value, (partial_entity, origin));
+                    }
+                    Err(e) => self.errors.push(e),
+                }
+            }
+        }
+
+        // REGIONS
+
+        for (region, entities) in partial.regions.into_iter() {
+            // region tables may be empty
+            self.instances.entry(region.clone()).or_default();
+
+            for (entity, instances) in entities.into_iter() {
+                let existing = self
+                    .instances
+                    .get(&region)
+                    .and_then(|e| e.get(&entity.value))
+                    .map(|(_, o)| o);
+                let what = format!("{} instances in {region}", entity.value);
+                match define(what, existing, &path, entity.span) {
+                    Ok(origin) => {
+                        self.instances
+                            .get_mut(&region)
+                            .unwrap()
+                            .insert(entity.value, (instances, origin));
+                    }
+                    Err(e) => self.errors.push(e),
+                }
+            }
+        }
+    }
+
+    fn into_blueprint(mut self) -> Result<Blueprint> {
+        // the World must be fully defined across all partials
+        let (world_path, world_span) = match &self.world {
+            Some(origin) => (origin.path.clone(), origin.span.clone()),
+            None => {
+                self.error("World table must exist", &None, None);
+                (None, None)
+            }
+        };
+
+        if self.world.is_some() {
+            if self.name.is_none() {
+                self.error("World must have a name", &world_path, world_span.clone());
+            }
+            if self.description.is_none() {
+                self.error(
+                    "World must have a description",
+                    &world_path,
+                    world_span.clone(),
+                );
+            }
+            if self.regions.is_none() {
+                self.error(
+                    "World must have a regions property",
+                    &world_path,
+                    world_span,
+                );
+            }
+        }
+
+        match &self.entity_table {
+            None => self.error("Entity table must exist", &None, None),
+            Some(origin) if self.entities.is_empty() => {
+                let (path, span) = (origin.path.clone(), origin.span.clone());
+                self.error("Entity table must have at least 1 entity", &path, span);
+            }
+            _ => {}
+        }
+
+        // every region stated in the world table must have
+        // a table of instances in the blueprint
+        let regions = self
+            .regions
+            .take()
+            .map(|(regions, origin)| (regions, origin.path))
+            .unwrap_or_default();
+        for region in regions.0.iter() {
+            if !self.instances.contains_key(&region.value) {
+                let message = format!("Region {} table must exist", region.value);
+                self.error(message, &regions.1, region.span.clone());
+            }
+        }
+
+        // declared Nonces must be unique per Region and Entity
+        for (region, entities) in self.instances.iter() {
+            for (entity, (instances, origin)) in entities.iter() {
+                let mut nonces = instances.iter().filter_map(|i| i.nonce).collect::<Vec<_>>();
+                nonces.sort();
+                let duplicates = nonces.windows(2).filter(|n| n[0] == n[1]).map(|n| n[0]);
+
+                for nonce in duplicates.collect::<Vec<_>>().into_iter() {
+                    let message =
+                        format!("{entity} #{nonce} in {region} is defined more than once");
+                    self.errors.push(ParseError {
+                        path: origin.path.clone(),
+                        span: origin.span.clone(),
+                        message,
+                    });
+                }
+            }
+        }
+
+        if !self.errors.is_empty() {
+            // report in file and source order
+            self.errors
+                .sort_by_key(|e| (e.path.clone(), e.span.as_ref().map(|s| (s.line, s.column))));
+            bail!(ParseErrors(self.errors));
+        }
+
+        // create Blueprint
+        let name = self.name.map(|(n, _)| n).unwrap_or_default();
+        let description = self.description.map(|(d, _)| d).unwrap_or_default();
+        let mut blueprint = Blueprint::new(name, description);
+
+        // preload Instance Keys
+        blueprint.preload(
+            regions.0.iter().map(|r| r.value.clone()).collect(),
+            self.entities.keys().cloned().collect(),
+        );
+
+        // load Entities into World
+        for (entity, (partial_entity, _)) in self.entities.into_iter() {
+            blueprint.add_entity(entity.clone(), partial_entity.component_types);
+            blueprint.add_defaults(entity.clone(), partial_entity.defaults);
+            blueprint.add_constraints(entity, partial_entity.constraints);
+        }
+
+        // issued Nonces first, so removed Instances stay tombstoned
+        for (region, entities) in self.nonces.map(|(n, _)| n).unwrap_or_default() {
+            for (entity, nonce) in entities.into_iter() {
+                blueprint.reserve_nonce(region.clone(), entity, nonce);
+            }
+        }
+
+        // load Regions and their Instances, region tables
+        // not stated in the world table are ignored
+        for region in regions.0.into_iter() {
+            let Some(entities) = self.instances.remove(&region.value) else {
+                continue;
+            };
+
+            blueprint.add_region(region.value.clone(), entities.keys().cloned().collect());
+
+            for (entity, (instances, _)) in entities.into_iter() {
+                // undeclared entities are reported by Blueprint::validate
+                if !blueprint.entities.contains_key(&entity) {
+                    continue;
+                }
+
+                // Instances without a Nonce are issued one
+                // after the declared ones
+                let (declared, issued): (Vec<_>, Vec<_>) =
+                    instances.into_iter().partition(|i| i.nonce.is_some());
+
+                // preload guarantees the Region and Entity keys,
+                // declared Nonces are unique
+                for instance in declared.into_iter() {
+                    let _ = blueprint.insert_instance(
+                        region.value.clone(),
+                        entity.clone(),
+                        instance.nonce.unwrap_or_default(),
+                        instance.components,
+                    );
+                }
+                for instance in issued.into_iter() {
+                    let _ = blueprint.add_instance(
+                        region.value.clone(),
+                        entity.clone(),
+                        instance.components,
+                    );
+                }
+            }
+        }
+
+        Ok(blueprint)
+    }
+}
+
+/// Define a merged property, failing if it was already
+/// defined in another partial
+fn define(
+    what: String,
+    existing: Option<&Origin>,
+    path: &Option<PathBuf>,
+    span: Option<Span>,
+) -> Result<Origin, ParseError> {
+    match existing {
+        Some(origin) => Err(ParseError {
+            path: path.clone(),
+            span,
+            message: format!(
+                "{what} is defined in both {} and {}",
+                display_path(&origin.path),
+                display_path(path)
+            ),
+        }),
+        None => Ok(Origin {
+            path: path.clone(),
+            span,
+        }),
+    }
+}
+
+fn display_path(path: &Option<PathBuf>) -> String {
+    path.as_ref()
+        .map_or("<blueprint>".to_string(), |p| p.display().to_string())
+}
+
+/// Merge [`PartialBlueprint`]s into a [`Blueprint`]
+///
+/// Partials are merged in the given order. Fails with
+/// [`ParseErrors`] when a property is defined more than
+/// once or the merged World is incomplete
+pub fn merge_partials(partials: Vec<PartialBlueprint>) -> Result<Blueprint> {
+    let mut merger = Merger::default();
+
+    for partial in partials.into_iter() {
+        merger.merge(partial);
+    }
+
+    merger.into_blueprint()
+}
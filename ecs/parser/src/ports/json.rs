@@ -0,0 +1,611 @@
+//! Parser Port for JSON File Format
+
+use crate::{
+    adapter::Parser,
+    error::{ParseError, ParseErrors, Span},
+    partial::{
+        constraint_bound, constraint_max_len, constraint_option, instance_nonce, world_nonces,
+        Defined, PartialBlueprint, PartialEntities, PartialEntity, PartialInstance, PartialWorld,
+        COMPONENT_PROPERTIES, COMPONENT_TYPES, NONCE_KEY,
+    },
+};
+use anyhow::{bail, Result};
+use rush_ecs_core::{
+    blueprint::{BlueprintString, ComponentType, ComponentValue},
+    constraint::Constraint,
+};
+use serde_json::{Map, Value};
+use std::collections::BTreeMap;
+
+/// JSON Blueprint Parser
+///
+/// This [`Parser`] expects a Blueprint [`String`] with
+/// the same World, Entity and Region model as the
+/// [`TomlParser`](crate::toml::TomlParser)
+///
+/// Example
+///
+/// ```json
+/// {
+///     "world": {
+///         "name": "Sonic's World",
+///         "description": "This is Sonic's world",
+///         "regions": ["farm", "house"]
+///     },
+///     "entity": {
+///         "player": { "name": "String", "x": "f64", "y": "f64" },
+///         "apple": { "x": "f64", "y": "f64" }
+///     },
+///     "farm": {
+///         "player": [{ "name": "npc", "x": 0.0, "y": 0.0 }],
+///         "apple": [{ "x": 0.0, "y": 0.0 }]
+///     },
+///     "house": {
+///         "player": [{ "name": "npc", "x": 0.0, "y": 0.0 }]
+///     }
+/// }
+/// ```
+///
+/// Floats must be written with a decimal point (e.g. `0.0`),
+/// otherwise they are read as `i64`
+///
+#[derive(Clone, Debug, Default)]
+pub struct JsonParser {}
+
+impl Parser for JsonParser {
+    fn extensions(&self) -> &'static [&'static str] {
+        &["json"]
+    }
+
+    fn parse_partial(&self, blueprint_string: BlueprintString) -> Result<PartialBlueprint> {
+        // JSON syntax errors stop parsing early
+        let document = match serde_json::from_str::<Value>(&blueprint_string) {
+            Ok(d) => d,
+            Err(e) => bail!(ParseErrors(vec![ParseError {
+                path: None,
+                span: Some(Span::at(&blueprint_string, e.line(), e.column())),
+                message: json_error_message(&e),
+            }])),
+        };
+
+        // Blueprint errors are collected across the whole document,
+        // JSON values don't keep their position so errors name
+        // the property instead
+        let mut ctx = JsonContext::default();
+        let partial = match document.as_object() {
+            Some(object) => ctx.parse_partial(object),
+            None => {
+                ctx.error("Blueprint must be an object");
+                PartialBlueprint::default()
+            }
+        };
+
+        if !ctx.errors.is_empty() {
+            bail!(ParseErrors(ctx.errors));
+        }
+
+        Ok(partial)
+    }
+}
+
+/// Strip the position serde_json appends to its messages,
+/// it is reported in the [`Span`] instead
+fn json_error_message(error: &serde_json::Error) -> String {
+    let message = error.to_string();
+    let position = format!(" at line {} column {}", error.line(), error.column());
+
+    message
+        .strip_suffix(&position)
+        .unwrap_or(&message)
+        .to_string()
+}
+
+/// Collects every [`ParseError`] found while walking
+/// a JSON Blueprint document
+#[derive(Default)]
+struct JsonContext {
+    errors: Vec<ParseError>,
+}
+
+impl JsonContext {
+    /// Record an error
+    fn error(&mut self, message: impl Into<String>) {
+        self.errors.push(ParseError {
+            path: None,
+            span: None,
+            message: message.into(),
+        });
+    }
+
+    fn parse_partial(&mut self, object: &Map<String, Value>) -> PartialBlueprint {
+        let mut partial = PartialBlueprint::default();
+
+        for (key, value) in object.iter() {
+            match key.as_str() {
+                // WORLD
+                "world" => partial.world = self.parse_world(value),
+
+                // ENTITY
+                "entity" => partial.entities = self.parse_entities(value),
+
+                // REGIONS
+                _ => {
+                    let Some(region_object) = value.as_object() else {
+                        self.error(format!("Region {key} must be an object"));
+                        continue;
+                    };
+
+                    let mut entities = Vec::new();
+                    for (entity_name, instances_value) in region_object.iter() {
+                        let instances = self.parse_instances(key, entity_name, instances_value);
+                        entities.push((Defined::new(entity_name.to_string(), None), instances));
+                    }
+
+                    partial.regions.push((key.to_string(), entities));
+                }
+            }
+        }
+
+        partial
+    }
+
+    /// Parse the World object
+    fn parse_world(&mut self, world_value: &Value) -> Option<Defined<PartialWorld>> {
+        let Some(world_object) = world_value.as_object() else {
+            self.error("World must be an object");
+            return None;
+        };
+
+        let mut world = PartialWorld {
+            name: self.parse_world_string(world_object, "name"),
+            description: self.parse_world_string(world_object, "description"),
+            regions: None,
+            nonces: None,
+        };
+
+        if let Some(regions_value) = world_object.get("regions") {
+            match regions_value.as_array() {
+                None => self.error("World regions property must be an array"),
+                Some(array) if array.is_empty() => self.error("World must have at least 1 region"),
+                Some(array) => {
+                    let mut regions = Vec::new();
+                    for region in array.iter() {
+                        match region.as_str() {
+                            Some(r) => regions.push(Defined::new(r.to_string(), None)),
+                            None => {
+                                self.error("World regions property must be an array of strings")
+                            }
+                        }
+                    }
+                    world.regions = Some(Defined::new(regions, None));
+                }
+            }
+        }
+
+        if let Some(nonces_value) = world_object.get("nonces") {
+            let nonces = match json_component_value(nonces_value) {
+                Some(v) => world_nonces(v),
+                None => Err(
+                    "World nonces must be an object of Regions, Entities and their last nonce"
+                        .to_string(),
+                ),
+            };
+
+            match nonces {
+                Ok(n) => world.nonces = Some(Defined::new(n, None)),
+                Err(e) => self.error(e),
+            }
+        }
+
+        Some(Defined::new(world, None))
+    }
+
+    /// Parse a string property of the World object
+    fn parse_world_string(
+        &mut self,
+        world_object: &Map<String, Value>,
+        key: &str,
+    ) -> Option<Defined<String>> {
+        let value = world_object.get(key)?;
+
+        match value.as_str() {
+            Some(v) => Some(Defined::new(v.to_string(), None)),
+            None => {
+                self.error(format!("World {key} must be a string"));
+                None
+            }
+        }
+    }
+
+    /// Parse the Entity object into each Entity's schema
+    fn parse_entities(&mut self, entity_value: &Value) -> Option<Defined<PartialEntities>> {
+        let Some(entity_object) = entity_value.as_object() else {
+            self.error("Entity must be an object");
+            return None;
+        };
+
+        let mut entities = Vec::new();
+
+        for (entity_name, component_value) in entity_object.iter() {
+            // must be an object of properties e.g. { "x": "f64", "y": "f64" }
+            let Some(component_object) = component_value.as_object() else {
+                self.error(format!(
+                    "Entity {entity_name} must be an object of component types"
+                ));
+                continue;
+            };
+
+            let mut partial_entity = PartialEntity::default();
+            for (component_name, component_value) in component_object.iter() {
+                if component_name == NONCE_KEY {
+                    self.error(format!(
+                        "Entity {entity_name} component {NONCE_KEY} is reserved for the Instance nonce"
+                    ));
+                    continue;
+                }
+
+                let Some((component_type, default, constraint)) =
+                    self.parse_component(entity_name, component_name, component_value)
+                else {
+                    continue;
+                };
+
+                partial_entity
+                    .component_types
+                    .insert(component_name.to_string(), component_type);
+                if let Some(default) = default {
+                    partial_entity
+                        .defaults
+                        .insert(component_name.to_string(), default);
+                }
+                if !constraint.is_empty() {
+                    partial_entity
+                        .constraints
+                        .insert(component_name.to_string(), constraint);
+                }
+            }
+
+            entities.push((Defined::new(entity_name.to_string(), None), partial_entity));
+        }
+
+        Some(Defined::new(entities, None))
+    }
+
+    /// Parse a Component of an Entity's schema
+    ///
+    /// Accepts the short form `"hp": "i64"` or the long
+    /// form `"hp": { "type": "i64", "default": 100, "min": 0 }`
+    fn parse_component(
+        &mut self,
+        entity_name: &str,
+        component_name: &str,
+        component_value: &Value,
+    ) -> Option<(ComponentType, Option<ComponentValue>, Constraint)> {
+        let Some(component_object) = component_value.as_object() else {
+            let component_type =
+                self.parse_component_type(entity_name, component_name, component_value)?;
+            return Some((component_type, None, Constraint::default()));
+        };
+
+        for key in component_object.keys() {
+            if !COMPONENT_PROPERTIES.contains(&key.as_str()) {
+                self.error(format!(
+                    "Entity {entity_name} component {component_name} has unknown property {key}, expected one of {}",
+                    COMPONENT_PROPERTIES.join(", ")
+                ));
+            }
+        }
+
+        let Some(type_value) = component_object.get("type") else {
+            self.error(format!(
+                "Entity {entity_name} component {component_name} must have a type"
+            ));
+            return None;
+        };
+
+        let component_type = self.parse_component_type(entity_name, component_name, type_value)?;
+
+        // default type is checked against the schema by Blueprint::validate
+        let default = match component_object.get("default") {
+            Some(value) => match json_component_value(value) {
+                Some(v) => Some(v),
+                None => {
+                    self.error(format!(
+                        "Unsupported data type {} for default of {component_name} of {entity_name}",
+                        json_type_name(value)
+                    ));
+                    return None;
+                }
+            },
+            None => None,
+        };
+
+        let mut constraint = Constraint::default();
+        let mut errors = Vec::new();
+
+        for key in ["min", "max"] {
+            let Some(value) = component_object.get(key) else {
+                continue;
+            };
+
+            let bound = match json_component_value(value) {
+                Some(v) => constraint_bound(key, &component_type, v),
+                None => Err(format!("{key} must be a {component_type}")),
+            };
+
+            match bound {
+                Ok(v) if key == "min" => constraint.min = Some(v),
+                Ok(v) => constraint.max = Some(v),
+                Err(e) => errors.push(e),
+            }
+        }
+
+        if let Some(value) = component_object.get("one_of") {
+            match value.as_array() {
+                Some(array) if !array.is_empty() => {
+                    let mut one_of = Vec::new();
+                    for value in array.iter() {
+                        let option = match json_component_value(value) {
+                            Some(v) => constraint_option(&component_type, v),
+                            None => Err(format!("one_of values must be a {component_type}")),
+                        };
+
+                        match option {
+                            Ok(v) => one_of.push(v),
+                            Err(e) => errors.push(e),
+                        }
+                    }
+                    constraint.one_of = Some(one_of);
+                }
+                _ => errors.push("one_of must be a non-empty array".to_string()),
+            }
+        }
+
+        if let Some(value) = component_object.get("max_len") {
+            let max_len = match json_component_value(value) {
+                Some(v) => constraint_max_len(&component_type, v),
+                None => Err("max_len must be a non-negative integer".to_string()),
+            };
+
+            match max_len {
+                Ok(v) => constraint.max_len = Some(v),
+                Err(e) => errors.push(e),
+            }
+        }
+
+        if !errors.is_empty() {
+            for e in errors.into_iter() {
+                self.error(format!(
+                    "Entity {entity_name} component {component_name} {e}"
+                ));
+            }
+            return None;
+        }
+
+        Some((component_type, default, constraint))
+    }
+
+    /// Parse the type of a Component, e.g. `"i64"`
+    fn parse_component_type(
+        &mut self,
+        entity_name: &str,
+        component_name: &str,
+        type_value: &Value,
+    ) -> Option<ComponentType> {
+        let Some(type_name) = type_value.as_str() else {
+            self.error(format!(
+                "Entity {entity_name} component {component_name} type must be a string"
+            ));
+            return None;
+        };
+
+        match type_name.parse::<ComponentType>() {
+            Ok(t) => Some(t),
+            Err(e) => {
+                self.error(format!(
+                    "Entity {entity_name} component {component_name}: {e}, expected one of {COMPONENT_TYPES}"
+                ));
+                None
+            }
+        }
+    }
+
+    /// Parse the Instances of an Entity in a Region,
+    /// each with an optional `nonce`
+    fn parse_instances(
+        &mut self,
+        region_name: &str,
+        entity_name: &str,
+        instances_value: &Value,
+    ) -> Vec<PartialInstance> {
+        let mut instances = Vec::new();
+
+        let Some(array) = instances_value.as_array() else {
+            self.error(format!(
+                "{entity_name} instances in {region_name} must be an array of objects"
+            ));
+            return instances;
+        };
+
+        for instance_value in array.iter() {
+            let Some(instance_object) = instance_value.as_object() else {
+                self.error(format!(
+                    "{entity_name} instance in {region_name} must be an object"
+                ));
+                continue;
+            };
+
+            // build each entity's component tree
+            let mut instance = PartialInstance::default();
+
+            // get (component, value) pairs
+            for (component, value) in instance_object.iter() {
+                if component == NONCE_KEY {
+                    let nonce = match json_component_value(value) {
+                        Some(v) => instance_nonce(v),
+                        None => Err("nonce must be a positive integer".to_string()),
+                    };
+
+                    match nonce {
+                        Ok(n) => instance.nonce = Some(n),
+                        Err(e) => {
+                            self.error(format!("{entity_name} instance in {region_name} {e}"))
+                        }
+                    }
+                    continue;
+                }
+
+                let value = match json_component_value(value) {
+                    Some(v) => v,
+                    None => {
+                        self.error(format!(
+                            "Unsupported data type {} for {component} of {entity_name} in {region_name}",
+                            json_type_name(value)
+                        ));
+                        continue;
+                    }
+                };
+
+                instance.components.insert(component.to_string(), value);
+            }
+
+            instances.push(instance);
+        }
+
+        instances
+    }
+}
+
+/// Returns the [`ComponentValue`] of a JSON value,
+/// if its type is supported
+fn json_component_value(value: &Value) -> Option<ComponentValue> {
+    match value {
+        Value::String(v) => Some(ComponentValue::String(v.to_string())),
+        Value::Bool(v) => Some(ComponentValue::Boolean(*v)),
+        Value::Number(v) if v.is_i64() => v.as_i64().map(ComponentValue::Integer),
+        Value::Number(v) if v.is_f64() => v.as_f64().map(ComponentValue::Float),
+        // u64 past i64::MAX
+        Value::Number(v) if v.is_u64() => v.as_u64().map(ComponentValue::U64),
+        Value::Array(array) => array
+            .iter()
+            .map(json_component_value)
+            .collect::<Option<Vec<_>>>()
+            .map(ComponentValue::List),
+        // Maps become Structs when validated against a Struct schema
+        Value::Object(object) => object
+            .iter()
+            .map(|(k, v)| json_component_value(v).map(|v| (k.to_string(), v)))
+            .collect::<Option<BTreeMap<_, _>>>()
+            .map(ComponentValue::Map),
+        _ => None,
+    }
+}
+
+/// Returns the JSON name of the value's type
+fn json_type_name(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "boolean",
+        Value::Number(_) => "number",
+        Value::String(_) => "string",
+        Value::Array(_) => "array",
+        Value::Object(_) => "object",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::file_to_string;
+    use std::path::Path;
+
+    #[test]
+    fn test_json_parser_file() {
+        let path = Path::new("mock/blueprint/file.json");
+        let blueprint_string = file_to_string(path);
+
+        let json_parser = JsonParser::default();
+        let blueprint = json_parser.parse_string(blueprint_string).unwrap();
+
+        assert_eq!(blueprint.name, "Sonic's World");
+        assert_eq!(
+            blueprint.regions.keys().collect::<Vec<_>>(),
+            vec!["farm", "house"]
+        );
+        assert_eq!(blueprint.entities["player"]["speed"], ComponentType::Float);
+        assert_eq!(
+            blueprint.instances["farm"]["apple"][&1]["x"],
+            ComponentValue::Float(0.0)
+        );
+    }
+
+    #[test]
+    fn test_json_parser_syntax_error() {
+        let blueprint_string = "{\n  \"world\": {\n    \"name\": \"Test\",,\n  }\n}".to_string();
+
+        let json_parser = JsonParser::default();
+        let err = json_parser.parse_string(blueprint_string).unwrap_err();
+        let ParseErrors(errors) = err.downcast_ref::<ParseErrors>().unwrap();
+
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].span.as_ref().unwrap().line, 3);
+    }
+
+    #[test]
+    fn test_json_parser_component_constraints() {
+        let blueprint_string = r#"{
+            "world": { "name": "Test", "description": "Test", "regions": ["region"] },
+            "entity": {
+                "player": {
+                    "hp": { "type": "i64", "default": 100, "min": 0, "max": 100 },
+                    "class": { "type": "String", "one_of": ["warrior", "mage"], "max_len": 0.5 }
+                }
+            },
+            "region": { "player": [] }
+        }"#
+        .to_string();
+
+        let json_parser = JsonParser::default();
+        let err = json_parser
+            .parse_string(blueprint_string.clone())
+            .unwrap_err();
+        let ParseErrors(errors) = err.downcast_ref::<ParseErrors>().unwrap();
+        assert_eq!(
+            errors[0].message,
+            "Entity player component class max_len must be a non-negative integer"
+        );
+
+        let blueprint_string = blueprint_string.replace("0.5", "16");
+        let blueprint = json_parser.parse_string(blueprint_string).unwrap();
+        let constraints = &blueprint.constraints["player"];
+        assert_eq!(constraints["hp"].max, Some(ComponentValue::Integer(100)));
+        assert_eq!(constraints["class"].max_len, Some(16));
+    }
+
+    #[test]
+    fn test_json_parser_instance_nonces() {
+        let blueprint_string = r#"{
+            "world": {
+                "name": "Test",
+                "description": "Test",
+                "regions": ["farm"],
+                "nonces": { "farm": { "apple": 7 } }
+            },
+            "entity": { "apple": { "x": "i64" } },
+            "farm": { "apple": [{ "nonce": 2, "x": 1 }, { "x": 2 }] }
+        }"#
+        .to_string();
+
+        let json_parser = JsonParser::default();
+        let mut blueprint = json_parser.parse_string(blueprint_string).unwrap();
+
+        // declared nonces are kept, the rest are issued after
+        // the last issued nonce
+        let apples = &blueprint.instances["farm"]["apple"];
+        assert_eq!(apples.keys().collect::<Vec<_>>(), vec![&2, &8]);
+        let nonce = blueprint
+            .add_default_instance("farm".to_string(), "apple".to_string())
+            .unwrap();
+        assert_eq!(nonce, 9);
+    }
+}
@@ -1,12 +1,21 @@
 //! Parser Port for TOML File Format
 
-use crate::{adapter::Parser, error::utils::ensure_syntax};
-use anyhow::Result;
-use rush_ecs_core::blueprint::{
-    Blueprint, BlueprintString, Component, ComponentType, ComponentValue, Entity,
+use crate::{
+    adapter::{Emitter, Parser},
+    error::{ParseError, ParseErrors, Span},
+    partial::{
+        constraint_bound, constraint_max_len, constraint_option, instance_nonce, world_nonces,
+        Defined, PartialBlueprint, PartialEntities, PartialEntity, PartialInstance, PartialWorld,
+        COMPONENT_PROPERTIES, COMPONENT_TYPES, NONCE_KEY,
+    },
 };
-use std::collections::BTreeMap;
-use toml::{Table, Value};
+use anyhow::{bail, Result};
+use rush_ecs_core::{
+    blueprint::{BlueprintString, ComponentType, ComponentValue},
+    constraint::Constraint,
+};
+use std::{collections::BTreeMap, ops::Range};
+use toml_edit::{ImDocument, Item, Key, Table, TableLike, Value};
 
 /// TOML Blueprint Parser
 ///
@@ -32,7 +41,7 @@ use toml::{Table, Value};
 ///    { name = "npc", x = 0.0, y = 0.0, w = 0.0, h = 0.0, speed = 0.0 }
 /// ]
 /// apple = [
-///     { x = 0, y = 0}
+///     { x = 0.0, y = 0.0}
 /// ]
 ///
 /// [house]
@@ -45,192 +54,816 @@ use toml::{Table, Value};
 pub struct TomlParser {}
 
 impl Parser for TomlParser {
-    fn parse_string(&self, blueprint_string: BlueprintString) -> Result<Blueprint> {
-        // expecting a valid TOML
-        let table: Table = blueprint_string.parse::<Table>().expect("invalid TOML");
+    fn extensions(&self) -> &'static [&'static str] {
+        &["toml"]
+    }
 
-        // ensure syntax for top-level Tables and Properties are met
+    fn parse_partial(&self, blueprint_string: BlueprintString) -> Result<PartialBlueprint> {
+        // TOML syntax errors stop parsing early
+        let document = match ImDocument::parse(blueprint_string.as_str()) {
+            Ok(d) => d,
+            Err(e) => bail!(ParseErrors(vec![ParseError::new(
+                e.message(),
+                &blueprint_string,
+                e.span(),
+            )])),
+        };
 
-        // WORLD
+        // Blueprint errors are collected across the whole document
+        let mut ctx = TomlContext::new(&blueprint_string);
+        let partial = ctx.parse_partial(document.as_table());
 
-        ensure_syntax(
-            "World table must exist".to_string(),
-            table.contains_key("world"),
-        );
-        ensure_syntax(
-            "World table must be a table".to_string(),
-            table["world"].is_table(),
-        );
+        if !ctx.errors.is_empty() {
+            // report in source order
+            ctx.errors
+                .sort_by_key(|e| e.span.as_ref().map(|s| (s.line, s.column)));
+            bail!(ParseErrors(ctx.errors));
+        }
 
-        let world_table = table["world"].as_table().unwrap();
+        Ok(partial)
+    }
+}
 
-        ensure_syntax(
-            "World must have a name".to_string(),
-            world_table.contains_key("name"),
-        );
+/// Collects every [`ParseError`] found while walking
+/// a TOML Blueprint document
+struct TomlContext<'a> {
+    source: &'a str,
+    errors: Vec<ParseError>,
+}
 
-        ensure_syntax(
-            "World name must be a string".to_string(),
-            world_table["name"].is_str(),
-        );
+impl<'a> TomlContext<'a> {
+    fn new(source: &'a str) -> Self {
+        Self {
+            source,
+            errors: Vec::new(),
+        }
+    }
 
-        ensure_syntax(
-            "World must have a description".to_string(),
-            world_table.contains_key("description"),
-        );
+    /// Locate a byte range in the source
+    fn span(&self, range: Option<Range<usize>>) -> Option<Span> {
+        range.map(|r| Span::from_range(self.source, r))
+    }
 
-        ensure_syntax(
-            "World description must be a string".to_string(),
-            world_table["description"].is_str(),
-        );
+    /// Record an error at an optional span in the source
+    fn error(&mut self, message: impl Into<String>, range: Option<Range<usize>>) {
+        self.errors
+            .push(ParseError::new(message, self.source, range));
+    }
 
-        ensure_syntax(
-            "World must have a regions property".to_string(),
-            world_table.contains_key("regions"),
-        );
-        ensure_syntax(
-            "World regions property must be an array".to_string(),
-            world_table["regions"].is_array(),
-        );
-        ensure_syntax(
-            "World must have at least 1 region".to_string(),
-            !world_table["regions"].as_array().unwrap().is_empty(),
-        );
-        ensure_syntax(
-            "World regions property must be an array of strings".to_string(),
-            world_table["regions"].as_array().unwrap()[0].is_str(),
-        );
+    fn parse_partial(&mut self, table: &Table) -> PartialBlueprint {
+        let mut partial = PartialBlueprint::default();
 
-        // get regions into Vec<String>
-        let regions = world_table["regions"]
-            .as_array()
-            .unwrap()
-            .iter()
-            .map(|r| r.as_str().unwrap().to_string()) // unwrap ok
-            .collect::<Vec<_>>();
+        for (key, item) in table.iter() {
+            match key {
+                // WORLD
+                "world" => partial.world = self.parse_world(item),
 
-        // REGIONS
+                // ENTITY
+                "entity" => partial.entities = self.parse_entities(item),
 
-        // every region stated in the world table must have
-        // a table of instances in the blueprint
-        for region in regions.iter() {
-            ensure_syntax(
-                format!("Region {region} table must exist"),
-                // certain region exists
-                table.contains_key(region),
-            );
+                // REGIONS
+                _ => {
+                    let Some(region_table) = item.as_table_like() else {
+                        self.error(format!("Region {key} must be a table"), item.span());
+                        continue;
+                    };
+
+                    let mut entities = Vec::new();
+                    for (entity_name, instances_item) in region_table.iter() {
+                        let entity_span = region_table
+                            .get_key_value(entity_name)
+                            .and_then(|(k, _)| k.span());
+                        let instances = self.parse_instances(key, entity_name, instances_item);
+                        entities.push((
+                            Defined::new(entity_name.to_string(), self.span(entity_span)),
+                            instances,
+                        ));
+                    }
+
+                    partial.regions.push((key.to_string(), entities));
+                }
+            }
         }
 
-        // ENTITY
+        partial
+    }
 
-        ensure_syntax(
-            "Enttiy table must exist".to_string(),
-            table.contains_key("entity"),
-        );
-        ensure_syntax(
-            "Entity table must be a table".to_string(),
-            table["entity"].is_table(),
-        );
+    /// Parse the World table
+    fn parse_world(&mut self, world_item: &Item) -> Option<Defined<PartialWorld>> {
+        let Some(world_table) = world_item.as_table_like() else {
+            self.error("World table must be a table", world_item.span());
+            return None;
+        };
 
-        let entity_table = table["entity"].as_table().unwrap();
-        let entities = entity_table.keys().cloned().collect::<Vec<_>>();
+        let mut world = PartialWorld {
+            name: self.parse_world_string(world_table, "name"),
+            description: self.parse_world_string(world_table, "description"),
+            regions: None,
+            nonces: None,
+        };
 
-        ensure_syntax(
-            "Entity table must have at least 1 entity properties".to_string(),
-            // not empty
-            !entities.is_empty() &&
-            // must be a table of properties e.g. { x = 0, y = 0 }
-            entity_table[&entities[0]].is_table(),
-        );
+        if let Some(regions_item) = world_table.get("regions") {
+            match regions_item.as_array() {
+                None => self.error(
+                    "World regions property must be an array",
+                    regions_item.span(),
+                ),
+                Some(array) if array.is_empty() => {
+                    self.error("World must have at least 1 region", regions_item.span())
+                }
+                Some(array) => {
+                    let mut regions = Vec::new();
+                    for region in array.iter() {
+                        match region.as_str() {
+                            Some(r) => {
+                                regions.push(Defined::new(r.to_string(), self.span(region.span())))
+                            }
+                            None => self.error(
+                                "World regions property must be an array of strings",
+                                region.span(),
+                            ),
+                        }
+                    }
+                    world.regions = Some(Defined::new(regions, self.span(regions_item.span())));
+                }
+            }
+        }
+
+        if let Some(nonces_item) = world_table.get("nonces") {
+            let nonces = match toml_component_value(nonces_item) {
+                Some(v) => world_nonces(v),
+                None => Err(
+                    "World nonces must be a table of Regions, Entities and their last nonce"
+                        .to_string(),
+                ),
+            };
+
+            match nonces {
+                Ok(n) => world.nonces = Some(Defined::new(n, self.span(nonces_item.span()))),
+                Err(e) => self.error(e, nonces_item.span()),
+            }
+        }
 
-        // parse World's name
-        let world_name = world_table["name"].as_str().unwrap().to_string();
-        let world_description = world_table["description"].as_str().unwrap().to_string();
-
-        // create Blueprint
-        let mut blueprint = Blueprint::new(world_name, world_description);
-
-        // TODO: Move this closer to Load Instances
-        // preload Instance Keys
-        blueprint.preload(regions.clone(), entities.clone());
-
-        // load Regions into World
-        for region_name in regions.iter() {
-            // load into World tree
-            if let Some(region_table) = table[region_name].as_table() {
-                // get entities from keys in the table
-                let entities = region_table.keys().cloned().collect::<Vec<Entity>>();
-                blueprint.add_region(region_name.clone(), entities);
-            }
-        }
-
-        // load Entities into World
-        for entity_name in entities.into_iter() {
-            if let Some(component_table) = entity_table[&entity_name].as_table() {
-                // load Entities
-                let mut component_type_tree: BTreeMap<Component, ComponentType> = BTreeMap::new();
-                for component_name in component_table.keys() {
-                    // unwrap ok, a value is expected
-                    let value = component_table
-                        .get(component_name)
-                        .unwrap()
-                        .as_str()
-                        .unwrap()
-                        .to_string();
-                    component_type_tree.insert(component_name.to_string(), value);
+        Some(Defined::new(world, self.span(world_item.span())))
+    }
+
+    /// Parse a string property of the World table
+    fn parse_world_string(
+        &mut self,
+        world_table: &dyn TableLike,
+        key: &str,
+    ) -> Option<Defined<String>> {
+        let item = world_table.get(key)?;
+
+        match item.as_str() {
+            Some(v) => Some(Defined::new(v.to_string(), self.span(item.span()))),
+            None => {
+                self.error(format!("World {key} must be a string"), item.span());
+                None
+            }
+        }
+    }
+
+    /// Parse the Entity table into each Entity's schema
+    fn parse_entities(&mut self, entity_item: &Item) -> Option<Defined<PartialEntities>> {
+        let Some(entity_table) = entity_item.as_table_like() else {
+            self.error("Entity table must be a table", entity_item.span());
+            return None;
+        };
+
+        let mut entities = Vec::new();
+
+        for (entity_name, component_item) in entity_table.iter() {
+            let entity_span = entity_table
+                .get_key_value(entity_name)
+                .and_then(|(k, _)| k.span());
+
+            // must be a table of properties e.g. { x = "f64", y = "f64" }
+            let component_table = match component_item.as_table_like() {
+                Some(t) => t,
+                None => {
+                    self.error(
+                        format!("Entity {entity_name} must be a table of component types"),
+                        component_item.span(),
+                    );
+                    continue;
+                }
+            };
+
+            let mut partial_entity = PartialEntity::default();
+            for (component_name, component_item) in component_table.iter() {
+                if component_name == NONCE_KEY {
+                    self.error(
+                        format!("Entity {entity_name} component {NONCE_KEY} is reserved for the Instance nonce"),
+                        component_item.span(),
+                    );
+                    continue;
+                }
+
+                let Some((component_type, default, constraint)) =
+                    self.parse_component(entity_name, component_name, component_item)
+                else {
+                    continue;
+                };
+
+                partial_entity
+                    .component_types
+                    .insert(component_name.to_string(), component_type);
+                if let Some(default) = default {
+                    partial_entity
+                        .defaults
+                        .insert(component_name.to_string(), default);
+                }
+                if !constraint.is_empty() {
+                    partial_entity
+                        .constraints
+                        .insert(component_name.to_string(), constraint);
                 }
-                blueprint.add_entity(entity_name, component_type_tree);
-            }
-        }
-
-        // TODO: Refactor nested For loops for readability
-        //
-        // load Instances
-        //
-        // In blueprint.rs:
-        //  pub instances: BTreeMap<Region, BTreeMap<Entity, Vec<ComponentTree>>>,
-
-        let blueprint_regions = blueprint.regions.clone();
-
-        for region_name in regions.into_iter() {
-            // if there are entities in region
-            if let Some(entities_in_region) = blueprint_regions.get(&region_name) {
-                // get each entity in region
-                for entity_name in entities_in_region.iter() {
-                    if let Some(instances) = table[&region_name][entity_name].as_array() {
-                        for instance in instances.iter() {
-                            // build each entity's component tree
-                            if let Some(entity_components) = instance.as_table() {
-                                let mut component_tree: BTreeMap<Component, ComponentValue> =
-                                    BTreeMap::new();
-
-                                // get (component, value) pairs
-                                for (toml_component, toml_value) in entity_components.into_iter() {
-                                    let component = toml_component.to_string();
-                                    let value = match toml_value {
-                                        Value::String(v) => ComponentValue::String(v.to_string()),
-                                        Value::Float(v) => ComponentValue::Float(*v),
-                                        Value::Integer(v) => ComponentValue::Integer(*v),
-                                        Value::Boolean(v) => ComponentValue::Boolean(*v),
-                                        _ => panic!("Unsupported data type"),
-                                    };
-
-                                    component_tree.insert(component, value);
-                                }
-
-                                blueprint.add_instance(
-                                    region_name.clone(),
-                                    entity_name.to_string(),
-                                    component_tree,
-                                )?;
+            }
+
+            entities.push((
+                Defined::new(entity_name.to_string(), self.span(entity_span)),
+                partial_entity,
+            ));
+        }
+
+        Some(Defined::new(entities, self.span(entity_item.span())))
+    }
+
+    /// Parse a Component of an Entity's schema
+    ///
+    /// Accepts the short form `hp = "i64"` or the long
+    /// form `hp = { type = "i64", default = 100 }`
+    fn parse_component(
+        &mut self,
+        entity_name: &str,
+        component_name: &str,
+        component_item: &Item,
+    ) -> Option<(ComponentType, Option<ComponentValue>, Constraint)> {
+        let Some(component_table) = component_item.as_table_like() else {
+            let component_type =
+                self.parse_component_type(entity_name, component_name, component_item)?;
+            return Some((component_type, None, Constraint::default()));
+        };
+
+        for (key, item) in component_table.iter() {
+            if !COMPONENT_PROPERTIES.contains(&key) {
+                self.error(
+                    format!(
+                        "Entity {entity_name} component {component_name} has unknown property {key}, expected one of {}",
+                        COMPONENT_PROPERTIES.join(", ")
+                    ),
+                    item.span(),
+                );
+            }
+        }
+
+        let Some(type_item) = component_table.get("type") else {
+            self.error(
+                format!("Entity {entity_name} component {component_name} must have a type"),
+                component_item.span(),
+            );
+            return None;
+        };
+
+        let component_type = self.parse_component_type(entity_name, component_name, type_item)?;
+
+        // default type is checked against the schema by Blueprint::validate
+        let default = match component_table.get("default") {
+            Some(item) => match toml_component_value(item) {
+                Some(v) => Some(v),
+                None => {
+                    self.error(
+                        format!(
+                            "Unsupported data type {} for default of {component_name}",
+                            item.type_name()
+                        ),
+                        item.span(),
+                    );
+                    return None;
+                }
+            },
+            None => None,
+        };
+
+        let mut constraint = Constraint::default();
+        let mut is_valid = true;
+
+        for key in ["min", "max"] {
+            let Some(item) = component_table.get(key) else {
+                continue;
+            };
+
+            let bound = match toml_component_value(item) {
+                Some(v) => constraint_bound(key, &component_type, v),
+                None => Err(format!("{key} must be a {component_type}")),
+            };
+
+            match bound {
+                Ok(v) if key == "min" => constraint.min = Some(v),
+                Ok(v) => constraint.max = Some(v),
+                Err(e) => {
+                    self.error(
+                        format!("Entity {entity_name} component {component_name} {e}"),
+                        item.span(),
+                    );
+                    is_valid = false;
+                }
+            }
+        }
+
+        if let Some(item) = component_table.get("one_of") {
+            match item.as_array() {
+                Some(array) if !array.is_empty() => {
+                    let mut one_of = Vec::new();
+                    for value in array.iter() {
+                        let option = match toml_value_component_value(value) {
+                            Some(v) => constraint_option(&component_type, v),
+                            None => Err(format!("one_of values must be a {component_type}")),
+                        };
+
+                        match option {
+                            Ok(v) => one_of.push(v),
+                            Err(e) => {
+                                self.error(
+                                    format!("Entity {entity_name} component {component_name} {e}"),
+                                    value.span(),
+                                );
+                                is_valid = false;
                             }
                         }
                     }
+                    constraint.one_of = Some(one_of);
+                }
+                _ => {
+                    self.error(
+                        format!(
+                            "Entity {entity_name} component {component_name} one_of must be a non-empty array"
+                        ),
+                        item.span(),
+                    );
+                    is_valid = false;
+                }
+            }
+        }
+
+        if let Some(item) = component_table.get("max_len") {
+            let max_len = match toml_component_value(item) {
+                Some(v) => constraint_max_len(&component_type, v),
+                None => Err("max_len must be a non-negative integer".to_string()),
+            };
+
+            match max_len {
+                Ok(v) => constraint.max_len = Some(v),
+                Err(e) => {
+                    self.error(
+                        format!("Entity {entity_name} component {component_name} {e}"),
+                        item.span(),
+                    );
+                    is_valid = false;
+                }
+            }
+        }
+
+        if !is_valid {
+            return None;
+        }
+
+        Some((component_type, default, constraint))
+    }
+
+    /// Parse the type of a Component, e.g. `"i64"`
+    fn parse_component_type(
+        &mut self,
+        entity_name: &str,
+        component_name: &str,
+        type_item: &Item,
+    ) -> Option<ComponentType> {
+        let Some(type_name) = type_item.as_str() else {
+            self.error(
+                format!("Entity {entity_name} component {component_name} type must be a string"),
+                type_item.span(),
+            );
+            return None;
+        };
+
+        match type_name.parse::<ComponentType>() {
+            Ok(t) => Some(t),
+            Err(e) => {
+                self.error(
+                    format!("{e}, expected one of {COMPONENT_TYPES}"),
+                    type_item.span(),
+                );
+                None
+            }
+        }
+    }
+
+    /// Parse the Instances of an Entity in a Region
+    ///
+    /// Accepts an array of inline tables or an array of tables,
+    /// each with an optional `nonce`
+    fn parse_instances(
+        &mut self,
+        region_name: &str,
+        entity_name: &str,
+        instances_item: &Item,
+    ) -> Vec<PartialInstance> {
+        let mut instances = Vec::new();
+
+        let instance_tables: Vec<&dyn TableLike> = match instances_item {
+            Item::Value(Value::Array(array)) => array
+                .iter()
+                .filter_map(|v| match v.as_inline_table() {
+                    Some(t) => Some(t as &dyn TableLike),
+                    None => {
+                        self.error(
+                            format!("{entity_name} instance in {region_name} must be a table"),
+                            v.span(),
+                        );
+                        None
+                    }
+                })
+                .collect(),
+            Item::ArrayOfTables(array) => array.iter().map(|t| t as &dyn TableLike).collect(),
+            _ => {
+                self.error(
+                    format!("{entity_name} instances in {region_name} must be an array of tables"),
+                    instances_item.span(),
+                );
+                return instances;
+            }
+        };
+
+        for instance_table in instance_tables.into_iter() {
+            // build each entity's component tree
+            let mut instance = PartialInstance::default();
+
+            // get (component, value) pairs
+            for (component, item) in instance_table.iter() {
+                if component == NONCE_KEY {
+                    let nonce = match toml_component_value(item) {
+                        Some(v) => instance_nonce(v),
+                        None => Err("nonce must be a positive integer".to_string()),
+                    };
+
+                    match nonce {
+                        Ok(n) => instance.nonce = Some(n),
+                        Err(e) => self.error(
+                            format!("{entity_name} instance in {region_name} {e}"),
+                            item.span(),
+                        ),
+                    }
+                    continue;
+                }
+
+                let value = match toml_component_value(item) {
+                    Some(v) => v,
+                    None => {
+                        self.error(
+                            format!("Unsupported data type {} for {component}", item.type_name()),
+                            item.span(),
+                        );
+                        continue;
+                    }
+                };
+
+                instance.components.insert(component.to_string(), value);
+            }
+
+            instances.push(instance);
+        }
+
+        instances
+    }
+}
+
+/// Returns the [`ComponentValue`] of a TOML value,
+/// if its type is supported
+fn toml_component_value(item: &Item) -> Option<ComponentValue> {
+    match item {
+        Item::Value(value) => toml_value_component_value(value),
+        Item::Table(table) => {
+            toml_table_component_value(table.iter().map(|(k, i)| (k, toml_component_value(i))))
+        }
+        _ => None,
+    }
+}
+
+/// Returns the [`ComponentValue`] of a TOML [`Value`],
+/// if its type is supported
+fn toml_value_component_value(value: &Value) -> Option<ComponentValue> {
+    match value {
+        Value::String(v) => Some(ComponentValue::String(v.value().to_string())),
+        Value::Float(v) => Some(ComponentValue::Float(*v.value())),
+        Value::Integer(v) => Some(ComponentValue::Integer(*v.value())),
+        Value::Boolean(v) => Some(ComponentValue::Boolean(*v.value())),
+        Value::Array(array) => array
+            .iter()
+            .map(toml_value_component_value)
+            .collect::<Option<Vec<_>>>()
+            .map(ComponentValue::List),
+        Value::InlineTable(table) => toml_table_component_value(
+            table
+                .iter()
+                .map(|(k, v)| (k, toml_value_component_value(v))),
+        ),
+        _ => None,
+    }
+}
+
+/// Returns the Map [`ComponentValue`] of a TOML table,
+/// if every value's type is supported
+///
+/// Tables are read as Maps, they become Structs when
+/// validated against a Struct schema
+fn toml_table_component_value<'a>(
+    pairs: impl Iterator<Item = (&'a str, Option<ComponentValue>)>,
+) -> Option<ComponentValue> {
+    pairs
+        .map(|(k, v)| v.map(|v| (k.to_string(), v)))
+        .collect::<Option<BTreeMap<_, _>>>()
+        .map(ComponentValue::Map)
+}
+
+/// TOML Blueprint Emitter
+///
+/// This [`Emitter`] writes Blueprints in the canonical
+/// form of the Rush TOML DSL Specification:
+///
+/// - Tables in order: `[world]`, `[entity]`, then Regions
+///   sorted by name
+/// - Entities, Components and Regions sorted by name
+/// - Instances in their original order, one per line,
+///   with their `nonce` first when it is known
+///
+/// Comments and formatting of the source are not kept,
+/// see [`has_comments`] and [`strip_comments`]
+///
+#[derive(Clone, Debug, Default)]
+pub struct TomlEmitter {}
+
+impl Emitter for TomlEmitter {
+    fn emit_partial(&self, partial: &PartialBlueprint) -> Result<BlueprintString> {
+        let mut tables = Vec::new();
+
+        // WORLD
+
+        if let Some(world) = &partial.world {
+            let world = &world.value;
+            let mut table = String::from("[world]\n");
+
+            if let Some(name) = &world.name {
+                table += &format!("name = {}\n", toml_string(&name.value));
+            }
+
+            if let Some(description) = &world.description {
+                table += &format!("description = {}\n", toml_string(&description.value));
+            }
+
+            if let Some(regions) = &world.regions {
+                let mut regions = regions.value.iter().map(|r| &r.value).collect::<Vec<_>>();
+                regions.sort();
+                regions.dedup();
+
+                let regions = regions
+                    .into_iter()
+                    .map(|r| toml_string(r))
+                    .collect::<Vec<_>>();
+                table += &format!("regions = [{}]\n", regions.join(", "));
+            }
+
+            if let Some(nonces) = &world.nonces {
+                let nonces = nonces.value.iter().map(|(region, entities)| {
+                    let entities = entities.iter().map(|(e, n)| (e, n.to_string()));
+                    (region, inline_table(entities))
+                });
+                table += &format!("nonces = {}\n", inline_table(nonces));
+            }
+
+            tables.push(table);
+        }
+
+        // ENTITY
+
+        if let Some(entities) = &partial.entities {
+            let mut entities = entities.value.iter().collect::<Vec<_>>();
+            entities.sort_by(|a, b| a.0.value.cmp(&b.0.value));
+
+            let mut table = String::from("[entity]\n");
+            for (entity, partial_entity) in entities.into_iter() {
+                let components = partial_entity.component_types.iter().map(|(c, t)| {
+                    let component_type = toml_string(&t.to_string());
+
+                    let mut properties = vec![("type".to_string(), component_type.clone())];
+                    if let Some(default) = partial_entity.defaults.get(c) {
+                        properties.push(("default".to_string(), toml_value(default)));
+                    }
+                    if let Some(constraint) = partial_entity.constraints.get(c) {
+                        if let Some(min) = &constraint.min {
+                            properties.push(("min".to_string(), toml_value(min)));
+                        }
+                        if let Some(max) = &constraint.max {
+                            properties.push(("max".to_string(), toml_value(max)));
+                        }
+                        if let Some(one_of) = &constraint.one_of {
+                            let values = one_of.iter().map(toml_value).collect::<Vec<_>>();
+                            properties
+                                .push(("one_of".to_string(), format!("[{}]", values.join(", "))));
+                        }
+                        if let Some(max_len) = constraint.max_len {
+                            properties.push(("max_len".to_string(), max_len.to_string()));
+                        }
+                    }
+
+                    // long form only when there is more than a type
+                    let component = match properties.len() {
+                        1 => component_type,
+                        _ => inline_table(properties.iter().map(|(k, v)| (k, v.clone()))),
+                    };
+
+                    (c, component)
+                });
+                table += &format!(
+                    "{} = {}\n",
+                    toml_key(&entity.value),
+                    inline_table(components)
+                );
+            }
+
+            tables.push(table);
+        }
+
+        // REGIONS
+
+        let mut regions = partial.regions.iter().collect::<Vec<_>>();
+        regions.sort_by(|a, b| a.0.cmp(&b.0));
+
+        for (region, entities) in regions.into_iter() {
+            let mut entities = entities.iter().collect::<Vec<_>>();
+            entities.sort_by(|a, b| a.0.value.cmp(&b.0.value));
+
+            let mut table = format!("[{}]\n", toml_key(region));
+            for (entity, instances) in entities.into_iter() {
+                let key = toml_key(&entity.value);
+
+                if instances.is_empty() {
+                    table += &format!("{key} = []\n");
+                    continue;
+                }
+
+                table += &format!("{key} = [\n");
+                let nonce_key = NONCE_KEY.to_string();
+                for instance in instances.iter() {
+                    let nonce = instance.nonce.map(|n| (&nonce_key, n.to_string()));
+                    let components = instance.components.iter().map(|(c, v)| (c, toml_value(v)));
+                    table += &format!(
+                        "    {},\n",
+                        inline_table(nonce.into_iter().chain(components))
+                    );
                 }
+                table += "]\n";
             }
+
+            tables.push(table);
         }
 
-        Ok(blueprint)
+        Ok(tables.join("\n"))
+    }
+}
+
+/// Returns a key, quoted if it is not a bare key
+fn toml_key(key: &str) -> String {
+    Key::new(key).display_repr().to_string()
+}
+
+/// Is `true` if the TOML source has comments
+///
+/// The [`TomlEmitter`] drops comments, check before
+/// replacing a source with its emitted form
+pub fn has_comments(source: &str) -> bool {
+    !comment_spans(source).is_empty()
+}
+
+/// Returns the TOML source without its comments
+///
+/// Lines with only a comment are removed and trailing
+/// whitespace before a comment is trimmed, so a commented
+/// source in canonical form strips to its emitted form
+pub fn strip_comments(source: &str) -> String {
+    let mut stripped = String::with_capacity(source.len());
+    let mut last = 0;
+
+    for span in comment_spans(source) {
+        stripped.push_str(&source[last..span.start]);
+        stripped.truncate(stripped.trim_end_matches([' ', '\t']).len());
+        last = span.end;
+
+        // drop the line break of a comment-only line
+        if stripped.is_empty() || stripped.ends_with('\n') {
+            let rest = &source[last..];
+            if rest.starts_with("\r\n") {
+                last += 2;
+            } else if rest.starts_with('\n') {
+                last += 1;
+            }
+        }
+    }
+    stripped.push_str(&source[last..]);
+
+    stripped
+}
+
+/// Returns the byte ranges of the comments in a TOML source,
+/// from their `#` up to the end of their line
+fn comment_spans(source: &str) -> Vec<Range<usize>> {
+    let mut spans = Vec::new();
+    let mut rest = source;
+
+    while let Some(c) = rest.chars().next() {
+        // skip strings, `#` is only a comment outside of them
+        let delimiter = ["\"\"\"", "'''", "\"", "'"]
+            .into_iter()
+            .find(|d| rest.starts_with(d));
+
+        let Some(delimiter) = delimiter else {
+            if c == '#' {
+                let start = source.len() - rest.len();
+                let len = rest.find(['\r', '\n']).unwrap_or(rest.len());
+                spans.push(start..start + len);
+                rest = &rest[len..];
+                continue;
+            }
+            rest = &rest[c.len_utf8()..];
+            continue;
+        };
+
+        rest = &rest[delimiter.len()..];
+        // only basic strings have escapes
+        let escapes = delimiter.starts_with('"');
+        loop {
+            let Some(c) = rest.chars().next() else {
+                return spans;
+            };
+            if escapes && c == '\\' {
+                // skip the escaped character
+                let mut chars = rest.chars();
+                chars.next();
+                chars.next();
+                rest = chars.as_str();
+                continue;
+            }
+            if rest.starts_with(delimiter) {
+                rest = &rest[delimiter.len()..];
+                break;
+            }
+            rest = &rest[c.len_utf8()..];
+        }
+    }
+
+    spans
+}
+
+/// Returns an escaped and quoted string
+fn toml_string(value: &str) -> String {
+    Value::from(value).to_string()
+}
+
+/// Returns the TOML representation of a [`ComponentValue`]
+fn toml_value(value: &ComponentValue) -> String {
+    match value {
+        ComponentValue::String(v) => toml_string(v),
+        ComponentValue::Integer(v) => Value::from(*v).to_string(),
+        ComponentValue::Float(v) => Value::from(*v).to_string(),
+        ComponentValue::Boolean(v) => Value::from(*v).to_string(),
+        ComponentValue::U8(v) => Value::from(*v as i64).to_string(),
+        ComponentValue::U32(v) => Value::from(*v as i64).to_string(),
+        // TOML integers are i64, larger values are written as strings
+        ComponentValue::U64(v) => match i64::try_from(*v) {
+            Ok(v) => Value::from(v).to_string(),
+            Err(_) => toml_string(&v.to_string()),
+        },
+        // written as strings to keep every decimal place
+        ComponentValue::Fixed(v) => toml_string(&v.to_string()),
+        ComponentValue::List(values) => {
+            let values = values.iter().map(toml_value).collect::<Vec<_>>();
+            format!("[{}]", values.join(", "))
+        }
+        ComponentValue::Map(values) | ComponentValue::Struct(values) => {
+            inline_table(values.iter().map(|(k, v)| (k, toml_value(v))))
+        }
+    }
+}
+
+/// Returns an inline table (e.g. `{ x = 0.0, y = 0.0 }`)
+fn inline_table<'a>(pairs: impl Iterator<Item = (&'a String, String)>) -> String {
+    let pairs = pairs
+        .map(|(k, v)| format!("{} = {v}", toml_key(k)))
+        .collect::<Vec<_>>();
+
+    match pairs.is_empty() {
+        true => String::from("{}"),
+        false => format!("{{ {} }}", pairs.join(", ")),
     }
 }
 
@@ -238,6 +871,7 @@ impl Parser for TomlParser {
 mod tests {
     use super::*;
     use crate::utils::file_to_string;
+    use rush_ecs_core::validation::{Diagnostic, ValidationError};
     use std::path::Path;
 
     #[test]
@@ -247,8 +881,551 @@ mod tests {
 
         let toml_parser = TomlParser::default();
         let blueprint = toml_parser.parse_string(blueprint_string).unwrap();
-        println!("{:?}", blueprint);
-        // TODO: Assert value
-        assert!(true)
+
+        assert_eq!(blueprint.name, "Test World");
+        assert_eq!(blueprint.description, "Test World description");
+        assert_eq!(
+            blueprint.regions.keys().collect::<Vec<_>>(),
+            vec!["test_region1", "test_region2"]
+        );
+        assert_eq!(
+            blueprint.entities.keys().collect::<Vec<_>>(),
+            vec!["test_entity1", "test_entity2"]
+        );
+        assert_eq!(
+            blueprint.instances["test_region1"]["test_entity1"][&1]["test_property"],
+            ComponentValue::Integer(0)
+        );
+    }
+
+    #[test]
+    fn test_toml_parser_typed_schema() {
+        let path = Path::new("mock/fixtures/ports/blueprint.toml");
+        let blueprint_string = file_to_string(path);
+
+        let toml_parser = TomlParser::default();
+        let blueprint = toml_parser.parse_string(blueprint_string).unwrap();
+        let component_type = blueprint
+            .entities
+            .get("test_entity1")
+            .unwrap()
+            .get("test_property")
+            .unwrap();
+
+        assert_eq!(*component_type, ComponentType::Integer);
+    }
+
+    #[test]
+    fn test_toml_parser_unknown_type() {
+        let blueprint_string = r#"
+[world]
+name = "Test World"
+description = "Test World description"
+regions = ["region"]
+
+[entity]
+entity = { x = "f46" }
+
+[region]
+"#
+        .to_string();
+
+        let toml_parser = TomlParser::default();
+        let err = toml_parser.parse_string(blueprint_string).unwrap_err();
+        let ParseErrors(errors) = err.downcast_ref::<ParseErrors>().unwrap();
+
+        assert_eq!(errors.len(), 1);
+        assert_eq!(
+            errors[0].message,
+            "unknown component type: f46, expected one of String, i64, f64, bool, u8, u32, u64, Fixed<N>, [T], Map<String, T>, { field: T }"
+        );
+        assert_eq!(errors[0].span.as_ref().unwrap().line, 8);
+        assert_eq!(errors[0].span.as_ref().unwrap().column, 16);
+    }
+
+    #[test]
+    fn test_toml_parser_collects_errors() {
+        let blueprint_string = r#"
+[world]
+name = 143
+description = "Test World description"
+regions = ["region", 7]
+
+[entity]
+entity = { x = "f64" }
+
+[region]
+entity = [{ x = 1979-05-27 }]
+"#
+        .to_string();
+
+        let toml_parser = TomlParser::default();
+        let err = toml_parser.parse_string(blueprint_string).unwrap_err();
+        let ParseErrors(errors) = err.downcast_ref::<ParseErrors>().unwrap();
+
+        let messages = errors
+            .iter()
+            .map(|e| (e.span.as_ref().unwrap().line, e.message.as_str()))
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            messages,
+            vec![
+                (3, "World name must be a string"),
+                (5, "World regions property must be an array of strings"),
+                (11, "Unsupported data type datetime for x"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_toml_parser_validates_instances() {
+        let blueprint_string = r#"
+[world]
+name = "Test World"
+description = "Test World description"
+regions = ["farm"]
+
+[entity]
+apple = { x = "f64", y = "f64" }
+
+[farm]
+apple = [
+    { x = 0, y = 0.0 }
+]
+"#
+        .to_string();
+
+        let toml_parser = TomlParser::default();
+        let err = toml_parser
+            .parse_string(blueprint_string.clone())
+            .unwrap_err();
+        let ValidationError(diagnostics) = err.downcast_ref::<ValidationError>().unwrap();
+
+        assert_eq!(
+            *diagnostics,
+            vec![Diagnostic::TypeMismatch {
+                region: "farm".to_string(),
+                entity: "apple".to_string(),
+                nonce: 1,
+                component: "x".to_string(),
+                expected: ComponentType::Float,
+                found: ComponentValue::Integer(0),
+            }]
+        );
+
+        // still available for callers that validate on their own
+        assert!(toml_parser.parse_unvalidated(blueprint_string).is_ok());
+    }
+
+    #[test]
+    fn test_toml_emitter_round_trip() {
+        let path = Path::new("mock/blueprint/file.toml");
+        let blueprint_string = file_to_string(path);
+
+        let toml_parser = TomlParser::default();
+        let toml_emitter = TomlEmitter::default();
+
+        let blueprint = toml_parser.parse_string(blueprint_string).unwrap();
+        let emitted = toml_emitter.emit(&blueprint).unwrap();
+        let reparsed = toml_parser.parse_string(emitted.clone()).unwrap();
+
+        assert_eq!(blueprint, reparsed);
+        // canonical output is stable
+        assert_eq!(emitted, toml_emitter.emit(&reparsed).unwrap());
+    }
+
+    #[test]
+    fn test_toml_emitter_nonce_round_trip() {
+        let blueprint_string = r#"
+[world]
+name = "Test World"
+description = "Test World description"
+regions = ["farm"]
+
+[entity]
+apple = { x = "i64" }
+
+[farm]
+apple = [{ x = 1 }, { x = 2 }, { x = 3 }]
+"#
+        .to_string();
+
+        let toml_parser = TomlParser::default();
+        let toml_emitter = TomlEmitter::default();
+
+        let (farm, apple) = ("farm".to_string(), "apple".to_string());
+        let mut blueprint = toml_parser.parse_string(blueprint_string).unwrap();
+        blueprint
+            .remove_instance(farm.clone(), apple.clone(), 1)
+            .unwrap();
+        blueprint
+            .remove_instance(farm.clone(), apple.clone(), 3)
+            .unwrap();
+
+        // the kept Instance and the tombstoned Nonce are written
+        let emitted = toml_emitter.emit(&blueprint).unwrap();
+        assert!(emitted.contains("nonces = { farm = { apple = 3 } }"));
+        assert!(emitted.contains("{ nonce = 2, x = 2 }"));
+
+        let mut reparsed = toml_parser.parse_string(emitted.clone()).unwrap();
+        assert_eq!(blueprint, reparsed);
+        assert_eq!(emitted, toml_emitter.emit(&reparsed).unwrap());
+
+        // removed Nonces are not issued again
+        let nonce = reparsed.add_default_instance(farm, apple).unwrap();
+        assert_eq!(nonce, 4);
+    }
+
+    #[test]
+    fn test_toml_parser_instance_nonces() {
+        let blueprint_string = r#"
+[world]
+name = "Test World"
+description = "Test World description"
+regions = ["farm"]
+
+[entity]
+apple = { x = "i64" }
+
+[farm]
+apple = [{ x = 1 }, { nonce = 5, x = 2 }]
+"#
+        .to_string();
+
+        let toml_parser = TomlParser::default();
+
+        // Instances without a nonce are issued one after the declared ones
+        let blueprint = toml_parser.parse_string(blueprint_string.clone()).unwrap();
+        let apples = &blueprint.instances["farm"]["apple"];
+        assert_eq!(apples.keys().collect::<Vec<_>>(), vec![&5, &6]);
+        assert_eq!(apples[&6]["x"], ComponentValue::Integer(1));
+
+        let invalid = [
+            (
+                blueprint_string.replace("{ x = 1 }", "{ nonce = 5, x = 1 }"),
+                "apple #5 in farm is defined more than once",
+            ),
+            (
+                blueprint_string.replace("nonce = 5", "nonce = 0"),
+                "apple instance in farm nonce must be a positive integer",
+            ),
+            (
+                blueprint_string.replace("x = \"i64\"", "nonce = \"u64\""),
+                "Entity apple component nonce is reserved for the Instance nonce",
+            ),
+        ];
+
+        for (blueprint_string, message) in invalid.into_iter() {
+            let err = toml_parser.parse_string(blueprint_string).unwrap_err();
+            let ParseErrors(errors) = err.downcast_ref::<ParseErrors>().unwrap();
+            assert_eq!(errors[0].message, message);
+        }
+    }
+
+    #[test]
+    fn test_has_comments() {
+        assert!(has_comments("# World\n[world]\n"));
+        assert!(has_comments("[world]\nname = \"World\" # trailing\n"));
+        assert!(has_comments("[farm]\nplayer = [\n  # npc\n  {}\n]\n"));
+
+        assert!(!has_comments("[world]\nname = \"#1 World\"\n"));
+        assert!(!has_comments("[world]\nname = 'C:\\#'\n"));
+        assert!(!has_comments("[world]\nname = \"say \\\"#hi\\\"\"\n"));
+        assert!(!has_comments(
+            "[world]\ndescription = \"\"\"\n# not a comment\n\"\"\"\n"
+        ));
+    }
+
+    #[test]
+    fn test_strip_comments() {
+        assert_eq!(strip_comments("# World\n[world]\n"), "[world]\n");
+        assert_eq!(
+            strip_comments("[world]\nname = \"World\" # trailing\n"),
+            "[world]\nname = \"World\"\n"
+        );
+        assert_eq!(
+            strip_comments("[farm]\nplayer = [\n  # npc\n  {}\n]\n"),
+            "[farm]\nplayer = [\n  {}\n]\n"
+        );
+        assert_eq!(strip_comments("[world] # end"), "[world]");
+
+        let source = "[world]\nname = \"#1 World\"\n";
+        assert_eq!(strip_comments(source), source);
+    }
+
+    #[test]
+    fn test_toml_emitter_canonical() {
+        let blueprint_string = r#"
+[farm]
+apple = [{ y = 1, x = 2.5, "ripe?" = true }]
+
+[entity]
+apple = { y = "i64", x = "f64", "ripe?" = "bool" }
+tree = {}
+
+[world]
+regions = ["farm", "house"]
+description = "Test World \"description\""
+name = "Test World"
+
+[house]
+tree = []
+"#
+        .to_string();
+
+        let toml_parser = TomlParser::default();
+        let blueprint = toml_parser.parse_string(blueprint_string).unwrap();
+        let emitted = TomlEmitter::default().emit(&blueprint).unwrap();
+
+        assert_eq!(
+            emitted,
+            r#"[world]
+name = "Test World"
+description = 'Test World "description"'
+regions = ["farm", "house"]
+
+[entity]
+apple = { "ripe?" = "bool", x = "f64", y = "i64" }
+tree = {}
+
+[farm]
+apple = [
+    { nonce = 1, "ripe?" = true, x = 2.5, y = 1 },
+]
+
+[house]
+tree = []
+"#
+        );
+    }
+
+    #[test]
+    fn test_toml_parser_component_defaults() {
+        let blueprint_string = r#"
+[world]
+name = "Test World"
+description = "Test World description"
+regions = ["region"]
+
+[entity]
+player = { hp = { type = "i64", default = 100 }, speed = { type = "f64", default = 1.5 }, name = "String" }
+
+[region]
+player = []
+"#
+        .to_string();
+
+        let toml_parser = TomlParser::default();
+        let blueprint = toml_parser.parse_string(blueprint_string).unwrap();
+
+        let components = blueprint
+            .get_default_components(&"player".to_string())
+            .unwrap();
+        assert_eq!(components["hp"], ComponentValue::Integer(100));
+        assert_eq!(components["speed"], ComponentValue::Float(1.5));
+        assert_eq!(components["name"], ComponentValue::String(String::new()));
+
+        let emitted = TomlEmitter::default().emit(&blueprint).unwrap();
+        assert!(emitted.contains(r#"hp = { type = "i64", default = 100 }"#));
+        assert_eq!(toml_parser.parse_string(emitted).unwrap(), blueprint);
+    }
+
+    #[test]
+    fn test_toml_parser_component_defaults_mismatch() {
+        let blueprint_string = r#"
+[world]
+name = "Test World"
+description = "Test World description"
+regions = ["region"]
+
+[entity]
+player = { hp = { type = "i64", default = "full" }, speed = { default = 1.5 } }
+
+[region]
+player = []
+"#
+        .to_string();
+
+        let toml_parser = TomlParser::default();
+        let err = toml_parser
+            .parse_string(blueprint_string.clone())
+            .unwrap_err();
+        let ParseErrors(errors) = err.downcast_ref::<ParseErrors>().unwrap();
+        assert_eq!(errors.len(), 1);
+        assert_eq!(
+            errors[0].message,
+            "Entity player component speed must have a type"
+        );
+
+        let blueprint_string = blueprint_string.replace(", speed = { default = 1.5 }", "");
+        let err = toml_parser.parse_string(blueprint_string).unwrap_err();
+        let ValidationError(diagnostics) = err.downcast_ref::<ValidationError>().unwrap();
+        assert!(matches!(
+            diagnostics[0],
+            Diagnostic::DefaultTypeMismatch { .. }
+        ));
+    }
+
+    #[test]
+    fn test_toml_parser_component_constraints() {
+        let blueprint_string = r#"
+[world]
+name = "Test World"
+description = "Test World description"
+regions = ["region"]
+
+[entity]
+player = { hp = { type = "i64", default = 100, min = 0, max = 100 }, speed = { type = "f64", max = 10 }, class = { type = "String", one_of = ["warrior", "mage"], max_len = 16 } }
+
+[region]
+player = [{ hp = 50, speed = 1.5, class = "mage" }]
+"#
+        .to_string();
+
+        let toml_parser = TomlParser::default();
+        let blueprint = toml_parser.parse_string(blueprint_string.clone()).unwrap();
+
+        let constraints = &blueprint.constraints["player"];
+        assert_eq!(constraints["hp"].min, Some(ComponentValue::Integer(0)));
+        assert_eq!(constraints["speed"].max, Some(ComponentValue::Float(10.0)));
+        assert_eq!(constraints["class"].max_len, Some(16));
+
+        let emitted = TomlEmitter::default().emit(&blueprint).unwrap();
+        assert!(emitted.contains(r#"hp = { type = "i64", default = 100, min = 0, max = 100 }"#));
+        assert!(emitted.contains(
+            r#"class = { type = "String", one_of = ["warrior", "mage"], max_len = 16 }"#
+        ));
+        assert_eq!(toml_parser.parse_string(emitted).unwrap(), blueprint);
+
+        let blueprint_string = blueprint_string.replace("class = \"mage\"", "class = \"rogue\"");
+        let err = toml_parser.parse_string(blueprint_string).unwrap_err();
+        let ValidationError(diagnostics) = err.downcast_ref::<ValidationError>().unwrap();
+        assert_eq!(
+            diagnostics[0].to_string(),
+            "player #1 in region component class: value rogue is not one of warrior, mage"
+        );
+    }
+
+    #[test]
+    fn test_toml_parser_component_constraints_declaration() {
+        let blueprint_string = r#"
+[world]
+name = "Test World"
+description = "Test World description"
+regions = ["region"]
+
+[entity]
+player = { hp = { type = "i64", min = 0.5 }, name = { type = "String", max = 3, max_len = -1 }, class = { type = "String", one_of = [] } }
+
+[region]
+player = []
+"#
+        .to_string();
+
+        let toml_parser = TomlParser::default();
+        let err = toml_parser.parse_string(blueprint_string).unwrap_err();
+        let ParseErrors(errors) = err.downcast_ref::<ParseErrors>().unwrap();
+
+        assert_eq!(
+            errors
+                .iter()
+                .map(|e| e.message.as_str())
+                .collect::<Vec<_>>(),
+            vec![
+                "Entity player component hp min must be a i64, found 0.5",
+                "Entity player component name max is only allowed on numeric components, found String",
+                "Entity player component name max_len must be a non-negative integer",
+                "Entity player component class one_of must be a non-empty array",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_toml_parser_collection_components() {
+        let blueprint_string = r#"
+[world]
+name = "Test World"
+description = "Test World description"
+regions = ["region"]
+
+[entity]
+player = { inventory = "[String]", stats = "{ str: i64, dex: i64 }", flags = "Map<String, bool>", path = { type = "[{ x: f64, y: f64 }]", default = [] } }
+
+[region]
+player = [{ inventory = ["sword"], stats = { str = 3, dex = 5 }, flags = { quest = true }, path = [{ x = 0.0, y = 1.0 }] }]
+"#
+        .to_string();
+
+        let toml_parser = TomlParser::default();
+        let blueprint = toml_parser.parse_string(blueprint_string).unwrap();
+
+        assert_eq!(
+            blueprint.entities["player"]["stats"].to_string(),
+            "{ dex: i64, str: i64 }"
+        );
+
+        let instance = &blueprint.instances["region"]["player"][&1];
+        assert_eq!(
+            instance["inventory"],
+            ComponentValue::List(vec![ComponentValue::String("sword".to_string())])
+        );
+        assert!(matches!(instance["stats"], ComponentValue::Struct(_)));
+        assert!(matches!(instance["flags"], ComponentValue::Map(_)));
+        assert_eq!(instance["path"].to_string(), "[{ x: 0, y: 1 }]");
+
+        let emitted = TomlEmitter::default().emit(&blueprint).unwrap();
+        assert!(emitted.contains(r#"stats = "{ dex: i64, str: i64 }""#));
+        assert!(emitted.contains(r#"path = [{ x = 0.0, y = 1.0 }]"#));
+        assert_eq!(toml_parser.parse_string(emitted).unwrap(), blueprint);
+    }
+
+    #[test]
+    fn test_toml_parser_numeric_components() {
+        let blueprint_string = r#"
+[world]
+name = "Test World"
+description = "Test World description"
+regions = ["region"]
+
+[entity]
+player = { level = "u8", xp = "u32", gold = "u64", price = { type = "Fixed<2>", min = 0 } }
+
+[region]
+player = [{ level = 255, xp = 70000, gold = "18446744073709551615", price = 1.25 }]
+"#
+        .to_string();
+
+        let toml_parser = TomlParser::default();
+        let blueprint = toml_parser.parse_string(blueprint_string.clone()).unwrap();
+
+        let instance = &blueprint.instances["region"]["player"][&1];
+        assert_eq!(instance["level"], ComponentValue::U8(255));
+        assert_eq!(instance["xp"], ComponentValue::U32(70000));
+        assert_eq!(instance["gold"], ComponentValue::U64(u64::MAX));
+        assert_eq!(instance["price"].to_string(), "1.25");
+
+        let emitted = TomlEmitter::default().emit(&blueprint).unwrap();
+        assert!(emitted.contains(r#"gold = "18446744073709551615""#));
+        assert!(emitted.contains(r#"price = "1.25""#));
+        assert!(emitted.contains(r#"price = { type = "Fixed<2>", min = "0.00" }"#));
+        assert_eq!(toml_parser.parse_string(emitted).unwrap(), blueprint);
+
+        // out of range or more decimal places than declared
+        let blueprint_string = blueprint_string
+            .replace("level = 255", "level = 256")
+            .replace("price = 1.25", "price = 1.255");
+        let err = toml_parser.parse_string(blueprint_string).unwrap_err();
+        let ValidationError(diagnostics) = err.downcast_ref::<ValidationError>().unwrap();
+        assert_eq!(
+            diagnostics
+                .iter()
+                .map(|d| d.to_string())
+                .collect::<Vec<_>>(),
+            vec![
+                "player #1 in region component level expected u8, found 256",
+                "player #1 in region component price expected Fixed<2>, found 1.255",
+            ]
+        );
     }
 }
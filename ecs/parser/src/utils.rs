@@ -1,7 +1,7 @@
 use rush_ecs_core::blueprint::BlueprintString;
 use std::{
     fs::{read_dir, read_to_string},
-    path::Path,
+    path::{Path, PathBuf},
 };
 
 /// Load Blueprint from file
@@ -15,31 +15,63 @@ pub fn file_to_string(path: &Path) -> BlueprintString {
     read_to_string(path).expect("invalid path")
 }
 
+/// List files in Directory
+///
+/// Lists the files in a given directory, in file
+/// name order. Panics when path is invalid
+pub fn dir_to_paths(path: &Path) -> Vec<PathBuf> {
+    // expecting valid paths
+    let mut list_of_files = read_dir(path)
+        .expect("invalid path")
+        .map(|de| de.expect("invalid directory entry").path())
+        .filter(|p| p.is_file())
+        .collect::<Vec<_>>();
+
+    // read_dir order is platform-dependent, sort for a stable result
+    list_of_files.sort();
+
+    list_of_files
+}
+
+/// Is `true` if the file is a Blueprint source
+///
+/// Blueprint sources have one of the given `extensions`.
+/// Hidden files and editor leftovers (e.g. `.farm.toml.swp`,
+/// `#farm.toml#`, `farm.toml~`) are not Blueprint sources
+pub fn is_blueprint_file(path: &Path, extensions: &[&str]) -> bool {
+    let Some(file_name) = path.file_name().and_then(|f| f.to_str()) else {
+        return false;
+    };
+
+    if file_name.starts_with('.') || file_name.starts_with('#') || file_name.ends_with('~') {
+        return false;
+    }
+
+    path.extension()
+        .and_then(|e| e.to_str())
+        .is_some_and(|e| extensions.contains(&e))
+}
+
 /// Load Blueprint from Directory
 ///
 /// Loads blueprint from combination of files in
 /// a given directory. Panics when path is invalid.
 ///
 /// Returns a [`BlueprintString`] of the entire combined
-/// blueprint separated with an empty line (\n)
+/// blueprint, in file name order, separated with an empty line (\n)
 ///
 /// Example:
 ///
 /// file1_contents
-/// -> Empty line
+/// -> Empty line
 /// file2_contents
 ///
 pub fn dir_to_string(path: &Path) -> BlueprintString {
-    let list_of_files = read_dir(path).expect("invalid path");
-
     // holds the entire blueprint string
     let mut loaded_string = String::default();
 
     // loop over files in given directory
-    for de in list_of_files {
-        // expecting valid path
-        let dir_entry = de.expect("invalid directory entry");
-        let filepath = dir_entry.path();
+    for filepath in dir_to_paths(path) {
         let content = read_to_string(filepath).expect("invalid path");
 
         // combine
@@ -66,4 +98,41 @@ mod tests {
         let string = dir_to_string(path);
         assert_eq!(string, "a\n\nb\n\n");
     }
+
+    #[test]
+    fn test_dir_to_paths() {
+        let path = Path::new("mock/fixtures/utils/dir_to_string");
+        let paths = dir_to_paths(path);
+        assert_eq!(paths, vec![path.join("a"), path.join("b")]);
+    }
+
+    #[test]
+    fn test_is_blueprint_file() {
+        let extensions = ["toml"];
+
+        assert!(is_blueprint_file(
+            Path::new("blueprint/farm.toml"),
+            &extensions
+        ));
+        assert!(!is_blueprint_file(
+            Path::new("blueprint/README.md"),
+            &extensions
+        ));
+        assert!(!is_blueprint_file(
+            Path::new("blueprint/.farm.toml.swp"),
+            &extensions
+        ));
+        assert!(!is_blueprint_file(
+            Path::new("blueprint/.#farm.toml"),
+            &extensions
+        ));
+        assert!(!is_blueprint_file(
+            Path::new("blueprint/#farm.toml#"),
+            &extensions
+        ));
+        assert!(!is_blueprint_file(
+            Path::new("blueprint/farm.toml~"),
+            &extensions
+        ));
+    }
 }
@@ -1,9 +1,14 @@
 use std::str::FromStr;
 
 use crate::auth::{Auth, FilesystemAuth};
-use crate::storage::{Solana, Storage};
+use crate::storage::{pull, InstanceUpdate, Solana, Storage};
 use anyhow::Result;
-use rush_ecs_core::blueprint::{Component, ComponentValue, Entity, Region};
+use rush_ecs_core::{
+    blueprint::{Blueprint, Component, ComponentValue, Entity, Region},
+    diff::BlueprintDiff,
+    numeric::ComponentOp,
+    query::{Query, QueryRow},
+};
 use solana_sdk::{pubkey::Pubkey, signer::keypair::Keypair};
 
 pub struct BevySDK {
@@ -37,10 +42,34 @@ impl BevySDK {
         }
     }
 
+    /// Rebuild a deployed World as a [`Blueprint`]
+    pub fn pull(rpc_url: String, program_id: &str, world: &str) -> Result<Blueprint> {
+        let program_id_pubkey = Pubkey::from_str(program_id)?;
+        let world_pubkey = Pubkey::from_str(world)?;
+
+        pull(rpc_url, &program_id_pubkey, &world_pubkey)
+    }
+
     pub fn migrate(&mut self) -> Result<()> {
         self.storage.migrate()
     }
 
+    pub fn plan(&mut self) -> Result<BlueprintDiff> {
+        self.storage.plan()
+    }
+
+    pub fn sync(&mut self) -> Result<BlueprintDiff> {
+        self.storage.sync()
+    }
+
+    pub fn launch(&mut self) -> Result<()> {
+        self.storage.launch()
+    }
+
+    pub fn pause(&mut self) -> Result<()> {
+        self.storage.pause()
+    }
+
     pub fn create(&mut self, region: Region, entity: Entity) -> Result<u64> {
         self.storage.create(region, entity)
     }
@@ -66,6 +95,66 @@ impl BevySDK {
         self.storage.set(region, entity, nonce, component, value)
     }
 
+    pub fn set_many(&mut self, updates: Vec<InstanceUpdate>) -> Result<()> {
+        self.storage.set_many(updates)
+    }
+
+    pub fn version(&mut self, region: Region, entity: Entity, nonce: u64) -> Result<u64> {
+        self.storage.version(region, entity, nonce)
+    }
+
+    pub fn set_versioned(
+        &mut self,
+        region: Region,
+        entity: Entity,
+        nonce: u64,
+        component: Component,
+        value: ComponentValue,
+        expected_version: u64,
+    ) -> Result<()> {
+        self.storage
+            .set_versioned(region, entity, nonce, component, value, expected_version)
+    }
+
+    pub fn modify(
+        &mut self,
+        region: Region,
+        entity: Entity,
+        nonce: u64,
+        component: Component,
+        op: ComponentOp,
+    ) -> Result<()> {
+        self.storage.modify(region, entity, nonce, component, op)
+    }
+
+    pub fn increment(
+        &mut self,
+        region: Region,
+        entity: Entity,
+        nonce: u64,
+        component: Component,
+        delta: ComponentValue,
+    ) -> Result<()> {
+        self.storage
+            .increment(region, entity, nonce, component, delta)
+    }
+
+    pub fn decrement(
+        &mut self,
+        region: Region,
+        entity: Entity,
+        nonce: u64,
+        component: Component,
+        delta: ComponentValue,
+    ) -> Result<()> {
+        self.storage
+            .decrement(region, entity, nonce, component, delta)
+    }
+
+    pub fn query(&mut self, query: &Query) -> Result<Vec<QueryRow>> {
+        self.storage.query(query)
+    }
+
     pub fn signin(&self) -> Keypair {
         // TODO: Temporary
         self.keypair.insecure_clone()
@@ -1,9 +1,21 @@
+use rush_ecs_svm::error::RushStoreError;
 use thiserror::Error;
 
 #[derive(Error, Debug)]
 pub enum StorageError {
     #[error("storage not yet migrated")]
     NotMigrated,
+
+    #[error("world not found: {0}")]
+    WorldNotFound(String),
+
+    #[error("rush-store program error: {0}")]
+    Program(RushStoreError),
+
+    /// Instance was updated since its version was read,
+    /// refetch it and retry
+    #[error("instance changed since it was read")]
+    VersionConflict,
 }
 
 #[derive(Error, Debug)]
@@ -4,7 +4,22 @@
 //! for Rush SDKs
 
 use anyhow::Result;
-use rush_ecs_core::blueprint::{Component, ComponentValue, Entity, Region};
+use rush_ecs_core::{
+    blueprint::{Component, ComponentValue, Entity, Region},
+    diff::BlueprintDiff,
+    numeric::ComponentOp,
+    query::{Query, QueryRow},
+};
+
+/// Component value to set with [`Storage::set_many`]
+#[derive(Clone, Debug, PartialEq)]
+pub struct InstanceUpdate {
+    pub region: Region,
+    pub entity: Entity,
+    pub nonce: u64,
+    pub component: Component,
+    pub value: ComponentValue,
+}
 
 /// Storage Trait
 ///
@@ -22,10 +37,25 @@ pub trait Storage: Send + Sync + 'static {
     ///
     /// (e.g. Uploading World into Solana)
     fn migrate(&mut self) -> Result<()>;
+    /// Changes [`Storage::sync`] would make, without making them
+    fn plan(&mut self) -> Result<BlueprintDiff>;
+    /// Reconcile storage with the local Blueprint
+    ///
+    /// Only sends the changes needed, so syncing an
+    /// up to date storage does nothing. Returns the
+    /// changes made
+    fn sync(&mut self) -> Result<BlueprintDiff>;
+    /// Launch the World, opening it to Instance
+    /// authorities other than the World authority
+    ///
+    /// Also resumes a paused World
+    fn launch(&mut self) -> Result<()>;
+    /// Pause the World, only the World authority can
+    /// change Instances until it's launched again
+    fn pause(&mut self) -> Result<()>;
     /// Create new instance of Entity under a specific Region
     ///
-    /// Returns u64 index of new instance in Blueprint instances
-    /// mainly used for nonce
+    /// Returns the 1-based nonce of the new instance
     fn create(&mut self, region: Region, entity: Entity) -> Result<u64>;
     /// Delete specific instance of Entity under a specific Region
     fn delete(&mut self, region: Region, entity: Entity, nonce: u64) -> Result<()>;
@@ -40,6 +70,10 @@ pub trait Storage: Send + Sync + 'static {
     ) -> Result<ComponentValue>;
 
     /// Set value of a specific Component for a specific Instance
+    ///
+    /// Fails with a [`CoreError`](rush_ecs_core::error::CoreError)
+    /// if the value doesn't follow the Entity's schema or
+    /// violates the Component's Constraint
     fn set(
         &mut self,
         region: Region,
@@ -48,4 +82,84 @@ pub trait Storage: Send + Sync + 'static {
         component: Component,
         value: ComponentValue,
     ) -> Result<()>;
+
+    /// Set values of several Components, across Instances
+    ///
+    /// Values are checked like [`Storage::set`] before
+    /// anything is sent. Updates sent together are all or
+    /// nothing, but a batch may take several transactions
+    fn set_many(&mut self, updates: Vec<InstanceUpdate>) -> Result<()>;
+
+    /// Get the version of a specific Instance
+    ///
+    /// Every update to its Components bumps the version,
+    /// pass it to [`Storage::set_versioned`] to only update
+    /// an unchanged Instance
+    fn version(&mut self, region: Region, entity: Entity, nonce: u64) -> Result<u64>;
+
+    /// Set value of a specific Component if the Instance is
+    /// still at `expected_version`
+    ///
+    /// Fails with [`StorageError::VersionConflict`](crate::error::StorageError::VersionConflict)
+    /// if the Instance was updated since, refetch and retry
+    fn set_versioned(
+        &mut self,
+        region: Region,
+        entity: Entity,
+        nonce: u64,
+        component: Component,
+        value: ComponentValue,
+        expected_version: u64,
+    ) -> Result<()>;
+
+    /// Apply an operation to a numeric Component of a
+    /// specific Instance
+    ///
+    /// The value is read and written by the storage in one
+    /// step, so concurrent operations don't overwrite each
+    /// other. Deltas are cast to the Component's delta type
+    /// and bounds to its type, see [`ComponentOp::checked_cast`]
+    ///
+    /// The operation is first applied to the current value
+    /// and rejected if the result violates the Component's
+    /// Constraint
+    fn modify(
+        &mut self,
+        region: Region,
+        entity: Entity,
+        nonce: u64,
+        component: Component,
+        op: ComponentOp,
+    ) -> Result<()>;
+
+    /// Add to a numeric Component, see [`Storage::modify`]
+    fn increment(
+        &mut self,
+        region: Region,
+        entity: Entity,
+        nonce: u64,
+        component: Component,
+        delta: ComponentValue,
+    ) -> Result<()> {
+        self.modify(region, entity, nonce, component, ComponentOp::Add(delta))
+    }
+
+    /// Subtract from a numeric Component, see [`Storage::modify`]
+    ///
+    /// Deltas are signed, so unsigned Components go down too
+    fn decrement(
+        &mut self,
+        region: Region,
+        entity: Entity,
+        nonce: u64,
+        component: Component,
+        delta: ComponentValue,
+    ) -> Result<()> {
+        self.modify(region, entity, nonce, component, ComponentOp::Sub(delta))
+    }
+
+    /// Get the Instances matching a [`Query`]
+    ///
+    /// Rows are ordered by Region, Entity and nonce
+    fn query(&mut self, query: &Query) -> Result<Vec<QueryRow>>;
 }
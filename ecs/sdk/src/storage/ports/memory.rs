@@ -45,20 +45,18 @@
 //         // create new instance with default values
 //         let nonce = self.blueprint.add_default_instance(region, entity)?;
 //
-//         // return index (nonce) of new instance
+//         // return nonce of new instance
 //         Ok(nonce)
 //     }
 //
-//     // TODO: Implement Delete instance
-//     // NOTE: In general, we need to consider the fact that deleting an instance
-//     // in the Memory store breaks the Vector indexing
 //     async fn delete(&mut self, region: Region, entity: Entity, nonce: u64) -> Result<()> {
 //         // migration guard
 //         if !self.migrated {
 //             bail!(StorageError::NotMigrated);
 //         }
 //
-//         panic!("Not yet implemented");
+//         // nonce is tombstoned, other instances keep theirs
+//         self.blueprint.remove_instance(region, entity, nonce)?;
 //
 //         Ok(())
 //     }
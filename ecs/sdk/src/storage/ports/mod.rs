@@ -1,5 +1,4 @@
 mod memory;
 mod solana;
 
-pub use memory::*;
 pub use solana::*;
@@ -1,21 +1,43 @@
-use crate::{error::StorageError, storage::Storage};
+use crate::{
+    error::StorageError,
+    storage::{InstanceUpdate, Storage},
+};
 use anyhow::{bail, Result};
 use borsh::BorshDeserialize;
 use colored::Colorize;
-use rush_ecs_core::blueprint::{Blueprint, Component, ComponentValue, Entity, Region};
-use rush_ecs_parser::{toml::TomlParser, Loader};
+use rush_ecs_core::{
+    blueprint::{
+        Blueprint, Component, ComponentType, ComponentTypeTree, ComponentValue, Entity, Region,
+    },
+    diff::{BlueprintDiff, InstanceChange},
+    error::CoreError,
+    numeric::ComponentOp,
+    query::{Query, QueryRow},
+};
+use rush_ecs_parser::Loader;
 use rush_ecs_svm::{
-    client::{ix_create_world, ix_spawn_entity, ix_update_entity},
+    client::{
+        ix_batch_update, ix_create_world, ix_despawn_entity, ix_launch_world, ix_modify_component,
+        ix_pause_world, ix_spawn_entity, ix_update_entity, ix_update_entity_versioned,
+        ix_update_world,
+    },
+    error::RushStoreError,
+    instruction::ComponentUpdate,
     pda::{InstancePDA, WorldPDA},
     state::{Instance, World},
 };
-use solana_client::rpc_client::RpcClient;
+use solana_client::{client_error::ClientError, rpc_client::RpcClient};
 use solana_sdk::{
+    borsh1,
+    instruction::{Instruction, InstructionError},
+    message::Message,
+    packet::PACKET_DATA_SIZE,
     pubkey::Pubkey,
+    signature::Signature,
     signer::{keypair::Keypair, Signer},
-    transaction::Transaction,
+    transaction::{Transaction, TransactionError},
 };
-use std::path::Path;
+use std::{collections::BTreeMap, path::Path};
 
 // #[derive(Clone, Debug, Default, Eq, PartialEq)]
 #[derive(Debug, PartialEq)]
@@ -29,9 +51,8 @@ pub struct Solana {
 // TODO: Fix data type
 impl Solana {
     pub fn new(program_id: Pubkey, signer: Keypair, rpc_url: String, path: &str) -> Self {
-        // TODO: Support other parsers. Pinned to TOML for now
-        let toml_parser = TomlParser {};
-        let loader = Loader::new(toml_parser);
+        // parser is picked by each file's extension
+        let loader = Loader::default();
         let path = Path::new(path);
         let blueprint = loader
             .load_blueprint(path)
@@ -44,105 +65,526 @@ impl Solana {
             rpc_url,
         }
     }
-}
-
-impl Storage for Solana {
-    fn migrate(&mut self) -> Result<()> {
-        let client = RpcClient::new(self.rpc_url.clone());
 
-        let regions = self.blueprint.regions.keys().cloned().collect::<Vec<_>>();
-        let entities = self.blueprint.entities.keys().cloned().collect::<Vec<_>>();
-        let (world_pda, world_bump) = WorldPDA::find_pda(
+    fn world_pda(&self) -> (Pubkey, u8) {
+        WorldPDA::find_pda(
             &self.program_id,
             self.blueprint.name.as_str(),
             self.blueprint.description.as_str(),
-        );
+        )
+    }
 
-        let ix = ix_create_world(
-            &self.program_id,
+    /// Fetch an Instance and its PDA
+    ///
+    /// Fails with [`CoreError::InstanceNotFound`] if it
+    /// was never spawned or is despawned
+    fn fetch_instance(
+        &self,
+        region: &Region,
+        entity: &Entity,
+        nonce: u64,
+    ) -> Result<(Pubkey, Instance)> {
+        let client = RpcClient::new(self.rpc_url.clone());
+
+        let (world_pda, _) = self.world_pda();
+        let (instance_pda, _) =
+            InstancePDA::find_pda(&self.program_id, &world_pda, region, entity, nonce);
+
+        // despawned instances have no account or are zeroed
+        let Some(account) = client
+            .get_account_with_commitment(&instance_pda, client.commitment())?
+            .value
+        else {
+            bail!(CoreError::InstanceNotFound)
+        };
+        let instance_state = borsh1::try_from_slice_unchecked::<Instance>(&account.data)?;
+        if !instance_state.is_initialized() {
+            bail!(CoreError::InstanceNotFound)
+        }
+
+        Ok((instance_pda, instance_state))
+    }
+
+    /// Changes needed to bring the onchain World to the Blueprint
+    ///
+    /// `onchain` only has the signer's Instances, so Instances
+    /// of other authorities are never updated or despawned.
+    /// Warns about and leaves out the changes the store
+    /// program can't make
+    fn plan_sync(&self, onchain: Option<(&World, &Blueprint)>) -> BlueprintDiff {
+        let uncreated = Blueprint::new(
             self.blueprint.name.clone(),
             self.blueprint.description.clone(),
-            regions.clone(),
-            entities.clone(),
-            world_bump,
-            &world_pda,
-            &self.signer.pubkey(),
-            &self.signer.pubkey(),
         );
+        let (world, onchain) = match onchain {
+            Some((world, onchain)) => (Some(world), onchain),
+            None => (None, &uncreated),
+        };
+
+        let mut diff = onchain.diff(&self.blueprint);
+
+        // schemas aren't stored onchain
+        diff.schema_changes.clear();
+
+        // Regions and Entities with live Instances can't be
+        // removed, and the signer can't despawn the Instances
+        // of other authorities. Their Instances are kept too,
+        // so nothing is despawned for a removal that fails
+        let mut kept_regions = Vec::new();
+        let mut kept_entities = Vec::new();
+        if let Some(world) = world {
+            let foreign_live = |region: &Region, entity: &Entity| {
+                let own = onchain
+                    .instances
+                    .get(region)
+                    .and_then(|e| e.get(entity))
+                    .map_or(0, |i| i.len() as u64);
+                world.live_instances(region, entity) > own
+            };
+
+            diff.removed_regions.retain(|region| {
+                if world.entities.iter().any(|e| foreign_live(region, e)) {
+                    println!(
+                        "[{}] {region} has live Instances of other authorities, it can't be removed",
+                        "WARNING".yellow().bold(),
+                    );
+                    kept_regions.push(region.clone());
+                    return false;
+                }
 
-        let recent_blockhash = client.get_latest_blockhash()?;
-        let tx = Transaction::new_signed_with_payer(
-            &[ix],
-            Some(&self.signer.pubkey()),
-            &[&self.signer],
-            recent_blockhash,
-        );
+                true
+            });
+            diff.removed_entities.retain(|entity| {
+                if world.regions.iter().any(|r| foreign_live(r, entity)) {
+                    println!(
+                        "[{}] {entity} has live Instances of other authorities, it can't be removed",
+                        "WARNING".yellow().bold(),
+                    );
+                    kept_entities.push(entity.clone());
+                    return false;
+                }
+
+                true
+            });
+        }
+
+        diff.instance_changes.retain(|change| match change {
+            InstanceChange::Added {
+                region,
+                entity,
+                nonce,
+                ..
+            } => {
+                let last_nonce = onchain
+                    .nonces
+                    .get(region)
+                    .and_then(|e| e.get(entity))
+                    .copied()
+                    .unwrap_or_default();
+
+                if *nonce <= last_nonce {
+                    println!(
+                        "[{}] {entity} #{nonce} in {region} was despawned onchain or belongs to another authority, its nonce can't be reused",
+                        "WARNING".yellow().bold(),
+                    );
+                    return false;
+                }
+
+                true
+            }
+            InstanceChange::Updated {
+                region,
+                entity,
+                nonce,
+                component,
+                from,
+                to,
+            } => {
+                if from.is_none() || to.is_none() {
+                    println!(
+                        "[{}] {entity} #{nonce} in {region} can't add or remove component {component} onchain",
+                        "WARNING".yellow().bold(),
+                    );
+                    return false;
+                }
+
+                true
+            }
+            InstanceChange::Removed { region, entity, .. } => {
+                !kept_regions.contains(region) && !kept_entities.contains(entity)
+            }
+        });
+
+        diff
+    }
 
-        let signature = client.send_and_confirm_transaction(&tx)?;
+    /// Launch or pause the onchain World
+    ///
+    /// Does nothing if the World is already in that state
+    fn set_launched(&self, is_launched: bool) -> Result<()> {
+        let client = RpcClient::new(self.rpc_url.clone());
+        let (world_pda, _) = self.world_pda();
+
+        let Some(world_account) = client
+            .get_account_with_commitment(&world_pda, client.commitment())?
+            .value
+        else {
+            bail!(StorageError::NotMigrated)
+        };
+        let world = borsh1::try_from_slice_unchecked::<World>(&world_account.data)?;
+
+        let state = match is_launched {
+            true => "launched",
+            false => "paused",
+        };
+
+        if world.is_launched == is_launched {
+            println!(
+                "[{}] World is already {}: {}",
+                "WARNING".yellow().bold(),
+                state,
+                world_pda
+            );
+            return Ok(());
+        }
+
+        let ix = match is_launched {
+            true => ix_launch_world(&self.program_id, &world_pda, &self.signer.pubkey()),
+            false => ix_pause_world(&self.program_id, &world_pda, &self.signer.pubkey()),
+        };
+        let signature = self.send(&client, &[ix])?;
 
         println!(
-            "[{}] Created world: {}, Signature: {}",
+            "[{}] World {}: {}, Signature: {}",
             "SUCCESS".green().bold(),
+            state,
             world_pda,
             signature
         );
 
-        // push spawn_entity instructions
-        for region_name in regions.iter() {
-            for entity_name in entities.iter() {
-                // Blueprint preload ensures that unwrap is ok here
-                let instances = self
-                    .blueprint
-                    .instances
-                    .get(region_name)
-                    .unwrap()
-                    .get(entity_name)
-                    .unwrap();
-
-                for (each_index, each_instance) in instances.iter().enumerate() {
-                    let nonce = each_index as u64 + 1;
-                    let (instance_pda, instance_bump) = InstancePDA::find_pda(
-                        &self.program_id,
-                        &world_pda,
-                        region_name,
-                        entity_name,
-                        nonce,
-                    );
+        Ok(())
+    }
 
-                    let ix = ix_spawn_entity(
-                        &self.program_id,
-                        region_name.to_string(),
-                        entity_name.to_string(),
-                        each_instance.clone(),
-                        nonce,
-                        instance_bump,
-                        &instance_pda,
-                        &self.signer.pubkey(),
-                        &world_pda,
-                    );
+    /// Send instructions in a single transaction
+    fn send(&self, client: &RpcClient, ixs: &[Instruction]) -> Result<Signature> {
+        let recent_blockhash = client.get_latest_blockhash()?;
+        let tx = Transaction::new_signed_with_payer(
+            ixs,
+            Some(&self.signer.pubkey()),
+            &[&self.signer],
+            recent_blockhash,
+        );
 
-                    let recent_blockhash = client.get_latest_blockhash()?;
-                    let tx = Transaction::new_signed_with_payer(
-                        &[ix],
-                        Some(&self.signer.pubkey()),
-                        &[&self.signer],
-                        recent_blockhash,
-                    );
-                    let signature = client.send_and_confirm_transaction(&tx)?;
-                    println!(
-                        "[{}] Spawned #{}: {}, Signature: {}",
-                        "SUCCESS".green().bold(),
-                        nonce,
-                        instance_pda,
-                        signature
-                    );
-                }
+        client
+            .send_and_confirm_transaction(&tx)
+            .map_err(decode_client_error)
+    }
+}
+
+/// Pack updates into as few BatchUpdate instructions as
+/// possible, each fitting in a transaction of its own
+///
+/// Updates keep their order, so a later value for the same
+/// Component wins
+fn pack_updates(
+    program_id: &Pubkey,
+    world: &Pubkey,
+    instance_authority: &Pubkey,
+    updates: Vec<(Pubkey, Component, ComponentValue)>,
+) -> Vec<Instruction> {
+    let build = |instances: &[Pubkey], updates: &[ComponentUpdate]| {
+        ix_batch_update(
+            program_id,
+            updates.to_vec(),
+            instances,
+            instance_authority,
+            world,
+        )
+    };
+
+    let mut ixs = Vec::new();
+    let mut instances: Vec<Pubkey> = Vec::new();
+    let mut batch: Vec<ComponentUpdate> = Vec::new();
+
+    for (instance, component, value) in updates {
+        let mut next_instances = instances.clone();
+        let index = match next_instances.iter().position(|i| *i == instance) {
+            Some(index) => index,
+            None => {
+                next_instances.push(instance);
+                next_instances.len() - 1
             }
+        };
+        let mut next_batch = batch.clone();
+        next_batch.push(ComponentUpdate {
+            instance: index as u8,
+            component: component.clone(),
+            value: value.clone(),
+        });
+
+        let fits = index <= u8::MAX as usize
+            && fits_in_transaction(&[build(&next_instances, &next_batch)], instance_authority);
+        if fits || batch.is_empty() {
+            // an update too large on its own is sent anyway
+            // and rejected by the cluster
+            (instances, batch) = (next_instances, next_batch);
+            continue;
+        }
+
+        ixs.push(build(&instances, &batch));
+        instances = vec![instance];
+        batch = vec![ComponentUpdate {
+            instance: 0,
+            component,
+            value,
+        }];
+    }
+
+    if !batch.is_empty() {
+        ixs.push(build(&instances, &batch));
+    }
+
+    ixs
+}
+
+/// Is `true` if a transaction of the instructions, signed
+/// by the payer, fits in a packet
+fn fits_in_transaction(ixs: &[Instruction], payer: &Pubkey) -> bool {
+    let message = Message::new(ixs, Some(payer));
+    let signatures = message.header.num_required_signatures as usize;
+
+    // compact array of signatures before the message
+    1 + signatures * 64 + message.serialize().len() <= PACKET_DATA_SIZE
+}
+
+/// Decodes rush-store's custom error codes of a failed transaction
+/// into a [`StorageError`]
+///
+/// Other errors are returned as they are
+fn decode_client_error(err: ClientError) -> anyhow::Error {
+    if let Some(TransactionError::InstructionError(_, InstructionError::Custom(code))) =
+        err.get_transaction_error()
+    {
+        match RushStoreError::from_code(code) {
+            Some(RushStoreError::VersionMismatch) => return StorageError::VersionConflict.into(),
+            Some(e) => return StorageError::Program(e).into(),
+            None => {}
         }
+    }
+
+    err.into()
+}
 
+impl Storage for Solana {
+    fn migrate(&mut self) -> Result<()> {
+        // creates the World on the first run
+        self.sync()?;
         Ok(())
     }
 
+    fn plan(&mut self) -> Result<BlueprintDiff> {
+        let client = RpcClient::new(self.rpc_url.clone());
+        let (world_pda, _) = self.world_pda();
+
+        // Instances of other authorities are never synced
+        let onchain = fetch_blueprint(
+            &client,
+            &self.program_id,
+            &world_pda,
+            Some(&self.signer.pubkey()),
+        )?;
+        Ok(self.plan_sync(onchain.as_ref().map(|(w, b)| (w, b))))
+    }
+
+    fn sync(&mut self) -> Result<BlueprintDiff> {
+        let client = RpcClient::new(self.rpc_url.clone());
+        let (world_pda, world_bump) = self.world_pda();
+
+        // Instances of other authorities are never synced
+        let onchain = fetch_blueprint(
+            &client,
+            &self.program_id,
+            &world_pda,
+            Some(&self.signer.pubkey()),
+        )?;
+        let diff = self.plan_sync(onchain.as_ref().map(|(w, b)| (w, b)));
+
+        if diff.is_empty() {
+            println!(
+                "[{}] World is up to date: {}",
+                "SUCCESS".green().bold(),
+                world_pda
+            );
+            return Ok(diff);
+        }
+
+        // despawn before the World stops tracking their Region or Entity
+        for change in diff.instance_changes.iter() {
+            let InstanceChange::Removed {
+                region,
+                entity,
+                nonce,
+                ..
+            } = change
+            else {
+                continue;
+            };
+
+            let (instance_pda, _) =
+                InstancePDA::find_pda(&self.program_id, &world_pda, region, entity, *nonce);
+            let ix = ix_despawn_entity(
+                &self.program_id,
+                region.clone(),
+                entity.clone(),
+                &instance_pda,
+                &self.signer.pubkey(),
+                &world_pda,
+            );
+
+            let signature = self.send(&client, &[ix])?;
+            println!(
+                "[{}] Despawned #{}: {}, Signature: {}",
+                "SUCCESS".green().bold(),
+                nonce,
+                instance_pda,
+                signature
+            );
+        }
+
+        if onchain.is_none() {
+            let regions = self.blueprint.regions.keys().cloned().collect::<Vec<_>>();
+            let entities = self.blueprint.entities.keys().cloned().collect::<Vec<_>>();
+            let ix = ix_create_world(
+                &self.program_id,
+                self.blueprint.name.clone(),
+                self.blueprint.description.clone(),
+                regions,
+                entities,
+                world_bump,
+                &world_pda,
+                &self.signer.pubkey(),
+                &self.signer.pubkey(),
+            );
+
+            let signature = self.send(&client, &[ix])?;
+            println!(
+                "[{}] Created world: {}, Signature: {}",
+                "SUCCESS".green().bold(),
+                world_pda,
+                signature
+            );
+        } else if !(diff.added_regions.is_empty()
+            && diff.removed_regions.is_empty()
+            && diff.added_entities.is_empty()
+            && diff.removed_entities.is_empty())
+        {
+            // instances of removed regions and entities are
+            // despawned above, no need to force
+            let ix = ix_update_world(
+                &self.program_id,
+                diff.added_regions.clone(),
+                diff.removed_regions.clone(),
+                diff.added_entities.clone(),
+                diff.removed_entities.clone(),
+                false,
+                &world_pda,
+                &self.signer.pubkey(),
+            );
+
+            let signature = self.send(&client, &[ix])?;
+            println!(
+                "[{}] Updated world: {}, Signature: {}",
+                "SUCCESS".green().bold(),
+                world_pda,
+                signature
+            );
+        }
+
+        // spawn new instances in nonce order, the World
+        // counts them to issue the next nonce
+        for change in diff.instance_changes.iter() {
+            let InstanceChange::Added {
+                region,
+                entity,
+                nonce,
+                components,
+            } = change
+            else {
+                continue;
+            };
+
+            let (instance_pda, instance_bump) =
+                InstancePDA::find_pda(&self.program_id, &world_pda, region, entity, *nonce);
+
+            let ix = ix_spawn_entity(
+                &self.program_id,
+                region.clone(),
+                entity.clone(),
+                components.clone(),
+                *nonce,
+                instance_bump,
+                &instance_pda,
+                &self.signer.pubkey(),
+                &world_pda,
+            );
+
+            let signature = self.send(&client, &[ix])?;
+            println!(
+                "[{}] Spawned #{}: {}, Signature: {}",
+                "SUCCESS".green().bold(),
+                nonce,
+                instance_pda,
+                signature
+            );
+        }
+
+        // changed components are packed into BatchUpdates,
+        // like set_many
+        let updates = diff
+            .instance_changes
+            .iter()
+            .filter_map(|change| match change {
+                InstanceChange::Updated {
+                    region,
+                    entity,
+                    nonce,
+                    component,
+                    to: Some(value),
+                    ..
+                } => {
+                    let (instance_pda, _) =
+                        InstancePDA::find_pda(&self.program_id, &world_pda, region, entity, *nonce);
+                    Some((instance_pda, component.clone(), value.clone()))
+                }
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+
+        let ixs = pack_updates(&self.program_id, &world_pda, &self.signer.pubkey(), updates);
+        let count = ixs.len();
+
+        for (i, ix) in ixs.into_iter().enumerate() {
+            let signature = self.send(&client, &[ix])?;
+            println!(
+                "[{}] Updated batch {}/{}, Signature: {}",
+                "SUCCESS".green().bold(),
+                i + 1,
+                count,
+                signature
+            );
+        }
+
+        Ok(diff)
+    }
+
+    fn launch(&mut self) -> Result<()> {
+        self.set_launched(true)
+    }
+
+    fn pause(&mut self) -> Result<()> {
+        self.set_launched(false)
+    }
+
     fn create(&mut self, region: Region, entity: Entity) -> Result<u64> {
         let client = RpcClient::new(self.rpc_url.clone());
 
@@ -155,9 +597,22 @@ impl Storage for Solana {
         let world_account_data = client.get_account_data(&world_pda)?;
         let world = World::try_from_slice(&world_account_data)?;
         // TODO: Consider using the nonce internally in spawn_entity instruction
-        let nonce = world.instances.get(&region).unwrap().get(&entity).unwrap() + 1;
-
-        let default_components = self.blueprint.get_default_components(&entity).unwrap();
+        // removed Regions and Entities keep their last nonce
+        if !world.regions.contains(&region) {
+            bail!(CoreError::RegionNotFound)
+        }
+        if !world.entities.contains(&entity) {
+            bail!(CoreError::EntityNotFound)
+        }
+        let last_nonce = world
+            .instances
+            .get(&region)
+            .and_then(|e| e.get(&entity))
+            .copied()
+            .unwrap_or_default();
+        let nonce = last_nonce + 1;
+
+        let default_components = self.blueprint.get_default_components(&entity)?;
         let (instance_pda, instance_bump) =
             InstancePDA::find_pda(&self.program_id, &world_pda, &region, &entity, nonce);
 
@@ -173,14 +628,7 @@ impl Storage for Solana {
             &world_pda,
         );
 
-        let recent_blockhash = client.get_latest_blockhash()?;
-        let tx = Transaction::new_signed_with_payer(
-            &[ix],
-            Some(&self.signer.pubkey()),
-            &[&self.signer],
-            recent_blockhash,
-        );
-        let signature = client.send_and_confirm_transaction(&tx)?;
+        let signature = self.send(&client, &[ix])?;
 
         println!(
             "[{}] Spawned #{}: {}, Signature: {}",
@@ -193,8 +641,31 @@ impl Storage for Solana {
         Ok(nonce)
     }
 
-    // TODO: Implement Delete instance
     fn delete(&mut self, region: Region, entity: Entity, nonce: u64) -> Result<()> {
+        let client = RpcClient::new(self.rpc_url.clone());
+        let (world_pda, _) = self.world_pda();
+
+        let (instance_pda, _) =
+            InstancePDA::find_pda(&self.program_id, &world_pda, &region, &entity, nonce);
+
+        let ix = ix_despawn_entity(
+            &self.program_id,
+            region,
+            entity,
+            &instance_pda,
+            &self.signer.pubkey(),
+            &world_pda,
+        );
+
+        let signature = self.send(&client, &[ix])?;
+        println!(
+            "[{}] Despawned #{}: {}, Signature: {}",
+            "SUCCESS".green().bold(),
+            nonce,
+            instance_pda,
+            signature
+        );
+
         Ok(())
     }
 
@@ -205,20 +676,12 @@ impl Storage for Solana {
         nonce: u64,
         component: Component,
     ) -> Result<ComponentValue> {
-        let client = RpcClient::new(self.rpc_url.clone());
-
-        let (world_pda, _) = WorldPDA::find_pda(
-            &self.program_id,
-            self.blueprint.name.as_str(),
-            self.blueprint.description.as_str(),
-        );
-
-        let (instance_pda, _) =
-            InstancePDA::find_pda(&self.program_id, &world_pda, &region, &entity, nonce);
+        let (instance_pda, instance_state) = self.fetch_instance(&region, &entity, nonce)?;
 
-        let data = client.get_account_data(&instance_pda)?;
-        let instance_state = Instance::try_from_slice(&data)?;
-        let value = instance_state.components.get(&component).unwrap().clone();
+        let value = match instance_state.components.get(&component) {
+            Some(v) => v.clone(),
+            None => bail!(CoreError::ComponentNotFound),
+        };
 
         println!(
             "[{}] Fetching #{}: {}",
@@ -238,6 +701,11 @@ impl Storage for Solana {
         component: Component,
         value: ComponentValue,
     ) -> Result<()> {
+        // reject values the Blueprint doesn't allow before
+        // paying for a transaction
+        self.blueprint
+            .check_component_value(&entity, &component, &value)?;
+
         let client = RpcClient::new(self.rpc_url.clone());
 
         let (world_pda, _) = WorldPDA::find_pda(
@@ -255,16 +723,10 @@ impl Storage for Solana {
             value,
             &instance_pda,
             &self.signer.pubkey(),
+            &world_pda,
         );
 
-        let recent_blockhash = client.get_latest_blockhash()?;
-        let tx = Transaction::new_signed_with_payer(
-            &[ix],
-            Some(&self.signer.pubkey()),
-            &[&self.signer],
-            recent_blockhash,
-        );
-        let signature = client.send_and_confirm_transaction(&tx)?;
+        let signature = self.send(&client, &[ix])?;
 
         println!(
             "[{}] Updating #{}: {}, Signature: {}",
@@ -276,71 +738,469 @@ impl Storage for Solana {
 
         Ok(())
     }
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use assert_matches::assert_matches;
-    use borsh::BorshDeserialize;
-    use rush_ecs_svm::state::Instance;
-    use solana_program_test::*;
-    use solana_sdk::{
-        borsh1,
-        signer::{keypair::Keypair, SeedDerivable},
-    };
-    use std::str::FromStr;
 
-    // Happy path
-    #[test]
-    fn test_solana_migrate() {
-        // prepare test context
-        let program_id = Pubkey::from_str("FXm4HiySCyKv3HrynYKY7yfanyH7dJGMuvxXsbnvtW5c").unwrap();
-        let seed = [
-            192, 45, 79, 47, 38, 198, 135, 27, 191, 116, 8, 103, 96, 204, 251, 131, 110, 7, 179, 0,
-            236, 71, 217, 202, 191, 140, 13, 148, 165, 62, 107, 20, 118, 252, 252, 98, 134, 2, 49,
-            17, 166, 221, 114, 65, 149, 220, 228, 81, 254, 57, 227, 230, 70, 178, 135, 176, 103,
-            235, 188, 54, 173, 91, 232, 57,
-        ];
+    fn set_many(&mut self, updates: Vec<InstanceUpdate>) -> Result<()> {
+        // reject values the Blueprint doesn't allow before
+        // paying for any transaction
+        for update in updates.iter() {
+            self.blueprint.check_component_value(
+                &update.entity,
+                &update.component,
+                &update.value,
+            )?;
+        }
 
-        let signer = Keypair::from_seed(&seed).unwrap();
+        let client = RpcClient::new(self.rpc_url.clone());
+        let (world_pda, _) = self.world_pda();
+
+        let updates = updates
+            .into_iter()
+            .map(|u| {
+                let (instance_pda, _) = InstancePDA::find_pda(
+                    &self.program_id,
+                    &world_pda,
+                    &u.region,
+                    &u.entity,
+                    u.nonce,
+                );
+                (instance_pda, u.component, u.value)
+            })
+            .collect::<Vec<_>>();
+
+        let ixs = pack_updates(&self.program_id, &world_pda, &self.signer.pubkey(), updates);
+        let count = ixs.len();
+
+        for (i, ix) in ixs.into_iter().enumerate() {
+            let signature = self.send(&client, &[ix])?;
+
+            println!(
+                "[{}] Updating batch {}/{}, Signature: {}",
+                "SUCCESS".green().bold(),
+                i + 1,
+                count,
+                signature
+            );
+        }
 
-        let path_str = "fixtures/blueprint.toml";
-        let loader = Loader::new(TomlParser {});
-        let path = Path::new(path_str);
-        let blueprint = loader.load_blueprint(path).unwrap();
+        Ok(())
+    }
 
-        let (world_pda, world_bump) =
-            WorldPDA::find_pda(&program_id, &blueprint.name, &blueprint.description);
+    fn version(&mut self, region: Region, entity: Entity, nonce: u64) -> Result<u64> {
+        let (_, instance_state) = self.fetch_instance(&region, &entity, nonce)?;
+        Ok(instance_state.version)
+    }
 
-        let rpc_url = String::from("http://127.0.0.1:8899");
-        let client = RpcClient::new(rpc_url.clone());
-        let mut solana = Solana::new(program_id, signer.insecure_clone(), rpc_url, path_str);
+    fn set_versioned(
+        &mut self,
+        region: Region,
+        entity: Entity,
+        nonce: u64,
+        component: Component,
+        value: ComponentValue,
+        expected_version: u64,
+    ) -> Result<()> {
+        // reject values the Blueprint doesn't allow before
+        // paying for a transaction
+        self.blueprint
+            .check_component_value(&entity, &component, &value)?;
 
-        solana.migrate().unwrap();
+        let client = RpcClient::new(self.rpc_url.clone());
 
-        let data = client.get_account_data(&world_pda).unwrap();
-        let state = borsh1::try_from_slice_unchecked::<World>(&data).unwrap();
+        let (world_pda, _) = self.world_pda();
+        let (instance_pda, _) =
+            InstancePDA::find_pda(&self.program_id, &world_pda, &region, &entity, nonce);
 
-        assert!(state.is_initialized());
-        assert_eq!(state.name, blueprint.name);
-        assert_eq!(state.description, blueprint.description);
-        assert_eq!(
-            state.regions,
-            blueprint.regions.into_keys().collect::<Vec<_>>()
-        );
-        assert_eq!(
-            state.entities,
-            blueprint.entities.into_keys().collect::<Vec<_>>()
+        let ix = ix_update_entity_versioned(
+            &self.program_id,
+            component,
+            value,
+            expected_version,
+            &instance_pda,
+            &self.signer.pubkey(),
+            &world_pda,
         );
-        assert_eq!(state.world_authority, signer.pubkey());
-        assert_eq!(state.bump, world_bump);
-        assert!(!state.is_launched);
-    }
 
-    // Happy path
-    #[test]
-    fn test_solana_create() {
+        let signature = self.send(&client, &[ix])?;
+
+        println!(
+            "[{}] Updating #{} at version {}: {}, Signature: {}",
+            "SUCCESS".green().bold(),
+            nonce,
+            expected_version,
+            instance_pda,
+            signature
+        );
+
+        Ok(())
+    }
+
+    fn modify(
+        &mut self,
+        region: Region,
+        entity: Entity,
+        nonce: u64,
+        component: Component,
+        op: ComponentOp,
+    ) -> Result<()> {
+        // deltas and bounds must be of the expected types on-chain
+        let component_type = match self.blueprint.entities.get(&entity) {
+            Some(component_type_tree) => match component_type_tree.get(&component) {
+                Some(t) => t,
+                None => bail!(CoreError::ComponentNotFound),
+            },
+            None => bail!(CoreError::EntityNotFound),
+        };
+        let Some(op) = op.checked_cast(component_type) else {
+            bail!(CoreError::MismatchedDataType)
+        };
+
+        // reject results the Blueprint doesn't allow before
+        // paying for a transaction, concurrent operations may
+        // still move the value on-chain in between
+        let (instance_pda, instance_state) = self.fetch_instance(&region, &entity, nonce)?;
+        let Some(value) = instance_state.components.get(&component) else {
+            bail!(CoreError::ComponentNotFound)
+        };
+        let Some(value) = value.apply(&op) else {
+            bail!(CoreError::InvalidOperation(component))
+        };
+        self.blueprint
+            .check_component_value(&entity, &component, &value)?;
+
+        let client = RpcClient::new(self.rpc_url.clone());
+
+        let (world_pda, _) = self.world_pda();
+
+        let ix = ix_modify_component(
+            &self.program_id,
+            component,
+            op,
+            &instance_pda,
+            &self.signer.pubkey(),
+            &world_pda,
+        );
+
+        let signature = self.send(&client, &[ix])?;
+
+        println!(
+            "[{}] Modifying #{}: {}, Signature: {}",
+            "SUCCESS".green().bold(),
+            nonce,
+            instance_pda,
+            signature
+        );
+
+        Ok(())
+    }
+
+    fn query(&mut self, query: &Query) -> Result<Vec<QueryRow>> {
+        let client = RpcClient::new(self.rpc_url.clone());
+
+        let (world_pda, _) = WorldPDA::find_pda(
+            &self.program_id,
+            self.blueprint.name.as_str(),
+            self.blueprint.description.as_str(),
+        );
+        let world_account_data = client.get_account_data(&world_pda)?;
+        let world = World::try_from_slice(&world_account_data)?;
+
+        // the World records the last nonce spawned for each
+        // Region and Entity, only fetch the ones the query covers
+        let mut keys = Vec::new();
+        for (region, entities) in world.instances.iter() {
+            for (entity, last_nonce) in entities.iter() {
+                if !(query.matches_key(region, entity) && world.is_tracking(region, entity)) {
+                    continue;
+                }
+
+                for nonce in 1..=*last_nonce {
+                    let (instance_pda, _) =
+                        InstancePDA::find_pda(&self.program_id, &world_pda, region, entity, nonce);
+                    keys.push((region, entity, nonce, instance_pda));
+                }
+            }
+        }
+
+        let mut rows = Vec::new();
+        // RPC nodes serve at most 100 accounts per request
+        for chunk in keys.chunks(100) {
+            let pubkeys = chunk.iter().map(|k| k.3).collect::<Vec<_>>();
+            let accounts = client.get_multiple_accounts(&pubkeys)?;
+
+            for ((region, entity, nonce, _), account) in chunk.iter().zip(accounts) {
+                // despawned instances have no account
+                let Some(account) = account else {
+                    continue;
+                };
+
+                let instance_state = borsh1::try_from_slice_unchecked::<Instance>(&account.data)?;
+                if !instance_state.is_initialized() {
+                    continue;
+                }
+
+                if let Some(row) = query.apply(region, entity, *nonce, &instance_state.components) {
+                    rows.push(row);
+                }
+            }
+        }
+
+        println!(
+            "[{}] Queried {} instances: {} matched",
+            "SUCCESS".green().bold(),
+            keys.len(),
+            rows.len()
+        );
+
+        Ok(rows)
+    }
+}
+
+/// Pull a World from the store program
+///
+/// Rebuilds the [`Blueprint`] from the World account and
+/// every Instance account it lists, of any authority,
+/// under their onchain Nonces. Component schemas
+/// aren't stored onchain, they are inferred from the
+/// Instances' values
+pub fn pull(rpc_url: String, program_id: &Pubkey, world_pda: &Pubkey) -> Result<Blueprint> {
+    let client = RpcClient::new(rpc_url);
+
+    match fetch_blueprint(&client, program_id, world_pda, None)? {
+        Some((_, blueprint)) => Ok(blueprint),
+        None => bail!(StorageError::WorldNotFound(world_pda.to_string())),
+    }
+}
+
+/// Fetch the onchain World and rebuild it as a [`Blueprint`]
+///
+/// Returns `None` if the World wasn't created yet. Instances
+/// of other authorities are left out if an `authority` is given
+fn fetch_blueprint(
+    client: &RpcClient,
+    program_id: &Pubkey,
+    world_pda: &Pubkey,
+    authority: Option<&Pubkey>,
+) -> Result<Option<(World, Blueprint)>> {
+    let Some(world_account) = client
+        .get_account_with_commitment(world_pda, client.commitment())?
+        .value
+    else {
+        return Ok(None);
+    };
+    let world = borsh1::try_from_slice_unchecked::<World>(&world_account.data)?;
+
+    // the World records the last nonce spawned for each
+    // Region and Entity, despawned nonces have no account
+    let mut keys = Vec::new();
+    for (region, entities) in world.instances.iter() {
+        for (entity, last_nonce) in entities.iter() {
+            // Instances left behind by removed Regions and Entities
+            if !world.is_tracking(region, entity) {
+                continue;
+            }
+
+            for nonce in 1..=*last_nonce {
+                let (instance_pda, _) =
+                    InstancePDA::find_pda(program_id, world_pda, region, entity, nonce);
+                keys.push((region, entity, nonce, instance_pda));
+            }
+        }
+    }
+
+    // RPC nodes serve at most 100 accounts per request
+    let mut instances = Vec::new();
+    for chunk in keys.chunks(100) {
+        let pubkeys = chunk.iter().map(|k| k.3).collect::<Vec<_>>();
+        let accounts = client.get_multiple_accounts(&pubkeys)?;
+
+        for ((region, entity, nonce, _), account) in chunk.iter().zip(accounts) {
+            let Some(account) = account else {
+                continue;
+            };
+
+            let instance_state = borsh1::try_from_slice_unchecked::<Instance>(&account.data)?;
+            if !instance_state.is_initialized() {
+                continue;
+            }
+
+            instances.push(((*region).clone(), (*entity).clone(), *nonce, instance_state));
+        }
+    }
+
+    let blueprint = onchain_blueprint(&world, instances, authority);
+    Ok(Some((world, blueprint)))
+}
+
+/// Build a [`Blueprint`] from a World and its spawned Instances
+///
+/// Instances keep their onchain Nonces. Instances of other
+/// authorities are left out if an `authority` is given
+fn onchain_blueprint(
+    world: &World,
+    instances: Vec<(Region, Entity, u64, Instance)>,
+    authority: Option<&Pubkey>,
+) -> Blueprint {
+    let mut blueprint = Blueprint::new(world.name.clone(), world.description.clone());
+
+    // removed Regions and Entities keep their last nonce
+    for (region, entities) in world.instances.iter() {
+        for (entity, last_nonce) in entities.iter() {
+            if world.is_tracking(region, entity) {
+                blueprint
+                    .instances
+                    .entry(region.clone())
+                    .or_default()
+                    .insert(entity.clone(), BTreeMap::new());
+            }
+            blueprint.reserve_nonce(region.clone(), entity.clone(), *last_nonce);
+        }
+    }
+
+    for (region, entity, nonce, instance) in instances.into_iter() {
+        if authority.is_some_and(|a| *a != instance.instance_authority) {
+            continue;
+        }
+
+        // keys were inserted above
+        let _ = blueprint.insert_instance(region, entity, nonce, instance.components);
+    }
+
+    // a Region lists the Entities with Instances in it
+    for region in world.regions.iter() {
+        let entities = blueprint
+            .instances
+            .get(region)
+            .map(|e| {
+                e.iter()
+                    .filter(|(_, i)| !i.is_empty())
+                    .map(|(e, _)| e.clone())
+                    .collect()
+            })
+            .unwrap_or_default();
+        blueprint.add_region(region.clone(), entities);
+    }
+
+    // schemas aren't stored onchain, infer them from the
+    // first value of each Component that has a known type
+    for entity in world.entities.iter() {
+        let mut inferred: BTreeMap<&Component, (Option<ComponentType>, &ComponentValue)> =
+            BTreeMap::new();
+        let values = blueprint
+            .instances
+            .values()
+            .filter_map(|e| e.get(entity))
+            .flat_map(|i| i.values())
+            .flat_map(|c| c.iter());
+
+        for (component, value) in values {
+            let (component_type, _) = inferred.entry(component).or_insert((None, value));
+            if component_type.is_none() {
+                *component_type = value.infer_type();
+            }
+        }
+
+        let component_type_tree = inferred
+            .into_iter()
+            .map(|(c, (t, v))| (c.clone(), t.unwrap_or_else(|| untyped_component_type(v))))
+            .collect::<ComponentTypeTree>();
+        blueprint.add_entity(entity.clone(), component_type_tree);
+    }
+
+    blueprint
+}
+
+/// Type of a value whose empty collections hide their
+/// element type, which is assumed to be String
+fn untyped_component_type(value: &ComponentValue) -> ComponentType {
+    match value {
+        ComponentValue::List(v) => ComponentType::List(Box::new(
+            v.first()
+                .map(untyped_component_type)
+                .unwrap_or(ComponentType::String),
+        )),
+        ComponentValue::Map(v) => ComponentType::Map(Box::new(
+            v.values()
+                .next()
+                .map(untyped_component_type)
+                .unwrap_or(ComponentType::String),
+        )),
+        ComponentValue::Struct(v) => ComponentType::Struct(
+            v.iter()
+                .map(|(k, v)| (k.clone(), untyped_component_type(v)))
+                .collect(),
+        ),
+        // only collections can't be inferred
+        v => v.infer_type().unwrap_or(ComponentType::String),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use assert_matches::assert_matches;
+    use borsh::BorshDeserialize;
+    use rush_ecs_parser::{
+        toml::{TomlEmitter, TomlParser},
+        Emitter, Parser,
+    };
+    use rush_ecs_svm::{instruction::RushStoreInstruction, state::Instance};
+    use solana_program_test::*;
+    use solana_sdk::{
+        borsh1,
+        signer::{keypair::Keypair, SeedDerivable},
+    };
+    use std::str::FromStr;
+
+    // Happy path
+    #[test]
+    fn test_solana_migrate() {
+        // prepare test context
+        let program_id = Pubkey::from_str("FXm4HiySCyKv3HrynYKY7yfanyH7dJGMuvxXsbnvtW5c").unwrap();
+        let seed = [
+            192, 45, 79, 47, 38, 198, 135, 27, 191, 116, 8, 103, 96, 204, 251, 131, 110, 7, 179, 0,
+            236, 71, 217, 202, 191, 140, 13, 148, 165, 62, 107, 20, 118, 252, 252, 98, 134, 2, 49,
+            17, 166, 221, 114, 65, 149, 220, 228, 81, 254, 57, 227, 230, 70, 178, 135, 176, 103,
+            235, 188, 54, 173, 91, 232, 57,
+        ];
+
+        let signer = Keypair::from_seed(&seed).unwrap();
+
+        let path_str = "fixtures/blueprint.toml";
+        let loader = Loader::new(TomlParser {});
+        let path = Path::new(path_str);
+        let blueprint = loader.load_blueprint(path).unwrap();
+
+        let (world_pda, world_bump) =
+            WorldPDA::find_pda(&program_id, &blueprint.name, &blueprint.description);
+
+        let rpc_url = String::from("http://127.0.0.1:8899");
+        let client = RpcClient::new(rpc_url.clone());
+        let mut solana = Solana::new(program_id, signer.insecure_clone(), rpc_url, path_str);
+
+        solana.migrate().unwrap();
+
+        let data = client.get_account_data(&world_pda).unwrap();
+        let state = borsh1::try_from_slice_unchecked::<World>(&data).unwrap();
+
+        assert!(state.is_initialized());
+        assert_eq!(state.name, blueprint.name);
+        assert_eq!(state.description, blueprint.description);
+        assert_eq!(
+            state.regions,
+            blueprint.regions.into_keys().collect::<Vec<_>>()
+        );
+        assert_eq!(
+            state.entities,
+            blueprint.entities.into_keys().collect::<Vec<_>>()
+        );
+        assert_eq!(state.world_authority, signer.pubkey());
+        assert_eq!(state.bump, world_bump);
+        assert!(!state.is_launched);
+    }
+
+    // Happy path
+    #[test]
+    fn test_solana_create() {
         // prepare test context
         let program_id = Pubkey::from_str("FXm4HiySCyKv3HrynYKY7yfanyH7dJGMuvxXsbnvtW5c").unwrap();
         let seed = [
@@ -391,9 +1251,30 @@ mod tests {
         let instance_state = borsh1::try_from_slice_unchecked::<Instance>(&data).unwrap();
         let default_components = blueprint.get_default_components(&entity).unwrap();
         assert_eq!(instance_state.components, default_components);
+        // declared in the blueprint
+        assert_eq!(
+            instance_state.components["speed"],
+            ComponentValue::Float(1.5)
+        );
         assert_eq!(instance_state.nonce, expected_nonce);
         assert_eq!(instance_state.instance_authority, signer.pubkey());
         assert_eq!(instance_state.bump, instance_bump);
+
+        // unknown Regions and Entities are errors, not panics
+        let err = solana
+            .create("unknown".to_string(), entity.clone())
+            .unwrap_err();
+        assert_matches!(
+            err.downcast_ref::<CoreError>(),
+            Some(CoreError::RegionNotFound)
+        );
+        let err = solana
+            .create(region.clone(), "unknown".to_string())
+            .unwrap_err();
+        assert_matches!(
+            err.downcast_ref::<CoreError>(),
+            Some(CoreError::EntityNotFound)
+        );
     }
 
     // Happy path
@@ -429,8 +1310,8 @@ mod tests {
 
         let component_value = solana.get(region, entity, 1, component).unwrap();
 
-        let expected_parameter = 0;
-        assert_matches!(component_value, ComponentValue::Integer(expected_parameter));
+        let expected_parameter = 0.0;
+        assert_eq!(component_value, ComponentValue::Float(expected_parameter));
     }
 
     // Happy path
@@ -465,7 +1346,7 @@ mod tests {
         let entity = "player".to_string();
         let component = "x".to_string();
         let nonce = 1;
-        let value = ComponentValue::Integer(143);
+        let value = ComponentValue::Float(143.0);
 
         solana
             .set(
@@ -485,4 +1366,793 @@ mod tests {
         let component_value = instance_state.components.get(&component).unwrap().clone();
         assert_matches!(component_value, value);
     }
+
+    // Happy path
+    #[test]
+    fn test_solana_set_many() {
+        // prepare test context
+        let program_id = Pubkey::from_str("FXm4HiySCyKv3HrynYKY7yfanyH7dJGMuvxXsbnvtW5c").unwrap();
+        let seed = [
+            192, 45, 79, 47, 38, 198, 135, 27, 191, 116, 8, 103, 96, 204, 251, 131, 110, 7, 179, 0,
+            236, 71, 217, 202, 191, 140, 13, 148, 165, 62, 107, 20, 118, 252, 252, 98, 134, 2, 49,
+            17, 166, 221, 114, 65, 149, 220, 228, 81, 254, 57, 227, 230, 70, 178, 135, 176, 103,
+            235, 188, 54, 173, 91, 232, 57,
+        ];
+
+        let signer = Keypair::from_seed(&seed).unwrap();
+
+        let path_str = "fixtures/blueprint.toml";
+        let rpc_url = String::from("http://127.0.0.1:8899");
+        let mut solana = Solana::new(program_id, signer.insecure_clone(), rpc_url, path_str);
+
+        solana.migrate().unwrap();
+
+        let update = |region: &str, component: &str, value: f64| InstanceUpdate {
+            region: region.to_string(),
+            entity: "player".to_string(),
+            nonce: 1,
+            component: component.to_string(),
+            value: ComponentValue::Float(value),
+        };
+
+        solana
+            .set_many(vec![
+                update("farm", "x", 1.0),
+                update("farm", "y", 2.0),
+                update("house", "x", 3.0),
+            ])
+            .unwrap();
+
+        for (region, component, value) in
+            [("farm", "x", 1.0), ("farm", "y", 2.0), ("house", "x", 3.0)]
+        {
+            let stored = solana
+                .get(
+                    region.to_string(),
+                    "player".to_string(),
+                    1,
+                    component.to_string(),
+                )
+                .unwrap();
+            assert_eq!(stored, ComponentValue::Float(value));
+        }
+
+        // nothing is sent if any value is invalid, speed
+        // is at most 10
+        let invalid = update("farm", "speed", 100.0);
+        let err = solana
+            .set_many(vec![update("farm", "x", 5.0), invalid])
+            .unwrap_err();
+        assert_matches!(
+            err.downcast_ref::<CoreError>(),
+            Some(CoreError::ConstraintViolation { .. })
+        );
+        let stored = solana
+            .get("farm".to_string(), "player".to_string(), 1, "x".to_string())
+            .unwrap();
+        assert_eq!(stored, ComponentValue::Float(1.0));
+    }
+
+    #[test]
+    fn test_pack_updates() {
+        let program_id = Pubkey::new_unique();
+        let world = Pubkey::new_unique();
+        let authority = Pubkey::new_unique();
+        let (a, b) = (Pubkey::new_unique(), Pubkey::new_unique());
+
+        // small updates share one instruction and Instance accounts
+        let ixs = pack_updates(
+            &program_id,
+            &world,
+            &authority,
+            vec![
+                (a, "x".to_string(), ComponentValue::Float(1.0)),
+                (b, "x".to_string(), ComponentValue::Float(2.0)),
+                (a, "y".to_string(), ComponentValue::Float(3.0)),
+            ],
+        );
+        assert_eq!(ixs.len(), 1);
+        assert_eq!(ixs[0].accounts.len(), 5);
+        assert_eq!(ixs[0].accounts[3].pubkey, a);
+        assert_eq!(ixs[0].accounts[4].pubkey, b);
+
+        let instruction = RushStoreInstruction::try_from_slice(&ixs[0].data).unwrap();
+        let RushStoreInstruction::BatchUpdate { updates } = instruction else {
+            panic!("Expected a BatchUpdate");
+        };
+        assert_eq!(
+            updates.iter().map(|u| u.instance).collect::<Vec<_>>(),
+            vec![0, 1, 0]
+        );
+
+        // large updates are split across transactions
+        let updates = (0..10)
+            .map(|_| {
+                (
+                    Pubkey::new_unique(),
+                    "name".to_string(),
+                    ComponentValue::String("a".repeat(300)),
+                )
+            })
+            .collect::<Vec<_>>();
+        let ixs = pack_updates(&program_id, &world, &authority, updates);
+        assert!(ixs.len() > 1);
+        assert!(ixs
+            .iter()
+            .all(|ix| fits_in_transaction(std::slice::from_ref(ix), &authority)));
+        let count = ixs.iter().map(|ix| ix.accounts.len() - 3).sum::<usize>();
+        assert_eq!(count, 10);
+    }
+
+    // Happy path
+    #[test]
+    fn test_solana_set_versioned() {
+        // prepare test context
+        let program_id = Pubkey::from_str("FXm4HiySCyKv3HrynYKY7yfanyH7dJGMuvxXsbnvtW5c").unwrap();
+        let seed = [
+            192, 45, 79, 47, 38, 198, 135, 27, 191, 116, 8, 103, 96, 204, 251, 131, 110, 7, 179, 0,
+            236, 71, 217, 202, 191, 140, 13, 148, 165, 62, 107, 20, 118, 252, 252, 98, 134, 2, 49,
+            17, 166, 221, 114, 65, 149, 220, 228, 81, 254, 57, 227, 230, 70, 178, 135, 176, 103,
+            235, 188, 54, 173, 91, 232, 57,
+        ];
+
+        let signer = Keypair::from_seed(&seed).unwrap();
+
+        let path_str = "fixtures/blueprint.toml";
+        let rpc_url = String::from("http://127.0.0.1:8899");
+        let mut solana = Solana::new(program_id, signer.insecure_clone(), rpc_url, path_str);
+
+        solana.migrate().unwrap();
+
+        let region = "farm".to_string();
+        let entity = "player".to_string();
+        let component = "x".to_string();
+        let nonce = 1;
+
+        let version = solana
+            .version(region.clone(), entity.clone(), nonce)
+            .unwrap();
+
+        solana
+            .set_versioned(
+                region.clone(),
+                entity.clone(),
+                nonce,
+                component.clone(),
+                ComponentValue::Float(1.0),
+                version,
+            )
+            .unwrap();
+        assert_eq!(
+            solana
+                .version(region.clone(), entity.clone(), nonce)
+                .unwrap(),
+            version + 1
+        );
+
+        // stale version
+        let err = solana
+            .set_versioned(
+                region,
+                entity,
+                nonce,
+                component,
+                ComponentValue::Float(2.0),
+                version,
+            )
+            .unwrap_err();
+        assert_matches!(
+            err.downcast_ref::<StorageError>(),
+            Some(StorageError::VersionConflict)
+        );
+    }
+
+    // Happy path
+    #[test]
+    fn test_solana_increment() {
+        // prepare test context
+        let program_id = Pubkey::from_str("FXm4HiySCyKv3HrynYKY7yfanyH7dJGMuvxXsbnvtW5c").unwrap();
+        let seed = [
+            192, 45, 79, 47, 38, 198, 135, 27, 191, 116, 8, 103, 96, 204, 251, 131, 110, 7, 179, 0,
+            236, 71, 217, 202, 191, 140, 13, 148, 165, 62, 107, 20, 118, 252, 252, 98, 134, 2, 49,
+            17, 166, 221, 114, 65, 149, 220, 228, 81, 254, 57, 227, 230, 70, 178, 135, 176, 103,
+            235, 188, 54, 173, 91, 232, 57,
+        ];
+
+        let signer = Keypair::from_seed(&seed).unwrap();
+
+        let path_str = "fixtures/blueprint.toml";
+        let rpc_url = String::from("http://127.0.0.1:8899");
+        let mut solana = Solana::new(program_id, signer.insecure_clone(), rpc_url, path_str);
+
+        solana.migrate().unwrap();
+
+        let region = "farm".to_string();
+        let entity = "player".to_string();
+        let component = "x".to_string();
+        let nonce = 1;
+
+        solana
+            .set(
+                region.clone(),
+                entity.clone(),
+                nonce,
+                component.clone(),
+                ComponentValue::Float(1.0),
+            )
+            .unwrap();
+        solana
+            .increment(
+                region.clone(),
+                entity.clone(),
+                nonce,
+                component.clone(),
+                ComponentValue::Float(2.5),
+            )
+            .unwrap();
+        solana
+            .decrement(
+                region.clone(),
+                entity.clone(),
+                nonce,
+                component.clone(),
+                ComponentValue::Float(0.5),
+            )
+            .unwrap();
+
+        let value = solana
+            .get(region.clone(), entity.clone(), nonce, component.clone())
+            .unwrap();
+        assert_eq!(value, ComponentValue::Float(3.0));
+
+        solana
+            .modify(
+                region.clone(),
+                entity.clone(),
+                nonce,
+                component.clone(),
+                ComponentOp::Clamp {
+                    delta: ComponentValue::Float(100.0),
+                    min: ComponentValue::Float(0.0),
+                    max: ComponentValue::Float(10.0),
+                },
+            )
+            .unwrap();
+
+        let value = solana
+            .get(region.clone(), entity.clone(), nonce, component.clone())
+            .unwrap();
+        assert_eq!(value, ComponentValue::Float(10.0));
+
+        // operands must cast to the Component's type
+        let err = solana
+            .increment(
+                region.clone(),
+                entity.clone(),
+                nonce,
+                "name".to_string(),
+                ComponentValue::Integer(1),
+            )
+            .unwrap_err();
+        assert_matches!(
+            err.downcast_ref::<CoreError>(),
+            Some(CoreError::MismatchedDataType)
+        );
+
+        // results must pass the Component's Constraint, speed is 0..=10
+        solana
+            .set(
+                region.clone(),
+                entity.clone(),
+                nonce,
+                "speed".to_string(),
+                ComponentValue::Float(5.0),
+            )
+            .unwrap();
+        let err = solana
+            .increment(
+                region.clone(),
+                entity.clone(),
+                nonce,
+                "speed".to_string(),
+                ComponentValue::Float(10.0),
+            )
+            .unwrap_err();
+        assert_matches!(
+            err.downcast_ref::<CoreError>(),
+            Some(CoreError::ConstraintViolation { .. })
+        );
+        let err = solana
+            .decrement(
+                region.clone(),
+                entity.clone(),
+                nonce,
+                "speed".to_string(),
+                ComponentValue::Float(6.0),
+            )
+            .unwrap_err();
+        assert_matches!(
+            err.downcast_ref::<CoreError>(),
+            Some(CoreError::ConstraintViolation { .. })
+        );
+
+        let value = solana
+            .get(region, entity, nonce, "speed".to_string())
+            .unwrap();
+        assert_eq!(value, ComponentValue::Float(5.0));
+    }
+
+    // Happy path
+    #[test]
+    fn test_solana_set_list() {
+        // prepare test context
+        let program_id = Pubkey::from_str("FXm4HiySCyKv3HrynYKY7yfanyH7dJGMuvxXsbnvtW5c").unwrap();
+        let seed = [
+            192, 45, 79, 47, 38, 198, 135, 27, 191, 116, 8, 103, 96, 204, 251, 131, 110, 7, 179, 0,
+            236, 71, 217, 202, 191, 140, 13, 148, 165, 62, 107, 20, 118, 252, 252, 98, 134, 2, 49,
+            17, 166, 221, 114, 65, 149, 220, 228, 81, 254, 57, 227, 230, 70, 178, 135, 176, 103,
+            235, 188, 54, 173, 91, 232, 57,
+        ];
+
+        let signer = Keypair::from_seed(&seed).unwrap();
+
+        let path_str = "fixtures/blueprint.toml";
+        let rpc_url = String::from("http://127.0.0.1:8899");
+        let mut solana = Solana::new(program_id, signer.insecure_clone(), rpc_url, path_str);
+
+        solana.migrate().unwrap();
+
+        let region = "farm".to_string();
+        let entity = "player".to_string();
+        let component = "inventory".to_string();
+        let nonce = 1;
+        let value = ComponentValue::List(vec![ComponentValue::String("sword".to_string())]);
+
+        solana
+            .set(
+                region.clone(),
+                entity.clone(),
+                nonce,
+                component.clone(),
+                value.clone(),
+            )
+            .unwrap();
+
+        let component_value = solana.get(region, entity, nonce, component).unwrap();
+        assert_eq!(component_value, value);
+    }
+
+    // Happy path
+    #[test]
+    fn test_solana_sync() {
+        // prepare test context
+        let program_id = Pubkey::from_str("FXm4HiySCyKv3HrynYKY7yfanyH7dJGMuvxXsbnvtW5c").unwrap();
+        let seed = [
+            192, 45, 79, 47, 38, 198, 135, 27, 191, 116, 8, 103, 96, 204, 251, 131, 110, 7, 179, 0,
+            236, 71, 217, 202, 191, 140, 13, 148, 165, 62, 107, 20, 118, 252, 252, 98, 134, 2, 49,
+            17, 166, 221, 114, 65, 149, 220, 228, 81, 254, 57, 227, 230, 70, 178, 135, 176, 103,
+            235, 188, 54, 173, 91, 232, 57,
+        ];
+
+        let signer = Keypair::from_seed(&seed).unwrap();
+
+        let path_str = "fixtures/blueprint.toml";
+        let rpc_url = String::from("http://127.0.0.1:8899");
+        let mut solana = Solana::new(program_id, signer.insecure_clone(), rpc_url, path_str);
+
+        solana.sync().unwrap();
+
+        // idempotent
+        assert!(solana.plan().unwrap().is_empty());
+        assert!(solana.sync().unwrap().is_empty());
+
+        // edit the blueprint
+        let region = "farm".to_string();
+        let entity = "player".to_string();
+        let component = "x".to_string();
+        solana
+            .blueprint
+            .set_component_value(
+                region.clone(),
+                entity.clone(),
+                1,
+                component.clone(),
+                ComponentValue::Float(3.0),
+            )
+            .unwrap();
+        let nonce = solana
+            .blueprint
+            .add_default_instance(region.clone(), entity.clone())
+            .unwrap();
+
+        // plan only previews
+        let plan = solana.plan().unwrap();
+        assert_eq!(plan.instance_changes.len(), 2);
+        assert_eq!(
+            solana
+                .get(region.clone(), entity.clone(), 1, component.clone())
+                .unwrap(),
+            ComponentValue::Float(0.0)
+        );
+
+        assert_eq!(solana.sync().unwrap(), plan);
+        assert!(solana.plan().unwrap().is_empty());
+        assert_eq!(
+            solana
+                .get(region.clone(), entity.clone(), 1, component.clone())
+                .unwrap(),
+            ComponentValue::Float(3.0)
+        );
+
+        // despawn instances removed from the blueprint
+        solana
+            .blueprint
+            .remove_instance(region.clone(), entity.clone(), nonce)
+            .unwrap();
+        let diff = solana.sync().unwrap();
+        assert!(matches!(
+            diff.instance_changes[..],
+            [InstanceChange::Removed { nonce: n, .. }] if n == nonce
+        ));
+        assert!(solana.get(region, entity, nonce, component).is_err());
+    }
+
+    #[test]
+    fn test_onchain_blueprint() {
+        let world_pda = Pubkey::new_unique();
+        let (owner, other) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let (farm, apple) = ("farm".to_string(), "apple".to_string());
+
+        let mut world = World::new(
+            "World".to_string(),
+            "Description".to_string(),
+            owner,
+            vec![farm.clone()],
+            vec![apple.clone()],
+            255,
+            true,
+        );
+        world
+            .instances
+            .get_mut(&farm)
+            .unwrap()
+            .insert(apple.clone(), 3);
+
+        // #1 was despawned, #3 belongs to another authority
+        let instance = |nonce: u64, authority: Pubkey| {
+            let components = BTreeMap::from([("x".to_string(), ComponentValue::U64(nonce))]);
+            let instance = Instance::new(components, nonce, authority, 255, world_pda);
+            (farm.clone(), apple.clone(), nonce, instance)
+        };
+        let instances = vec![instance(2, owner), instance(3, other)];
+
+        let owned = onchain_blueprint(&world, instances.clone(), Some(&owner));
+        assert_eq!(
+            owned.instances[&farm][&apple].keys().collect::<Vec<_>>(),
+            vec![&2]
+        );
+        assert_eq!(owned.nonces[&farm][&apple], 3);
+
+        let pulled = onchain_blueprint(&world, instances, None);
+        assert_eq!(
+            pulled.instances[&farm][&apple].keys().collect::<Vec<_>>(),
+            vec![&2, &3]
+        );
+
+        // syncing a pulled World leaves other authorities' Instances alone
+        let mut solana = Solana {
+            blueprint: pulled,
+            program_id: Pubkey::new_unique(),
+            signer: Keypair::new(),
+            rpc_url: String::new(),
+        };
+        assert!(solana.plan_sync(Some((&world, &owned))).is_empty());
+
+        solana
+            .blueprint
+            .remove_instance(farm.clone(), apple.clone(), 3)
+            .unwrap();
+        assert!(solana.plan_sync(Some((&world, &owned))).is_empty());
+
+        // #3 keeps the Region live, nothing in it is removed
+        world
+            .live
+            .entry(farm.clone())
+            .or_default()
+            .insert(apple.clone(), 2);
+        solana.blueprint.regions.remove(&farm);
+        solana.blueprint.instances.remove(&farm);
+        assert!(solana.plan_sync(Some((&world, &owned))).is_empty());
+
+        // #3 despawned, the signer's #2 goes with the Region
+        world.live.get_mut(&farm).unwrap().insert(apple.clone(), 1);
+        let diff = solana.plan_sync(Some((&world, &owned)));
+        assert_eq!(diff.removed_regions, vec![farm.clone()]);
+        assert!(matches!(
+            diff.instance_changes[..],
+            [InstanceChange::Removed { nonce: 2, .. }]
+        ));
+    }
+
+    #[test]
+    fn test_pull_round_trip() {
+        let world_pda = Pubkey::new_unique();
+        let authority = Pubkey::new_unique();
+        let (farm, house) = ("farm".to_string(), "house".to_string());
+        let (apple, player) = ("apple".to_string(), "player".to_string());
+
+        let mut world = World::new(
+            "World".to_string(),
+            "Description".to_string(),
+            authority,
+            vec![farm.clone(), house.clone()],
+            vec![apple.clone(), player.clone()],
+            255,
+            true,
+        );
+        // apples #1 and #4 and player #1 were despawned
+        world
+            .instances
+            .get_mut(&farm)
+            .unwrap()
+            .insert(apple.clone(), 4);
+        world
+            .instances
+            .get_mut(&house)
+            .unwrap()
+            .insert(player.clone(), 1);
+        // removed Regions keep their last nonce
+        world.remove_region(&house, false).unwrap();
+
+        let apple_instance = |nonce: u64| {
+            let components = BTreeMap::from([
+                ("ripe".to_string(), ComponentValue::Boolean(nonce == 2)),
+                ("weight".to_string(), ComponentValue::U64(nonce * 10)),
+            ]);
+            let instance = Instance::new(components, nonce, authority, 255, world_pda);
+            (farm.clone(), apple.clone(), nonce, instance)
+        };
+        let onchain = onchain_blueprint(&world, vec![apple_instance(2), apple_instance(3)], None);
+
+        let emitted = TomlEmitter::default().emit(&onchain).unwrap();
+        let blueprint = TomlParser::default().parse_string(emitted).unwrap();
+
+        // onchain nonces and despawned nonces are kept
+        assert_eq!(blueprint.instances, onchain.instances);
+        assert_eq!(blueprint.nonces[&farm][&apple], 4);
+        assert_eq!(blueprint.nonces[&house][&player], 1);
+
+        let solana = Solana {
+            blueprint,
+            program_id: Pubkey::new_unique(),
+            signer: Keypair::new(),
+            rpc_url: String::new(),
+        };
+        assert!(solana.plan_sync(Some((&world, &onchain))).is_empty());
+    }
+
+    // Happy path
+    #[test]
+    fn test_solana_launch() {
+        // prepare test context
+        let program_id = Pubkey::from_str("FXm4HiySCyKv3HrynYKY7yfanyH7dJGMuvxXsbnvtW5c").unwrap();
+        let seed = [
+            192, 45, 79, 47, 38, 198, 135, 27, 191, 116, 8, 103, 96, 204, 251, 131, 110, 7, 179, 0,
+            236, 71, 217, 202, 191, 140, 13, 148, 165, 62, 107, 20, 118, 252, 252, 98, 134, 2, 49,
+            17, 166, 221, 114, 65, 149, 220, 228, 81, 254, 57, 227, 230, 70, 178, 135, 176, 103,
+            235, 188, 54, 173, 91, 232, 57,
+        ];
+
+        let signer = Keypair::from_seed(&seed).unwrap();
+
+        let path_str = "fixtures/blueprint.toml";
+        let rpc_url = String::from("http://127.0.0.1:8899");
+        let mut solana = Solana::new(program_id, signer.insecure_clone(), rpc_url, path_str);
+
+        solana.migrate().unwrap();
+
+        let client = RpcClient::new(solana.rpc_url.clone());
+        let (world_pda, _) = solana.world_pda();
+        let is_launched = || {
+            let data = client.get_account_data(&world_pda).unwrap();
+            borsh1::try_from_slice_unchecked::<World>(&data)
+                .unwrap()
+                .is_launched
+        };
+
+        solana.launch().unwrap();
+        assert!(is_launched());
+        // launching again does nothing
+        solana.launch().unwrap();
+        assert!(is_launched());
+
+        solana.pause().unwrap();
+        assert!(!is_launched());
+    }
+
+    // Happy path
+    #[test]
+    fn test_solana_pull() {
+        // prepare test context
+        let program_id = Pubkey::from_str("FXm4HiySCyKv3HrynYKY7yfanyH7dJGMuvxXsbnvtW5c").unwrap();
+        let seed = [
+            192, 45, 79, 47, 38, 198, 135, 27, 191, 116, 8, 103, 96, 204, 251, 131, 110, 7, 179, 0,
+            236, 71, 217, 202, 191, 140, 13, 148, 165, 62, 107, 20, 118, 252, 252, 98, 134, 2, 49,
+            17, 166, 221, 114, 65, 149, 220, 228, 81, 254, 57, 227, 230, 70, 178, 135, 176, 103,
+            235, 188, 54, 173, 91, 232, 57,
+        ];
+
+        let signer = Keypair::from_seed(&seed).unwrap();
+
+        let path_str = "fixtures/blueprint.toml";
+        let rpc_url = String::from("http://127.0.0.1:8899");
+        let mut solana = Solana::new(
+            program_id,
+            signer.insecure_clone(),
+            rpc_url.clone(),
+            path_str,
+        );
+
+        solana.migrate().unwrap();
+
+        let (world_pda, _) = WorldPDA::find_pda(
+            &program_id,
+            &solana.blueprint.name,
+            &solana.blueprint.description,
+        );
+        let pulled = pull(rpc_url, &program_id, &world_pda).unwrap();
+
+        assert_eq!(pulled.name, solana.blueprint.name);
+        assert_eq!(pulled.regions, solana.blueprint.regions);
+        assert_eq!(pulled.instances, solana.blueprint.instances);
+        // inferred from the instances, empty lists as [String]
+        assert_eq!(pulled.entities, solana.blueprint.entities);
+        assert!(pulled.diff(&solana.blueprint).is_empty());
+
+        let err = pull(
+            String::from("http://127.0.0.1:8899"),
+            &program_id,
+            &Pubkey::new_unique(),
+        )
+        .unwrap_err();
+        assert_matches!(
+            err.downcast_ref::<StorageError>(),
+            Some(StorageError::WorldNotFound(_))
+        );
+    }
+
+    // Happy path
+    #[test]
+    fn test_solana_query() {
+        // prepare test context
+        let program_id = Pubkey::from_str("FXm4HiySCyKv3HrynYKY7yfanyH7dJGMuvxXsbnvtW5c").unwrap();
+        let seed = [
+            192, 45, 79, 47, 38, 198, 135, 27, 191, 116, 8, 103, 96, 204, 251, 131, 110, 7, 179, 0,
+            236, 71, 217, 202, 191, 140, 13, 148, 165, 62, 107, 20, 118, 252, 252, 98, 134, 2, 49,
+            17, 166, 221, 114, 65, 149, 220, 228, 81, 254, 57, 227, 230, 70, 178, 135, 176, 103,
+            235, 188, 54, 173, 91, 232, 57,
+        ];
+
+        let signer = Keypair::from_seed(&seed).unwrap();
+
+        let path_str = "fixtures/blueprint.toml";
+        let rpc_url = String::from("http://127.0.0.1:8899");
+        let mut solana = Solana::new(program_id, signer.insecure_clone(), rpc_url, path_str);
+
+        solana.migrate().unwrap();
+
+        let region = "farm".to_string();
+        let entity = "player".to_string();
+        solana
+            .set(
+                region.clone(),
+                entity.clone(),
+                1,
+                "x".to_string(),
+                ComponentValue::Float(5.0),
+            )
+            .unwrap();
+
+        let query = Query::new()
+            .region(region.clone())
+            .entity(entity.clone())
+            .ge("x", 5.0)
+            .select(["name", "x"]);
+        let rows = solana.query(&query).unwrap();
+
+        // answers the same as the Blueprint would
+        solana
+            .blueprint
+            .set_component_value(
+                region,
+                entity,
+                1,
+                "x".to_string(),
+                ComponentValue::Float(5.0),
+            )
+            .unwrap();
+        assert_eq!(rows, solana.blueprint.query(&query));
+        assert_eq!(rows.len(), 1);
+        assert_eq!(rows[0].nonce, 1);
+        assert_eq!(rows[0].components.len(), 2);
+    }
+
+    // Unhappy path
+    #[test]
+    fn test_solana_set_rejects_invalid_value() {
+        // prepare test context
+        let program_id = Pubkey::from_str("FXm4HiySCyKv3HrynYKY7yfanyH7dJGMuvxXsbnvtW5c").unwrap();
+        let seed = [
+            192, 45, 79, 47, 38, 198, 135, 27, 191, 116, 8, 103, 96, 204, 251, 131, 110, 7, 179, 0,
+            236, 71, 217, 202, 191, 140, 13, 148, 165, 62, 107, 20, 118, 252, 252, 98, 134, 2, 49,
+            17, 166, 221, 114, 65, 149, 220, 228, 81, 254, 57, 227, 230, 70, 178, 135, 176, 103,
+            235, 188, 54, 173, 91, 232, 57,
+        ];
+
+        let signer = Keypair::from_seed(&seed).unwrap();
+
+        let path_str = "fixtures/blueprint.toml";
+        let rpc_url = String::from("http://127.0.0.1:8899");
+        let mut solana = Solana::new(program_id, signer.insecure_clone(), rpc_url, path_str);
+
+        let region = "farm".to_string();
+        let entity = "player".to_string();
+
+        // declared with max = 10 in the blueprint
+        let err = solana
+            .set(
+                region.clone(),
+                entity.clone(),
+                1,
+                "speed".to_string(),
+                ComponentValue::Float(11.0),
+            )
+            .unwrap_err();
+        assert_matches!(
+            err.downcast_ref::<CoreError>(),
+            Some(CoreError::ConstraintViolation { .. })
+        );
+
+        let err = solana
+            .set(
+                region,
+                entity,
+                1,
+                "x".to_string(),
+                ComponentValue::String("left".to_string()),
+            )
+            .unwrap_err();
+        assert_matches!(
+            err.downcast_ref::<CoreError>(),
+            Some(CoreError::MismatchedDataType)
+        );
+    }
+
+    #[test]
+    fn test_decode_client_error() {
+        let code = RushStoreError::ComponentNotFound as u32;
+        let err = decode_client_error(ClientError::from(TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(code),
+        )));
+        assert_matches!(
+            err.downcast_ref::<StorageError>(),
+            Some(StorageError::Program(RushStoreError::ComponentNotFound))
+        );
+
+        // conflicts have their own error to retry on
+        let code = RushStoreError::VersionMismatch as u32;
+        let err = decode_client_error(ClientError::from(TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(code),
+        )));
+        assert_matches!(
+            err.downcast_ref::<StorageError>(),
+            Some(StorageError::VersionConflict)
+        );
+
+        // not a rush-store error
+        let err = decode_client_error(ClientError::from(TransactionError::AccountNotFound));
+        assert!(err.downcast_ref::<StorageError>().is_none());
+    }
 }
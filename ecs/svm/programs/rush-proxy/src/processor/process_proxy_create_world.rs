@@ -1,27 +1,17 @@
 use crate::instruction::accounts::{Context, ProxyCreateWorldAccounts};
-use crate::store_cpi;
-use borsh::BorshSerialize;
 use rush_ecs_core::blueprint::{Entity, Region};
-use rush_ecs_svm::{
-    instruction::RushStoreInstruction,
-    pda::{UserPDA, WorldPDA},
-    state::World,
-};
+use rush_ecs_svm::{instruction::RushStoreInstruction, pda::UserPDA};
 use solana_program::{
-    borsh1,
     entrypoint::ProgramResult,
     instruction::{AccountMeta, Instruction},
     msg,
     program::invoke_signed,
     pubkey::Pubkey,
-    rent::Rent,
-    system_instruction,
     system_program::ID as SYSTEM_PROGRAM_ID,
-    sysvar::Sysvar,
 };
 ///
 /// - Proxies the CreateWorld instruction to create
-/// custom behavior on top of main storage logic
+///   custom behavior on top of main storage logic
 ///
 /// Accounts
 /// 0. `[SIGNER]`       World Authority
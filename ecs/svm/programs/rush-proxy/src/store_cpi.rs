@@ -1,7 +1,10 @@
 //! Similar to client in rush-svm, but used solana-program
 //! instead of solana-sdk
-use rush_ecs_core::blueprint::{Component, ComponentValue, Entity, Region};
-use rush_ecs_svm::instruction::RushStoreInstruction;
+use rush_ecs_core::{
+    blueprint::{Component, ComponentValue, Entity, Region},
+    numeric::ComponentOp,
+};
+use rush_ecs_svm::instruction::{ComponentUpdate, RushStoreInstruction};
 use solana_program::{
     instruction::{AccountMeta, Instruction},
     pubkey::Pubkey,
@@ -39,14 +42,24 @@ pub fn ix_create_world(
     )
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn ix_update_world(
     program_id: &Pubkey,
-    regions: Vec<Region>,
-    entities: Vec<Entity>,
+    add_regions: Vec<Region>,
+    remove_regions: Vec<Region>,
+    add_entities: Vec<Entity>,
+    remove_entities: Vec<Entity>,
+    force: bool,
     world: &Pubkey,
     world_authority: &Pubkey,
 ) -> Instruction {
-    let instruction = RushStoreInstruction::UpdateWorld { regions, entities };
+    let instruction = RushStoreInstruction::UpdateWorld {
+        add_regions,
+        remove_regions,
+        add_entities,
+        remove_entities,
+        force,
+    };
 
     Instruction::new_with_borsh(
         *program_id,
@@ -114,6 +127,7 @@ pub fn ix_update_entity(
     value: ComponentValue,
     instance: &Pubkey,
     instance_authority: &Pubkey,
+    world: &Pubkey,
 ) -> Instruction {
     let instruction = RushStoreInstruction::UpdateEntity { component, value };
 
@@ -123,16 +137,48 @@ pub fn ix_update_entity(
         vec![
             AccountMeta::new(*instance_authority, true),
             AccountMeta::new(*instance, false),
+            AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+            AccountMeta::new_readonly(*world, false),
+        ],
+    )
+}
+
+pub fn ix_update_entity_versioned(
+    program_id: &Pubkey,
+    component: Component,
+    value: ComponentValue,
+    expected_version: u64,
+    instance: &Pubkey,
+    instance_authority: &Pubkey,
+    world: &Pubkey,
+) -> Instruction {
+    let instruction = RushStoreInstruction::UpdateEntityVersioned {
+        component,
+        value,
+        expected_version,
+    };
+
+    Instruction::new_with_borsh(
+        *program_id,
+        &instruction,
+        vec![
+            AccountMeta::new(*instance_authority, true),
+            AccountMeta::new(*instance, false),
+            AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+            AccountMeta::new_readonly(*world, false),
         ],
     )
 }
 
 pub fn ix_despawn_entity(
     program_id: &Pubkey,
+    region: Region,
+    entity: Entity,
     instance: &Pubkey,
     instance_authority: &Pubkey,
+    world: &Pubkey,
 ) -> Instruction {
-    let instruction = RushStoreInstruction::DeleteWorld;
+    let instruction = RushStoreInstruction::DespawnEntity { region, entity };
 
     Instruction::new_with_borsh(
         *program_id,
@@ -140,6 +186,82 @@ pub fn ix_despawn_entity(
         vec![
             AccountMeta::new(*instance_authority, true),
             AccountMeta::new(*instance, false),
+            AccountMeta::new(*world, false),
         ],
     )
 }
+
+pub fn ix_launch_world(
+    program_id: &Pubkey,
+    world: &Pubkey,
+    world_authority: &Pubkey,
+) -> Instruction {
+    let instruction = RushStoreInstruction::LaunchWorld;
+
+    Instruction::new_with_borsh(
+        *program_id,
+        &instruction,
+        vec![
+            AccountMeta::new_readonly(*world_authority, true),
+            AccountMeta::new(*world, false),
+        ],
+    )
+}
+
+pub fn ix_pause_world(
+    program_id: &Pubkey,
+    world: &Pubkey,
+    world_authority: &Pubkey,
+) -> Instruction {
+    let instruction = RushStoreInstruction::PauseWorld;
+
+    Instruction::new_with_borsh(
+        *program_id,
+        &instruction,
+        vec![
+            AccountMeta::new_readonly(*world_authority, true),
+            AccountMeta::new(*world, false),
+        ],
+    )
+}
+
+pub fn ix_modify_component(
+    program_id: &Pubkey,
+    component: Component,
+    op: ComponentOp,
+    instance: &Pubkey,
+    instance_authority: &Pubkey,
+    world: &Pubkey,
+) -> Instruction {
+    let instruction = RushStoreInstruction::ModifyComponent { component, op };
+
+    Instruction::new_with_borsh(
+        *program_id,
+        &instruction,
+        vec![
+            AccountMeta::new_readonly(*instance_authority, true),
+            AccountMeta::new(*instance, false),
+            AccountMeta::new_readonly(*world, false),
+        ],
+    )
+}
+
+/// `updates` index into `instances`, passed as remaining accounts
+pub fn ix_batch_update(
+    program_id: &Pubkey,
+    updates: Vec<ComponentUpdate>,
+    instances: &[Pubkey],
+    instance_authority: &Pubkey,
+    world: &Pubkey,
+) -> Instruction {
+    let instruction = RushStoreInstruction::BatchUpdate { updates };
+
+    let mut accounts = vec![
+        AccountMeta::new(*instance_authority, true),
+        AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+        AccountMeta::new_readonly(*world, false),
+    ];
+    accounts.extend(instances.iter().map(|i| AccountMeta::new(*i, false)));
+
+    Instruction::new_with_borsh(*program_id, &instruction, accounts)
+}
@@ -0,0 +1,175 @@
+use super::update_entity::{is_same_type, resize_instance};
+use borsh::BorshSerialize;
+use rush_ecs_svm::instruction::accounts::{BatchUpdateAccounts, Context};
+use rush_ecs_svm::{
+    error::RushStoreError,
+    instruction::ComponentUpdate,
+    pda::WorldPDA,
+    require,
+    state::{Instance, World},
+};
+use solana_program::{
+    borsh1, clock::Clock, entrypoint::ProgramResult, program_error::ProgramError, pubkey::Pubkey,
+    system_program, sysvar::Sysvar,
+};
+
+/// Batch Update
+///
+/// - Sets Component values across the Instances passed as
+///   remaining accounts, all or nothing
+/// - Each Instance is read, updated and resized once, and
+///   its version bumped once
+///
+/// Accounts
+/// 0. `[WRITE, SIGNER]` Instance Authority
+/// 1. `[]`             System Program
+/// 2. `[]`             World PDA
+/// 3. `[WRITE]`        Instance PDAs, indexed by the updates
+///
+/// Instruction Data
+/// - updates: Vec<ComponentUpdate>,
+///
+/// Data Validations
+/// - Instance Authority signed and is the authority of every Instance
+/// - Instances are owned by this program, initialized and in the World
+/// - System Program is the System Program
+/// - World PDA is derived from its stored seeds
+/// - World is launched, unless Instance Authority is World Authority
+/// - Every update points to a remaining account
+/// - Components exist and the values are of their types
+///
+pub fn process_batch_update<'a>(
+    program_id: &Pubkey,
+    ctx: Context<'a, BatchUpdateAccounts<'a>>,
+    updates: Vec<ComponentUpdate>,
+) -> ProgramResult {
+    require!(
+        ctx.accounts.instance_authority.is_signer,
+        RushStoreError::MissingSignature,
+        "instance_authority"
+    );
+    require!(
+        system_program::check_id(ctx.accounts.system_program.key),
+        RushStoreError::InvalidSystemProgram,
+        "system_program"
+    );
+    require!(
+        ctx.accounts.world.owner == program_id,
+        RushStoreError::InvalidAccountOwner,
+        "world"
+    );
+
+    let world_data = ctx.accounts.world.try_borrow_data()?;
+    let world = borsh1::try_from_slice_unchecked::<World>(&world_data)?;
+    drop(world_data);
+    // World must be initialized
+    require!(
+        world.is_initialized(),
+        RushStoreError::WorldNotFound,
+        "world"
+    );
+
+    let world_pda = Pubkey::create_program_address(
+        &[
+            WorldPDA::TAG.as_bytes(),
+            world.name.as_bytes(),
+            world.description.as_bytes(),
+            &[world.bump],
+        ],
+        program_id,
+    )
+    .map_err(|_| RushStoreError::InvalidWorldPDA)?;
+    require!(
+        world_pda == *ctx.accounts.world.key,
+        RushStoreError::InvalidWorldPDA,
+        "world"
+    );
+    // only World Authority can update before launch and while paused
+    require!(
+        world.is_open_to(ctx.accounts.instance_authority.key),
+        RushStoreError::WorldNotLaunched,
+        "world"
+    );
+
+    require!(
+        updates
+            .iter()
+            .all(|u| (u.instance as usize) < ctx.remaining_accounts.len()),
+        ProgramError::NotEnoughAccountKeys,
+        "updates"
+    );
+
+    let slot = Clock::get()?.slot;
+
+    for (index, instance_account) in ctx.remaining_accounts.iter().enumerate() {
+        let mut updates = updates
+            .iter()
+            .filter(|u| u.instance as usize == index)
+            .peekable();
+        // unused accounts are left alone
+        if updates.peek().is_none() {
+            continue;
+        }
+
+        require!(
+            instance_account.owner == program_id,
+            RushStoreError::InvalidAccountOwner,
+            "instance {}",
+            index
+        );
+
+        let instance_data = instance_account.try_borrow_data()?;
+
+        // need to use Borsh version 1 for dynamic data
+        // else, de/serialization will fail with Account Unknown Error at runtime
+        let mut instance = borsh1::try_from_slice_unchecked::<Instance>(&instance_data)?;
+        // Relinquish borrow for realloc
+        drop(instance_data);
+        // despawned Instances are zeroed
+        require!(
+            instance.is_initialized(),
+            RushStoreError::InstanceNotFound,
+            "instance {}",
+            index
+        );
+        require!(
+            instance.instance_authority == *ctx.accounts.instance_authority.key,
+            RushStoreError::InvalidInstanceAuthority,
+            "instance_authority"
+        );
+        require!(
+            instance.world == *ctx.accounts.world.key,
+            RushStoreError::InstanceNotInWorld,
+            "instance {}",
+            index
+        );
+
+        for update in updates {
+            let component_mut = instance
+                .components
+                .get_mut(&update.component)
+                .ok_or(RushStoreError::ComponentNotFound)?;
+            // Component keeps the type it was spawned with
+            require!(
+                is_same_type(component_mut, &update.value),
+                RushStoreError::TypeMismatch,
+                "value"
+            );
+            *component_mut = update.value.clone();
+        }
+        instance.touch(slot);
+
+        resize_instance(
+            instance_account,
+            ctx.accounts.instance_authority,
+            ctx.accounts.system_program,
+            &instance,
+        )?;
+
+        // store new Instance state
+        let mut instance_data = instance_account.try_borrow_mut_data()?;
+        instance.serialize(&mut &mut instance_data[..])?;
+    }
+
+    Ok(())
+}
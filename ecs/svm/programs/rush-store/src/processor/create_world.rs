@@ -1,10 +1,10 @@
 use borsh::BorshSerialize;
 use rush_ecs_core::blueprint::{Entity, Region};
 use rush_ecs_svm::instruction::accounts::{Context, CreateWorldAccounts};
-use rush_ecs_svm::{pda::WorldPDA, state::World};
+use rush_ecs_svm::{error::RushStoreError, pda::WorldPDA, require, state::World};
 use solana_program::{
     borsh1, entrypoint::ProgramResult, program::invoke_signed, pubkey::Pubkey, rent::Rent,
-    system_instruction, sysvar::Sysvar,
+    system_instruction, system_program, sysvar::Sysvar,
 };
 
 /// Create World
@@ -13,9 +13,10 @@ use solana_program::{
 /// - Stores initial World state in newly created account
 ///
 /// Accounts
-/// 0. `[SIGNER]`       World Authority
-/// 1. `[WRITE]`        World PDA
-/// 2. `[]`             System Program
+/// 0. `[]`             World Authority
+/// 1. `[WRITE, SIGNER]` Payer
+/// 2. `[WRITE]`        World PDA
+/// 3. `[]`             System Program
 ///
 /// Instruction Data
 /// - name: String
@@ -25,7 +26,9 @@ use solana_program::{
 /// - bump: u8
 ///
 /// Data Validations
-/// -
+/// - Payer signed
+/// - World PDA is derived from name, description and bump
+/// - System Program is the System Program
 ///
 pub fn process_create_world(
     program_id: &Pubkey,
@@ -36,6 +39,35 @@ pub fn process_create_world(
     entities: Vec<Entity>,
     bump: u8,
 ) -> ProgramResult {
+    require!(
+        ctx.accounts.payer.is_signer,
+        RushStoreError::MissingSignature,
+        "payer"
+    );
+
+    // World must be created at the PDA of its seeds
+    let world_pda = Pubkey::create_program_address(
+        &[
+            WorldPDA::TAG.as_bytes(),
+            name.as_bytes(),
+            description.as_bytes(),
+            &[bump],
+        ],
+        program_id,
+    )
+    .map_err(|_| RushStoreError::InvalidWorldPDA)?;
+    require!(
+        world_pda == *ctx.accounts.world.key,
+        RushStoreError::InvalidWorldPDA,
+        "world"
+    );
+
+    require!(
+        system_program::check_id(ctx.accounts.system_program.key),
+        RushStoreError::InvalidSystemProgram,
+        "system_program"
+    );
+
     let new_world_state = World::new(
         name.clone(),
         description.clone(),
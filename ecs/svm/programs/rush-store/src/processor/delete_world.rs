@@ -1,10 +1,11 @@
 use rush_ecs_svm::instruction::accounts::{Context, DeleteWorldAccounts};
-use solana_program::{entrypoint::ProgramResult, pubkey::Pubkey};
+use rush_ecs_svm::{error::RushStoreError, pda::WorldPDA, require, state::World};
+use solana_program::{borsh1, entrypoint::ProgramResult, pubkey::Pubkey};
 
 /// Delete World
 ///
 /// - Transfers all lamports from World State PDA
-/// to World Authority (Signer) for closing
+///   to World Authority (Signer) for closing
 /// - Fills data with 0s for closing
 ///
 /// Accounts
@@ -15,12 +16,58 @@ use solana_program::{entrypoint::ProgramResult, pubkey::Pubkey};
 /// - (None)
 ///
 /// Data Validations
-/// -
+/// - World Authority signed and is the World's authority
+/// - World is owned by this program and initialized
+/// - World PDA is derived from its stored seeds
 ///
 pub fn process_delete_world(
-    _program_id: &Pubkey,
+    program_id: &Pubkey,
     ctx: Context<DeleteWorldAccounts>,
 ) -> ProgramResult {
+    require!(
+        ctx.accounts.world_authority.is_signer,
+        RushStoreError::MissingSignature,
+        "world_authority"
+    );
+    require!(
+        ctx.accounts.world.owner == program_id,
+        RushStoreError::InvalidAccountOwner,
+        "world"
+    );
+
+    // need to use Borsh version 1 for dynamic data
+    // else, de/serialization will fail with Account Unknown Error at runtime
+    let world_data = ctx.accounts.world.try_borrow_data()?;
+    let world = borsh1::try_from_slice_unchecked::<World>(&world_data)?;
+    drop(world_data);
+    // World must not be deleted already
+    require!(
+        world.is_initialized(),
+        RushStoreError::WorldNotFound,
+        "world"
+    );
+
+    let world_pda = Pubkey::create_program_address(
+        &[
+            WorldPDA::TAG.as_bytes(),
+            world.name.as_bytes(),
+            world.description.as_bytes(),
+            &[world.bump],
+        ],
+        program_id,
+    )
+    .map_err(|_| RushStoreError::InvalidWorldPDA)?;
+    require!(
+        world_pda == *ctx.accounts.world.key,
+        RushStoreError::InvalidWorldPDA,
+        "world"
+    );
+    require!(
+        world.world_authority == *ctx.accounts.world_authority.key,
+        RushStoreError::InvalidWorldAuthority,
+        "world_authority"
+    );
+
     // World State PDA
     let world_account_lamports = ctx.accounts.world.lamports();
 
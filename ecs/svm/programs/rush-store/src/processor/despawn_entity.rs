@@ -1,39 +1,173 @@
+use borsh::BorshSerialize;
+use rush_ecs_core::blueprint::{Entity, Region};
 use rush_ecs_svm::instruction::accounts::{Context, DespawnEntityAccounts};
-use solana_program::{entrypoint::ProgramResult, pubkey::Pubkey};
+use rush_ecs_svm::{
+    error::RushStoreError,
+    pda::{InstancePDA, WorldPDA},
+    require,
+    state::{Instance, World},
+};
+use solana_program::{
+    borsh1, entrypoint::ProgramResult, program_error::ProgramError, pubkey::Pubkey, rent::Rent,
+    sysvar::Sysvar,
+};
 
 /// Despawn Entity
 ///
+/// - Records the despawn in the World's Instance bookkeeping,
+///   the nonce stays spent so it's never reused
 /// - Transfers all lamports from Instance State PDA
-/// to Instance Authority (Signer) for closing
+///   to Instance Authority (Signer) for closing
 /// - Fills data with 0s for closing
 ///
 /// Accounts
-/// 0. `[SIGNER]`       Instance Authority
+/// 0. `[WRITE, SIGNER]` Instance Authority
 /// 1. `[WRITE]`        Instance PDA
+/// 2. `[WRITE]`        World PDA
 ///
 /// Instruction Data
-/// - (None)
+/// - region: Region,
+/// - entity: Entity,
 ///
 /// Data Validations
-/// -
+/// - Instance Authority signed and is the Instance's authority
+/// - Instance and World are owned by this program and initialized
+/// - World PDA is derived from its stored seeds
+/// - World is launched, unless Instance Authority is World Authority
+/// - Instance PDA is derived from World, Region and Entity
 ///
 pub fn process_despawn_entity(
-    _program_id: &Pubkey,
+    program_id: &Pubkey,
     ctx: Context<DespawnEntityAccounts>,
+    region: Region,
+    entity: Entity,
 ) -> ProgramResult {
-    // Instance State PDA
-    let instance_account_lamports = ctx.accounts.instance.lamports();
+    require!(
+        ctx.accounts.instance_authority.is_signer,
+        RushStoreError::MissingSignature,
+        "instance_authority"
+    );
+    require!(
+        ctx.accounts.instance.owner == program_id,
+        RushStoreError::InvalidAccountOwner,
+        "instance"
+    );
+    require!(
+        ctx.accounts.world.owner == program_id,
+        RushStoreError::InvalidAccountOwner,
+        "world"
+    );
 
-    // Instance Authority
-    let instance_authority_lamports = ctx.accounts.instance_authority.lamports();
+    // need to use Borsh version 1 for dynamic data
+    // else, de/serialization will fail with Account Unknown Error at runtime
+    let instance_data = ctx.accounts.instance.try_borrow_data()?;
+    let instance = borsh1::try_from_slice_unchecked::<Instance>(&instance_data)?;
+    drop(instance_data);
+    // Instance must not be despawned already
+    require!(
+        instance.is_initialized(),
+        RushStoreError::InstanceNotFound,
+        "instance"
+    );
+    require!(
+        instance.instance_authority == *ctx.accounts.instance_authority.key,
+        RushStoreError::InvalidInstanceAuthority,
+        "instance_authority"
+    );
+
+    // Region and Entity must be the ones the Instance was spawned in
+    let instance_pda = Pubkey::create_program_address(
+        &[
+            InstancePDA::TAG.as_bytes(),
+            ctx.accounts.world.key.as_ref(),
+            region.as_bytes(),
+            entity.as_bytes(),
+            &instance.nonce.to_le_bytes(),
+            &[instance.bump],
+        ],
+        program_id,
+    )
+    .map_err(|_| RushStoreError::InvalidInstancePDA)?;
+    require!(
+        instance_pda == *ctx.accounts.instance.key,
+        RushStoreError::InvalidInstancePDA,
+        "instance"
+    );
+
+    let world_data = ctx.accounts.world.try_borrow_data()?;
+    let mut world = borsh1::try_from_slice_unchecked::<World>(&world_data)?;
+    // Relinquish borrow for realloc
+    drop(world_data);
+    // World must be initialized
+    require!(
+        world.is_initialized(),
+        RushStoreError::WorldNotFound,
+        "world"
+    );
+
+    let world_pda = Pubkey::create_program_address(
+        &[
+            WorldPDA::TAG.as_bytes(),
+            world.name.as_bytes(),
+            world.description.as_bytes(),
+            &[world.bump],
+        ],
+        program_id,
+    )
+    .map_err(|_| RushStoreError::InvalidWorldPDA)?;
+    require!(
+        world_pda == *ctx.accounts.world.key,
+        RushStoreError::InvalidWorldPDA,
+        "world"
+    );
+    // only World Authority can despawn before launch and while paused
+    require!(
+        world.is_open_to(ctx.accounts.instance_authority.key),
+        RushStoreError::WorldNotLaunched,
+        "world"
+    );
+
+    // update world bookkeeping
+    let despawned_mut = world
+        .despawned
+        .entry(region.clone())
+        .or_default()
+        .entry(entity.clone())
+        .or_default();
+    *despawned_mut += 1;
+    // a forced removal may have stopped tracking the Instance
+    if let Some(live_mut) = world.live.get_mut(&region).and_then(|e| e.get_mut(&entity)) {
+        *live_mut = live_mut.saturating_sub(1);
+    }
+
+    // realloc, rent for the grown World is taken from the
+    // closed Instance and the rest goes to Instance Authority
+    let new_world_size = borsh1::get_instance_packed_len::<World>(&world)?;
+    let new_rent = Rent::get()?.minimum_balance(new_world_size);
+    let new_lamports_needed = new_rent.saturating_sub(ctx.accounts.world.lamports());
 
-    // direct transfer token_base (PDA) lamports into sale_authority
-    // NOTE: Direct transfer is okay since token_base is a PDA owned by sale_authority
+    ctx.accounts.world.realloc(new_world_size, true)?;
+
+    let refund = ctx
+        .accounts
+        .instance
+        .lamports()
+        .checked_sub(new_lamports_needed)
+        .ok_or(ProgramError::InsufficientFunds)?;
+
+    // direct transfer is okay since both World and Instance
+    // are owned by this program
     //
-    // I know, it looks like it's illegal but Solana ALWAYS does balances
-    // and checks pre and post so this is totally safe
+    // Solana ALWAYS does balances and checks pre and post so
+    // this is totally safe
+    let world_lamports = ctx.accounts.world.lamports();
+    **ctx.accounts.world.try_borrow_mut_lamports()? = world_lamports
+        .checked_add(new_lamports_needed) // None if overflow
+        .unwrap();
+
+    let instance_authority_lamports = ctx.accounts.instance_authority.lamports();
     **ctx.accounts.instance_authority.try_borrow_mut_lamports()? = instance_authority_lamports
-        .checked_add(instance_account_lamports) // None if overflow
+        .checked_add(refund) // None if overflow
         .unwrap();
 
     // zero out Instance State PDA lamports
@@ -43,5 +177,9 @@ pub fn process_despawn_entity(
     let mut instance_data = ctx.accounts.instance.try_borrow_mut_data()?;
     instance_data.fill(0);
 
+    // store new world state
+    let mut world_data = ctx.accounts.world.try_borrow_mut_data()?;
+    world.serialize(&mut &mut world_data[..])?;
+
     Ok(())
 }
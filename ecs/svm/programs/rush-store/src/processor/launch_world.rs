@@ -0,0 +1,80 @@
+use borsh::BorshSerialize;
+use rush_ecs_svm::instruction::accounts::{Context, LaunchWorldAccounts};
+use rush_ecs_svm::{error::RushStoreError, pda::WorldPDA, require, state::World};
+use solana_program::{borsh1, entrypoint::ProgramResult, pubkey::Pubkey};
+
+/// Launch World
+///
+/// - Opens the World to Instance Authorities other than
+///   World Authority, they can now Spawn, Update and
+///   Despawn Instances
+/// - Also resumes a paused World
+///
+/// Accounts
+/// 0. `[SIGNER]`       World Authority
+/// 1. `[WRITE]`        World PDA
+///
+/// Instruction Data
+/// - (None)
+///
+/// Data Validations
+/// - World Authority signed and is the World's authority
+/// - World is owned by this program and initialized
+/// - World PDA is derived from its stored seeds
+///
+pub fn process_launch_world(
+    program_id: &Pubkey,
+    ctx: Context<LaunchWorldAccounts>,
+) -> ProgramResult {
+    require!(
+        ctx.accounts.world_authority.is_signer,
+        RushStoreError::MissingSignature,
+        "world_authority"
+    );
+    require!(
+        ctx.accounts.world.owner == program_id,
+        RushStoreError::InvalidAccountOwner,
+        "world"
+    );
+
+    let mut world_data = ctx.accounts.world.try_borrow_mut_data()?;
+
+    // need to use Borsh version 1 for dynamic data
+    // else, de/serialization will fail with Account Unknown Error at runtime
+    let mut world = borsh1::try_from_slice_unchecked::<World>(&world_data)?;
+    // World must be initialized
+    require!(
+        world.is_initialized(),
+        RushStoreError::WorldNotFound,
+        "world"
+    );
+
+    let world_pda = Pubkey::create_program_address(
+        &[
+            WorldPDA::TAG.as_bytes(),
+            world.name.as_bytes(),
+            world.description.as_bytes(),
+            &[world.bump],
+        ],
+        program_id,
+    )
+    .map_err(|_| RushStoreError::InvalidWorldPDA)?;
+    require!(
+        world_pda == *ctx.accounts.world.key,
+        RushStoreError::InvalidWorldPDA,
+        "world"
+    );
+    require!(
+        world.world_authority == *ctx.accounts.world_authority.key,
+        RushStoreError::InvalidWorldAuthority,
+        "world_authority"
+    );
+
+    // size doesn't change, no realloc needed
+    world.is_launched = true;
+
+    // store new world state
+    world.serialize(&mut &mut world_data[..])?;
+
+    Ok(())
+}
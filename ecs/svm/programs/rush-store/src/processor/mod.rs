@@ -1,13 +1,21 @@
+pub mod batch_update;
 pub mod create_world;
 pub mod delete_world;
 pub mod despawn_entity;
+pub mod launch_world;
+pub mod modify_component;
+pub mod pause_world;
 pub mod spawn_entity;
 pub mod update_entity;
 pub mod update_world;
 
+use batch_update::*;
 use create_world::*;
 use delete_world::*;
 use despawn_entity::*;
+use launch_world::*;
+use modify_component::*;
+use pause_world::*;
 use spawn_entity::*;
 use update_entity::*;
 use update_world::*;
@@ -47,11 +55,20 @@ impl<'a> Processor {
                 bump,
             )?,
 
-            RushStoreInstruction::UpdateWorld { regions, entities } => process_update_world(
+            RushStoreInstruction::UpdateWorld {
+                add_regions,
+                remove_regions,
+                add_entities,
+                remove_entities,
+                force,
+            } => process_update_world(
                 program_id,
                 UpdateWorldAccounts::context(accounts)?,
-                regions,
-                entities,
+                add_regions,
+                remove_regions,
+                add_entities,
+                remove_entities,
+                force,
             )?,
 
             RushStoreInstruction::DeleteWorld => {
@@ -79,10 +96,45 @@ impl<'a> Processor {
                 UpdateEntityAccounts::context(accounts)?,
                 component,
                 value,
+                None,
+            )?,
+
+            RushStoreInstruction::DespawnEntity { region, entity } => process_despawn_entity(
+                program_id,
+                DespawnEntityAccounts::context(accounts)?,
+                region,
+                entity,
+            )?,
+
+            RushStoreInstruction::LaunchWorld => {
+                process_launch_world(program_id, LaunchWorldAccounts::context(accounts)?)?
+            }
+
+            RushStoreInstruction::PauseWorld => {
+                process_pause_world(program_id, PauseWorldAccounts::context(accounts)?)?
+            }
+
+            RushStoreInstruction::ModifyComponent { component, op } => process_modify_component(
+                program_id,
+                ModifyComponentAccounts::context(accounts)?,
+                component,
+                op,
+            )?,
+
+            RushStoreInstruction::UpdateEntityVersioned {
+                component,
+                value,
+                expected_version,
+            } => process_update_entity(
+                program_id,
+                UpdateEntityAccounts::context(accounts)?,
+                component,
+                value,
+                Some(expected_version),
             )?,
 
-            RushStoreInstruction::DespawnEntity => {
-                process_despawn_entity(program_id, DespawnEntityAccounts::context(accounts)?)?
+            RushStoreInstruction::BatchUpdate { updates } => {
+                process_batch_update(program_id, BatchUpdateAccounts::context(accounts)?, updates)?
             }
         }
 
@@ -0,0 +1,134 @@
+use borsh::BorshSerialize;
+use rush_ecs_core::{blueprint::Component, numeric::ComponentOp};
+use rush_ecs_svm::instruction::accounts::{Context, ModifyComponentAccounts};
+use rush_ecs_svm::{
+    error::RushStoreError,
+    pda::WorldPDA,
+    require,
+    state::{Instance, World},
+};
+use solana_program::{
+    borsh1, clock::Clock, entrypoint::ProgramResult, pubkey::Pubkey, sysvar::Sysvar,
+};
+
+/// Modify Component
+///
+/// - Try data slice with Borsh1 Unchecked
+/// - Apply the operation to the numeric component in place,
+///   reading and writing in a single instruction so concurrent
+///   operations never overwrite each other
+/// - Bump the Instance version and record the slot
+///
+/// Accounts
+/// 0. `[SIGNER]` Instance Authority
+/// 1. `[WRITE]`  Instance PDA
+/// 2. `[]`       World PDA
+///
+/// Data Validations
+/// - Instance Authority signed and is the Instance's authority
+/// - Instance is owned by this program and initialized
+/// - World is the one the Instance was spawned in
+/// - World PDA is derived from its stored seeds
+/// - World is launched, unless Instance Authority is World Authority
+/// - Component exists, is numeric, deltas are of its delta type
+///   and bounds of its type
+/// - Result fits the Component's type
+///
+/// Numeric values have a fixed size, so the Instance
+/// is never resized
+///
+pub fn process_modify_component(
+    program_id: &Pubkey,
+    ctx: Context<ModifyComponentAccounts>,
+    component: Component,
+    op: ComponentOp,
+) -> ProgramResult {
+    require!(
+        ctx.accounts.instance_authority.is_signer,
+        RushStoreError::MissingSignature,
+        "instance_authority"
+    );
+    require!(
+        ctx.accounts.instance.owner == program_id,
+        RushStoreError::InvalidAccountOwner,
+        "instance"
+    );
+    require!(
+        ctx.accounts.world.owner == program_id,
+        RushStoreError::InvalidAccountOwner,
+        "world"
+    );
+
+    let instance_data = ctx.accounts.instance.try_borrow_data()?;
+
+    // need to use Borsh version 1 for dynamic data
+    // else, de/serialization will fail with Account Unknown Error at runtime
+    let mut instance = borsh1::try_from_slice_unchecked::<Instance>(&instance_data)?;
+    drop(instance_data);
+    // despawned Instances are zeroed
+    require!(
+        instance.is_initialized(),
+        RushStoreError::InstanceNotFound,
+        "instance"
+    );
+    require!(
+        instance.instance_authority == *ctx.accounts.instance_authority.key,
+        RushStoreError::InvalidInstanceAuthority,
+        "instance_authority"
+    );
+    require!(
+        instance.world == *ctx.accounts.world.key,
+        RushStoreError::InstanceNotInWorld,
+        "world"
+    );
+
+    let world_data = ctx.accounts.world.try_borrow_data()?;
+    let world = borsh1::try_from_slice_unchecked::<World>(&world_data)?;
+    drop(world_data);
+    // World must be initialized
+    require!(
+        world.is_initialized(),
+        RushStoreError::WorldNotFound,
+        "world"
+    );
+
+    let world_pda = Pubkey::create_program_address(
+        &[
+            WorldPDA::TAG.as_bytes(),
+            world.name.as_bytes(),
+            world.description.as_bytes(),
+            &[world.bump],
+        ],
+        program_id,
+    )
+    .map_err(|_| RushStoreError::InvalidWorldPDA)?;
+    require!(
+        world_pda == *ctx.accounts.world.key,
+        RushStoreError::InvalidWorldPDA,
+        "world"
+    );
+    // only World Authority can update before launch and while paused
+    require!(
+        world.is_open_to(ctx.accounts.instance_authority.key),
+        RushStoreError::WorldNotLaunched,
+        "world"
+    );
+
+    let component_mut = instance
+        .components
+        .get_mut(&component)
+        .ok_or(RushStoreError::ComponentNotFound)?;
+    // only numbers, signed deltas and bounds of the Component's type
+    let same_type = component_mut.infer_type().is_some_and(|t| op.matches(&t));
+    require!(same_type, RushStoreError::TypeMismatch, "op");
+    *component_mut = component_mut
+        .apply(&op)
+        .ok_or(RushStoreError::InvalidOperation)?;
+    instance.touch(Clock::get()?.slot);
+
+    // store new Instance state, same size as before
+    let mut instance_data = ctx.accounts.instance.try_borrow_mut_data()?;
+    instance.serialize(&mut &mut instance_data[..])?;
+
+    Ok(())
+}
@@ -2,13 +2,14 @@ use borsh::BorshSerialize;
 use rush_ecs_core::blueprint::{Component, ComponentValue, Entity, Region};
 use rush_ecs_svm::instruction::accounts::{Context, SpawnEntityAccounts};
 use rush_ecs_svm::{
-    pda::InstancePDA,
+    error::RushStoreError,
+    pda::{InstancePDA, WorldPDA},
     require,
     state::{Instance, World},
 };
 use solana_program::{
-    borsh1, entrypoint::ProgramResult, hash::hash, msg, program::invoke_signed,
-    program_error::ProgramError, pubkey::Pubkey, rent::Rent, system_instruction, sysvar::Sysvar,
+    borsh1, clock::Clock, entrypoint::ProgramResult, program::invoke_signed, pubkey::Pubkey,
+    rent::Rent, system_instruction, system_program, sysvar::Sysvar,
 };
 use std::collections::BTreeMap;
 /// Spawn Entity
@@ -30,7 +31,14 @@ use std::collections::BTreeMap;
 /// - bump: u8,
 ///
 /// Data Validations
-/// -
+/// - Instance Authority signed
+/// - World is owned by this program and initialized
+/// - World PDA is derived from its stored seeds
+/// - World is launched, unless Instance Authority is World Authority
+/// - Instance PDA is derived from World, Region, Entity, nonce and bump
+/// - System Program is the System Program
+/// - Region and Entity exist in the World
+/// - nonce is greater than the last nonce spawned
 ///
 pub fn process_spawn_entity(
     program_id: &Pubkey,
@@ -41,33 +49,122 @@ pub fn process_spawn_entity(
     nonce: u64,
     bump: u8,
 ) -> ProgramResult {
+    require!(
+        ctx.accounts.instance_authority.is_signer,
+        RushStoreError::MissingSignature,
+        "instance_authority"
+    );
+    require!(
+        ctx.accounts.world.owner == program_id,
+        RushStoreError::InvalidAccountOwner,
+        "world"
+    );
+    require!(
+        system_program::check_id(ctx.accounts.system_program.key),
+        RushStoreError::InvalidSystemProgram,
+        "system_program"
+    );
+
+    // Instance must be created at the PDA of its seeds
+    let instance_pda = Pubkey::create_program_address(
+        &[
+            InstancePDA::TAG.as_bytes(),
+            ctx.accounts.world.key.as_ref(),
+            region.as_bytes(),
+            entity.as_bytes(),
+            &nonce.to_le_bytes(),
+            &[bump],
+        ],
+        program_id,
+    )
+    .map_err(|_| RushStoreError::InvalidInstancePDA)?;
+    require!(
+        instance_pda == *ctx.accounts.instance.key,
+        RushStoreError::InvalidInstancePDA,
+        "instance"
+    );
+
     // Make sure World exists
     let mut world_data = ctx.accounts.world.try_borrow_mut_data()?;
     let mut world = borsh1::try_from_slice_unchecked::<World>(&world_data)?;
     // World must be initialized
     require!(
         world.is_initialized(),
-        ProgramError::UninitializedAccount,
+        RushStoreError::WorldNotFound,
         "world"
     );
 
-    let new_instance_state = Instance::new(
+    let world_pda = Pubkey::create_program_address(
+        &[
+            WorldPDA::TAG.as_bytes(),
+            world.name.as_bytes(),
+            world.description.as_bytes(),
+            &[world.bump],
+        ],
+        program_id,
+    )
+    .map_err(|_| RushStoreError::InvalidWorldPDA)?;
+    require!(
+        world_pda == *ctx.accounts.world.key,
+        RushStoreError::InvalidWorldPDA,
+        "world"
+    );
+    // only World Authority can spawn before launch and while paused
+    require!(
+        world.is_open_to(ctx.accounts.instance_authority.key),
+        RushStoreError::WorldNotLaunched,
+        "world"
+    );
+
+    let mut new_instance_state = Instance::new(
         components,
         nonce,
         *ctx.accounts.instance_authority.key,
         bump,
+        *ctx.accounts.world.key,
+    );
+    new_instance_state.last_update_slot = Clock::get()?.slot;
+
+    // removed Regions and Entities keep their last nonce
+    // but can't spawn
+    require!(
+        world.regions.contains(&region),
+        RushStoreError::RegionNotFound,
+        "region"
+    );
+    require!(
+        world.entities.contains(&entity),
+        RushStoreError::EntityNotFound,
+        "entity"
     );
 
     // update world_data instances
     //
-    // create_world guarantees existence of keys, unwrap here is okay
+    // create_world and update_world track every Region and
+    // Entity of the World
     let instance_mut = world
         .instances
         .get_mut(&region)
-        .unwrap()
+        .ok_or(RushStoreError::RegionNotFound)?
         .get_mut(&entity)
-        .unwrap();
-    *instance_mut += 1;
+        .ok_or(RushStoreError::EntityNotFound)?;
+    // nonces of despawned Instances are never reused, skipped
+    // nonces are spent as well
+    require!(
+        nonce > *instance_mut,
+        RushStoreError::NonceAlreadyUsed,
+        "nonce"
+    );
+    *instance_mut = nonce;
+
+    // preloaded with the Instance counters, size doesn't change
+    let live_mut = world
+        .live
+        .entry(region.clone())
+        .or_default()
+        .entry(entity.clone())
+        .or_default();
+    *live_mut += 1;
 
     // need to use Borsh version 1 for dynamic data
     // else, de/serialization will fail with Account Unknown Error at runtime
@@ -1,39 +1,267 @@
 use borsh::BorshSerialize;
 use rush_ecs_core::blueprint::{Component, ComponentValue};
 use rush_ecs_svm::instruction::accounts::{Context, UpdateEntityAccounts};
-use rush_ecs_svm::state::Instance;
-use solana_program::{borsh1, entrypoint::ProgramResult, pubkey::Pubkey};
+use rush_ecs_svm::{
+    error::RushStoreError,
+    pda::WorldPDA,
+    require,
+    state::{Instance, World},
+};
+use solana_program::{
+    account_info::AccountInfo, borsh1, clock::Clock, entrypoint::ProgramResult, program::invoke,
+    pubkey::Pubkey, rent::Rent, system_instruction, system_program, sysvar::Sysvar,
+};
+use std::mem::discriminant;
 
 /// Update Entity
 ///
 /// - Try data slice with Borsh1 Unchecked
 /// - Update Instance State PDA with new component value
+/// - Bump the Instance version and record the slot
+/// - Realloc Instance State PDA to the new value's size,
+///   rent is topped up by or refunded to Instance Authority
 ///
 /// Accounts
-/// 0. `[SIGNER]`       Instance Authority
+/// 0. `[WRITE, SIGNER]` Instance Authority
 /// 1. `[WRITE]`        Instance PDA
+/// 2. `[]`             System Program
+/// 3. `[]`             World PDA
 ///
 /// Data Validations
-/// -
+/// - Instance Authority signed and is the Instance's authority
+/// - Instance is owned by this program and initialized
+/// - System Program is the System Program
+/// - World is the one the Instance was spawned in
+/// - World PDA is derived from its stored seeds
+/// - World is launched, unless Instance Authority is World Authority
+/// - Component exists and the value is of its type
+/// - Instance version is the expected version, if given
+///   (UpdateEntityVersioned)
+///
+/// Instances are only ever created by SpawnEntity at their PDA,
+/// so ownership is enough to trust the Instance account
 ///
 pub fn process_update_entity(
-    _program_id: &Pubkey,
+    program_id: &Pubkey,
     ctx: Context<UpdateEntityAccounts>,
     component: Component,
     value: ComponentValue,
+    expected_version: Option<u64>,
 ) -> ProgramResult {
-    let mut instance_data = ctx.accounts.instance.try_borrow_mut_data()?;
+    require!(
+        ctx.accounts.instance_authority.is_signer,
+        RushStoreError::MissingSignature,
+        "instance_authority"
+    );
+    require!(
+        ctx.accounts.instance.owner == program_id,
+        RushStoreError::InvalidAccountOwner,
+        "instance"
+    );
+    require!(
+        system_program::check_id(ctx.accounts.system_program.key),
+        RushStoreError::InvalidSystemProgram,
+        "system_program"
+    );
+    require!(
+        ctx.accounts.world.owner == program_id,
+        RushStoreError::InvalidAccountOwner,
+        "world"
+    );
+
+    let instance_data = ctx.accounts.instance.try_borrow_data()?;
 
     // need to use Borsh version 1 for dynamic data
     // else, de/serialization will fail with Account Unknown Error at runtime
     let mut instance = borsh1::try_from_slice_unchecked::<Instance>(&instance_data)?;
+    // Relinquish borrow for realloc
+    drop(instance_data);
+    // despawned Instances are zeroed
+    require!(
+        instance.is_initialized(),
+        RushStoreError::InstanceNotFound,
+        "instance"
+    );
+    require!(
+        instance.instance_authority == *ctx.accounts.instance_authority.key,
+        RushStoreError::InvalidInstanceAuthority,
+        "instance_authority"
+    );
+    require!(
+        instance.world == *ctx.accounts.world.key,
+        RushStoreError::InstanceNotInWorld,
+        "world"
+    );
+
+    let world_data = ctx.accounts.world.try_borrow_data()?;
+    let world = borsh1::try_from_slice_unchecked::<World>(&world_data)?;
+    drop(world_data);
+    // World must be initialized
+    require!(
+        world.is_initialized(),
+        RushStoreError::WorldNotFound,
+        "world"
+    );
 
-    // TODO: Error handling
-    let component_mut = instance.components.get_mut(&component).unwrap();
+    let world_pda = Pubkey::create_program_address(
+        &[
+            WorldPDA::TAG.as_bytes(),
+            world.name.as_bytes(),
+            world.description.as_bytes(),
+            &[world.bump],
+        ],
+        program_id,
+    )
+    .map_err(|_| RushStoreError::InvalidWorldPDA)?;
+    require!(
+        world_pda == *ctx.accounts.world.key,
+        RushStoreError::InvalidWorldPDA,
+        "world"
+    );
+    // only World Authority can update before launch and while paused
+    require!(
+        world.is_open_to(ctx.accounts.instance_authority.key),
+        RushStoreError::WorldNotLaunched,
+        "world"
+    );
+
+    // someone else updated the Instance since it was read
+    require!(
+        expected_version.map_or(true, |v| v == instance.version),
+        RushStoreError::VersionMismatch,
+        "instance"
+    );
+
+    let component_mut = instance
+        .components
+        .get_mut(&component)
+        .ok_or(RushStoreError::ComponentNotFound)?;
+    // Component keeps the type it was spawned with
+    require!(
+        is_same_type(component_mut, &value),
+        RushStoreError::TypeMismatch,
+        "value"
+    );
     *component_mut = value;
+    instance.touch(Clock::get()?.slot);
+
+    resize_instance(
+        ctx.accounts.instance,
+        ctx.accounts.instance_authority,
+        ctx.accounts.system_program,
+        &instance,
+    )?;
 
     // store new Instance state
+    let mut instance_data = ctx.accounts.instance.try_borrow_mut_data()?;
     instance.serialize(&mut &mut instance_data[..])?;
 
     Ok(())
 }
+
+/// Is `true` if `value` can replace `current`
+///
+/// Components keep the type they were spawned with, and
+/// the new value must be of a single type itself, e.g. no
+/// List mixing Integers and Strings
+pub(crate) fn is_same_type(current: &ComponentValue, value: &ComponentValue) -> bool {
+    is_consistent(value) && has_same_shape(current, value)
+}
+
+/// Is `true` if `value` is of the type of `current`
+///
+/// Empty collections only tell their kind, so any
+/// elements can fill them
+fn has_same_shape(current: &ComponentValue, value: &ComponentValue) -> bool {
+    use ComponentValue as V;
+
+    match (current, value) {
+        (V::List(current), V::List(values)) => current
+            .first()
+            .map_or(true, |c| values.iter().all(|v| has_same_shape(c, v))),
+        (V::Map(current), V::Map(values)) => current
+            .values()
+            .next()
+            .map_or(true, |c| values.values().all(|v| has_same_shape(c, v))),
+        (V::Struct(current), V::Struct(values)) => {
+            current.len() == values.len()
+                && current
+                    .iter()
+                    .all(|(k, c)| values.get(k).is_some_and(|v| has_same_shape(c, v)))
+        }
+        (V::Fixed(current), V::Fixed(value)) => current.scale() == value.scale(),
+        _ => discriminant(current) == discriminant(value),
+    }
+}
+
+/// Is `true` if every element of a collection in `value`
+/// is of the type of the others
+fn is_consistent(value: &ComponentValue) -> bool {
+    use ComponentValue as V;
+
+    // a fully typed value carries its own element types
+    if let Some(component_type) = value.infer_type() {
+        return component_type.matches(value);
+    }
+
+    let elements: Vec<&ComponentValue> = match value {
+        V::List(values) => values.iter().collect(),
+        V::Map(values) => values.values().collect(),
+        V::Struct(values) => return values.values().all(is_consistent),
+        _ => return true,
+    };
+
+    elements.iter().all(|v| is_consistent(v))
+        && elements.first().map_or(true, |first| {
+            elements
+                .iter()
+                .all(|v| has_same_shape(first, v) && has_same_shape(v, first))
+        })
+}
+
+/// Realloc the Instance account to fit its new state
+///
+/// Rent is topped up by Instance Authority when it grows
+/// and refunded to it when it shrinks
+pub(crate) fn resize_instance<'a>(
+    instance_account: &AccountInfo<'a>,
+    instance_authority: &AccountInfo<'a>,
+    system_program: &AccountInfo<'a>,
+    instance: &Instance,
+) -> ProgramResult {
+    // realloc, grows or shrinks with the new value
+    let new_instance_size = borsh1::get_instance_packed_len::<Instance>(instance)?;
+    let new_rent = Rent::get()?.minimum_balance(new_instance_size);
+    let instance_lamports = instance_account.lamports();
+
+    instance_account.realloc(new_instance_size, false)?;
+
+    if new_rent > instance_lamports {
+        // make rent-exempt again after growing
+        let from = instance_authority.key;
+        let to = instance_account.key;
+        let ix = system_instruction::transfer(from, to, new_rent - instance_lamports);
+
+        invoke(
+            &ix,
+            &[
+                instance_authority.clone(),
+                instance_account.clone(),
+                system_program.clone(),
+            ],
+        )?;
+    } else {
+        // refund rent freed by shrinking
+        //
+        // direct transfer is okay since Instance is owned by
+        // this program
+        let refund = instance_lamports - new_rent;
+        let instance_authority_lamports = instance_authority.lamports();
+        **instance_authority.try_borrow_mut_lamports()? = instance_authority_lamports
+            .checked_add(refund) // None if overflow
+            .unwrap();
+        **instance_account.try_borrow_mut_lamports()? = new_rent;
+    }
+
+    Ok(())
+}
@@ -1,35 +1,66 @@
 use borsh::BorshSerialize;
 use rush_ecs_core::blueprint::{Entity, Region};
 use rush_ecs_svm::instruction::accounts::{Context, UpdateWorldAccounts};
-use rush_ecs_svm::state::World;
+use rush_ecs_svm::{error::RushStoreError, pda::WorldPDA, require, state::World};
 use solana_program::{
     borsh1, entrypoint::ProgramResult, program::invoke, pubkey::Pubkey, system_instruction,
-    sysvar::rent::Rent, sysvar::Sysvar,
+    system_program, sysvar::rent::Rent, sysvar::Sysvar,
 };
 
 /// Update World
 ///
 /// - Try data slice with Borsh1 Unchecked
-/// - Update World State PDA with new entities / regions
+/// - Removes entities / regions and stops tracking their live
+///   Instances, their last nonces are kept
+/// - Adds entities / regions and starts tracking their Instances
+/// - Realloc World State PDA, rent is topped up by or refunded
+///   to World Authority
 ///
 /// Accounts
-/// 0. `[SIGNER]`       World Authority
+/// 0. `[WRITE, SIGNER]` World Authority
 /// 1. `[WRITE]`        World PDA
 /// 2. `[]`             System Program
 ///
 /// Instruction Data
-/// - regions: Vec<Region>,
-/// - entities: Vec<Entity>
+/// - add_regions: Vec<Region>,
+/// - remove_regions: Vec<Region>,
+/// - add_entities: Vec<Entity>,
+/// - remove_entities: Vec<Entity>,
+/// - force: bool
 ///
 /// Data Validations
-/// -
+/// - World Authority signed and is the World's authority
+/// - World is owned by this program and initialized
+/// - World PDA is derived from its stored seeds
+/// - System Program is the System Program
+/// - Removed regions / entities exist and have no live
+///   Instances, unless `force` is set
 ///
 pub fn process_update_world(
-    _program_id: &Pubkey,
+    program_id: &Pubkey,
     ctx: Context<UpdateWorldAccounts>,
-    regions: Vec<Region>,
-    entities: Vec<Entity>,
+    add_regions: Vec<Region>,
+    remove_regions: Vec<Region>,
+    add_entities: Vec<Entity>,
+    remove_entities: Vec<Entity>,
+    force: bool,
 ) -> ProgramResult {
+    require!(
+        ctx.accounts.world_authority.is_signer,
+        RushStoreError::MissingSignature,
+        "world_authority"
+    );
+    require!(
+        ctx.accounts.world.owner == program_id,
+        RushStoreError::InvalidAccountOwner,
+        "world"
+    );
+    require!(
+        system_program::check_id(ctx.accounts.system_program.key),
+        RushStoreError::InvalidSystemProgram,
+        "system_program"
+    );
+
     let world_data = ctx.accounts.world.try_borrow_data()?;
 
     // need to use Borsh version 1 for dynamic data
@@ -37,31 +68,82 @@ pub fn process_update_world(
     let mut world = borsh1::try_from_slice_unchecked::<World>(&world_data)?;
     // Relinquish borrow for realloc
     drop(world_data);
+    // World must be initialized
+    require!(
+        world.is_initialized(),
+        RushStoreError::WorldNotFound,
+        "world"
+    );
+
+    let world_pda = Pubkey::create_program_address(
+        &[
+            WorldPDA::TAG.as_bytes(),
+            world.name.as_bytes(),
+            world.description.as_bytes(),
+            &[world.bump],
+        ],
+        program_id,
+    )
+    .map_err(|_| RushStoreError::InvalidWorldPDA)?;
+    require!(
+        world_pda == *ctx.accounts.world.key,
+        RushStoreError::InvalidWorldPDA,
+        "world"
+    );
+    require!(
+        world.world_authority == *ctx.accounts.world_authority.key,
+        RushStoreError::InvalidWorldAuthority,
+        "world_authority"
+    );
 
-    // update world state
-    world.regions = regions;
-    world.entities = entities;
+    // update world state, removals first so a region or
+    // entity can be removed and added back in one go
+    for entity in remove_entities.iter() {
+        world.remove_entity(entity, force)?;
+    }
+    for region in remove_regions.iter() {
+        world.remove_region(region, force)?;
+    }
+    for region in add_regions {
+        world.add_region(region);
+    }
+    for entity in add_entities {
+        world.add_entity(entity);
+    }
 
-    // realloc
+    // realloc, grows or shrinks with the tracked Instances
     let new_world_size = borsh1::get_instance_packed_len::<World>(&world)?;
     let new_rent = Rent::get()?.minimum_balance(new_world_size);
-    let new_lamports_needed = new_rent.saturating_sub(ctx.accounts.world.lamports());
+    let world_lamports = ctx.accounts.world.lamports();
 
-    ctx.accounts.world.realloc(new_world_size, true)?;
+    ctx.accounts.world.realloc(new_world_size, false)?;
 
-    // make rent-exempt again after resize
-    let from = ctx.accounts.world_authority.key;
-    let to = ctx.accounts.world.key;
-    let ix = system_instruction::transfer(from, to, new_lamports_needed);
+    if new_rent > world_lamports {
+        // make rent-exempt again after growing
+        let from = ctx.accounts.world_authority.key;
+        let to = ctx.accounts.world.key;
+        let ix = system_instruction::transfer(from, to, new_rent - world_lamports);
 
-    invoke(
-        &ix,
-        &[
-            ctx.accounts.world_authority.clone(),
-            ctx.accounts.world.clone(),
-            ctx.accounts.system_program.clone(),
-        ],
-    )?;
+        invoke(
+            &ix,
+            &[
+                ctx.accounts.world_authority.clone(),
+                ctx.accounts.world.clone(),
+                ctx.accounts.system_program.clone(),
+            ],
+        )?;
+    } else {
+        // refund rent freed by shrinking
+        //
+        // direct transfer is okay since World is owned by
+        // this program
+        let refund = world_lamports - new_rent;
+        let world_authority_lamports = ctx.accounts.world_authority.lamports();
+        **ctx.accounts.world_authority.try_borrow_mut_lamports()? = world_authority_lamports
+            .checked_add(refund) // None if overflow
+            .unwrap();
+        **ctx.accounts.world.try_borrow_mut_lamports()? = new_rent;
+    }
 
     // store new world state
     let mut world_data = ctx.accounts.world.try_borrow_mut_data()?;
@@ -1,8 +1,49 @@
 #![cfg(test)]
 
+use rush_ecs_svm::error::RushStoreError;
+use solana_program_test::{BanksClientError, ProgramTestContext};
+use solana_sdk::{
+    instruction::{Instruction, InstructionError},
+    signature::{Keypair, Signer},
+    transaction::{Transaction, TransactionError},
+};
+
+mod test_batch_update;
 mod test_create_world;
 mod test_delete_world;
 mod test_despawn_entity;
+mod test_launch_world;
+mod test_modify_component;
 mod test_spawn_entity;
+mod test_unauthorized;
 mod test_update_entity;
 mod test_update_world;
+
+/// Sends the instructions paid by the payer, signed by
+/// the payer and `signers`
+async fn send(
+    ctx: &mut ProgramTestContext,
+    ixs: &[Instruction],
+    signers: &[&Keypair],
+) -> Result<(), BanksClientError> {
+    let payer = ctx.payer.insecure_clone();
+    let mut all_signers = vec![&payer];
+    all_signers.extend_from_slice(signers);
+
+    let transaction = Transaction::new_signed_with_payer(
+        ixs,
+        Some(&payer.pubkey()),
+        &all_signers,
+        ctx.get_new_latest_blockhash().await.unwrap(),
+    );
+
+    ctx.banks_client.process_transaction(transaction).await
+}
+
+/// Asserts the first instruction failed with `error`
+fn assert_custom_error(result: Result<(), BanksClientError>, error: RushStoreError) {
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        TransactionError::InstructionError(0, InstructionError::Custom(error as u32))
+    );
+}
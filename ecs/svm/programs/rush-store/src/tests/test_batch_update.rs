@@ -0,0 +1,294 @@
+use rush_ecs_core::blueprint::{Component, ComponentValue};
+use rush_ecs_svm::{
+    client::{ix_batch_update, ix_create_world, ix_launch_world, ix_pause_world, ix_spawn_entity},
+    error::RushStoreError,
+    instruction::ComponentUpdate,
+    pda::{InstancePDA, WorldPDA},
+    state::Instance,
+};
+use solana_program_test::*;
+use solana_sdk::{
+    account::Account,
+    instruction::InstructionError,
+    pubkey::Pubkey,
+    signature::{Keypair, Signer},
+    system_program,
+    transaction::TransactionError,
+};
+use std::collections::BTreeMap;
+
+use super::{assert_custom_error, send};
+
+async fn get_instance(ctx: &mut ProgramTestContext, instance: Pubkey) -> Instance {
+    ctx.banks_client
+        .get_account_data_with_borsh::<Instance>(instance)
+        .await
+        .unwrap()
+}
+
+fn update(instance: u8, component: &str, value: ComponentValue) -> ComponentUpdate {
+    ComponentUpdate {
+        instance,
+        component: component.to_string(),
+        value,
+    }
+}
+
+/// Test Happy Path and all-or-nothing failures
+#[tokio::test]
+async fn test_batch_update() {
+    let program_id = Pubkey::new_unique();
+    let program_test = ProgramTest::new(
+        // .so fixture is  retrieved from /target/deploy
+        "rush_ecs_store",
+        program_id,
+        // shank is incompatible with instantiating the BuiltInFunction
+        None,
+    );
+    let mut ctx = program_test.start_with_context().await;
+    let authority = ctx.payer.pubkey();
+
+    let name = String::from("Sonic's World");
+    let description = String::from("This is Sonic's World");
+    let region = String::from("region1");
+    let entity = String::from("entity1");
+
+    let (world_pda, world_bump) = WorldPDA::find_pda(&program_id, &name, &description);
+    let mut ixs = vec![ix_create_world(
+        &program_id,
+        name,
+        description,
+        vec![region.clone()],
+        vec![entity.clone()],
+        world_bump,
+        &world_pda,
+        &authority,
+        &authority,
+    )];
+
+    let mut components: BTreeMap<Component, ComponentValue> = BTreeMap::new();
+    components.insert(String::from("x"), ComponentValue::Float(0.0));
+    components.insert(String::from("y"), ComponentValue::Float(0.0));
+    components.insert(String::from("name"), ComponentValue::String("Bob".into()));
+    components.insert(String::from("tags"), ComponentValue::List(vec![]));
+
+    let mut instances = vec![];
+    for nonce in [1, 2] {
+        let (instance_pda, instance_bump) =
+            InstancePDA::find_pda(&program_id, &world_pda, &region, &entity, nonce);
+        ixs.push(ix_spawn_entity(
+            &program_id,
+            region.clone(),
+            entity.clone(),
+            components.clone(),
+            nonce,
+            instance_bump,
+            &instance_pda,
+            &authority,
+            &world_pda,
+        ));
+        instances.push(instance_pda);
+    }
+    send(&mut ctx, &ixs, &[]).await.unwrap();
+
+    // Bob moves and Alice gets a name long enough to realloc
+    let long_name = "Alice".repeat(40);
+    let ix = ix_batch_update(
+        &program_id,
+        vec![
+            update(0, "x", ComponentValue::Float(1.0)),
+            update(0, "y", ComponentValue::Float(2.0)),
+            update(1, "name", ComponentValue::String(long_name.clone())),
+        ],
+        &instances,
+        &authority,
+        &world_pda,
+    );
+    send(&mut ctx, &[ix], &[]).await.unwrap();
+
+    let bob = get_instance(&mut ctx, instances[0]).await;
+    assert_eq!(bob.components["x"], ComponentValue::Float(1.0));
+    assert_eq!(bob.components["y"], ComponentValue::Float(2.0));
+    // one version per Instance, not per Component
+    assert_eq!(bob.version, 1);
+
+    let alice = get_instance(&mut ctx, instances[1]).await;
+    assert_eq!(alice.components["name"], ComponentValue::String(long_name));
+    assert_eq!(alice.version, 1);
+
+    // a bad update rejects the whole batch
+    let ix = ix_batch_update(
+        &program_id,
+        vec![
+            update(0, "x", ComponentValue::Float(5.0)),
+            update(1, "x", ComponentValue::Integer(5)),
+        ],
+        &instances,
+        &authority,
+        &world_pda,
+    );
+    assert_custom_error(
+        send(&mut ctx, &[ix], &[]).await,
+        RushStoreError::TypeMismatch,
+    );
+
+    let bob = get_instance(&mut ctx, instances[0]).await;
+    assert_eq!(bob.components["x"], ComponentValue::Float(1.0));
+
+    // Lists can't mix types, even replacing an empty one
+    let mixed = ComponentValue::List(vec![
+        ComponentValue::Float(1.0),
+        ComponentValue::String("1".to_string()),
+    ]);
+    let ix = ix_batch_update(
+        &program_id,
+        vec![update(0, "tags", mixed)],
+        &instances,
+        &authority,
+        &world_pda,
+    );
+    assert_custom_error(
+        send(&mut ctx, &[ix], &[]).await,
+        RushStoreError::TypeMismatch,
+    );
+
+    // update past the Instance accounts
+    let ix = ix_batch_update(
+        &program_id,
+        vec![update(2, "x", ComponentValue::Float(5.0))],
+        &instances,
+        &authority,
+        &world_pda,
+    );
+    assert_eq!(
+        send(&mut ctx, &[ix], &[]).await.unwrap_err().unwrap(),
+        TransactionError::InstructionError(0, InstructionError::NotEnoughAccountKeys)
+    );
+}
+
+/// Test batches rejected for their signer, World or Instances
+#[tokio::test]
+async fn test_batch_update_unauthorized() {
+    let program_id = Pubkey::new_unique();
+    let mut program_test = ProgramTest::new(
+        // .so fixture is  retrieved from /target/deploy
+        "rush_ecs_store",
+        program_id,
+        // shank is incompatible with instantiating the BuiltInFunction
+        None,
+    );
+
+    // player isn't the World Authority
+    let player = Keypair::new();
+    program_test.add_account(
+        player.pubkey(),
+        Account {
+            lamports: 10_000_000_000,
+            owner: system_program::ID,
+            ..Account::default()
+        },
+    );
+    let mut ctx = program_test.start_with_context().await;
+    let world_authority = ctx.payer.insecure_clone();
+
+    let description = String::from("This is Sonic's World");
+    let region = String::from("region1");
+    let entity = String::from("entity1");
+
+    // the Instances are updated in world, other_world only
+    // holds an Instance of the same authority
+    let mut worlds = vec![];
+    for name in ["Sonic's World", "Tails' World"] {
+        let (world_pda, world_bump) = WorldPDA::find_pda(&program_id, name, &description);
+        let ix = ix_create_world(
+            &program_id,
+            name.to_string(),
+            description.clone(),
+            vec![region.clone()],
+            vec![entity.clone()],
+            world_bump,
+            &world_pda,
+            &world_authority.pubkey(),
+            &world_authority.pubkey(),
+        );
+        send(&mut ctx, &[ix], &[]).await.unwrap();
+        worlds.push(world_pda);
+    }
+    let (world_pda, other_world_pda) = (worlds[0], worlds[1]);
+
+    let mut components: BTreeMap<Component, ComponentValue> = BTreeMap::new();
+    components.insert(String::from("x"), ComponentValue::Float(0.0));
+
+    let spawn = |world: &Pubkey, nonce: u64, authority: &Pubkey| {
+        let (instance_pda, instance_bump) =
+            InstancePDA::find_pda(&program_id, world, &region, &entity, nonce);
+        let ix = ix_spawn_entity(
+            &program_id,
+            region.clone(),
+            entity.clone(),
+            components.clone(),
+            nonce,
+            instance_bump,
+            &instance_pda,
+            authority,
+            world,
+        );
+        (ix, instance_pda)
+    };
+    let move_all = |instances: &[Pubkey], authority: &Pubkey| {
+        let updates = (0..instances.len() as u8)
+            .map(|i| update(i, "x", ComponentValue::Float(1.0)))
+            .collect();
+        ix_batch_update(&program_id, updates, instances, authority, &world_pda)
+    };
+
+    let ix = ix_launch_world(&program_id, &world_pda, &world_authority.pubkey());
+    let (ix2, authority_instance) = spawn(&world_pda, 1, &world_authority.pubkey());
+    let (ix3, other_world_instance) = spawn(&other_world_pda, 1, &world_authority.pubkey());
+    send(&mut ctx, &[ix, ix2, ix3], &[]).await.unwrap();
+    let (ix, player_instance) = spawn(&world_pda, 2, &player.pubkey());
+    send(&mut ctx, &[ix], &[&player]).await.unwrap();
+
+    // signer doesn't own the Instance
+    let ix = move_all(&[authority_instance], &player.pubkey());
+    assert_custom_error(
+        send(&mut ctx, &[ix], &[&player]).await,
+        RushStoreError::InvalidInstanceAuthority,
+    );
+
+    // one of the Instances belongs to another authority
+    let ix = move_all(
+        &[authority_instance, player_instance],
+        &world_authority.pubkey(),
+    );
+    assert_custom_error(
+        send(&mut ctx, &[ix], &[]).await,
+        RushStoreError::InvalidInstanceAuthority,
+    );
+
+    // one of the Instances was spawned in another World
+    let ix = move_all(
+        &[authority_instance, other_world_instance],
+        &world_authority.pubkey(),
+    );
+    assert_custom_error(
+        send(&mut ctx, &[ix], &[]).await,
+        RushStoreError::InstanceNotInWorld,
+    );
+
+    // paused, closed to everyone but World Authority
+    let ix = ix_pause_world(&program_id, &world_pda, &world_authority.pubkey());
+    send(&mut ctx, &[ix], &[]).await.unwrap();
+    let ix = move_all(&[player_instance], &player.pubkey());
+    assert_custom_error(
+        send(&mut ctx, &[ix], &[&player]).await,
+        RushStoreError::WorldNotLaunched,
+    );
+
+    // nothing was written
+    for instance in [authority_instance, player_instance, other_world_instance] {
+        let instance = get_instance(&mut ctx, instance).await;
+        assert_eq!(instance.components["x"], ComponentValue::Float(0.0));
+        assert_eq!(instance.version, 0);
+    }
+}
@@ -3,7 +3,7 @@ use rush_ecs_core::blueprint::{Component, ComponentValue};
 use rush_ecs_svm::{
     client::{ix_create_world, ix_despawn_entity, ix_spawn_entity},
     pda::{InstancePDA, WorldPDA},
-    state::Instance,
+    state::{Instance, World},
 };
 use solana_program_test::*;
 use solana_sdk::{pubkey::Pubkey, signature::Signer, transaction::Transaction};
@@ -42,7 +42,7 @@ async fn test_despawn_entity() {
     );
 
     let region = String::from("region1");
-    let entity = String::from("entity");
+    let entity = String::from("entity1");
     let mut components: BTreeMap<Component, ComponentValue> = BTreeMap::new();
     let value = 143;
     components.insert(String::from("x"), ComponentValue::Integer(value));
@@ -54,8 +54,8 @@ async fn test_despawn_entity() {
 
     let ix2 = ix_spawn_entity(
         &program_id,
-        region,
-        entity,
+        region.clone(),
+        entity.clone(),
         components.clone(),
         nonce,
         instance_bump,
@@ -64,7 +64,14 @@ async fn test_despawn_entity() {
         &world_pda,
     );
 
-    let ix3 = ix_despawn_entity(&program_id, &instance_pda, &ctx.payer.pubkey());
+    let ix3 = ix_despawn_entity(
+        &program_id,
+        region.clone(),
+        entity.clone(),
+        &instance_pda,
+        &ctx.payer.pubkey(),
+        &world_pda,
+    );
 
     let transaction = Transaction::new_signed_with_payer(
         &[ix, ix2, ix3],
@@ -90,4 +97,39 @@ async fn test_despawn_entity() {
         state,
         Err(BanksClientError::ClientError("Account not found"))
     );
+
+    // world keeps the nonce spent and counts the despawn
+    let world_state = ctx
+        .banks_client
+        .get_account_data_with_borsh::<World>(world_pda)
+        .await
+        .unwrap();
+    assert_eq!(world_state.instances[&region][&entity], nonce);
+    assert_eq!(world_state.despawned[&region][&entity], 1);
+
+    // despawned nonce can't be spawned again
+    let ix = ix_spawn_entity(
+        &program_id,
+        region,
+        entity,
+        components,
+        nonce,
+        instance_bump,
+        &instance_pda,
+        &ctx.payer.pubkey(),
+        &world_pda,
+    );
+
+    let transaction = Transaction::new_signed_with_payer(
+        &[ix],
+        Some(&ctx.payer.pubkey()),
+        &[&ctx.payer.insecure_clone()],
+        ctx.get_new_latest_blockhash().await.unwrap(),
+    );
+
+    assert!(ctx
+        .banks_client
+        .process_transaction(transaction)
+        .await
+        .is_err());
 }
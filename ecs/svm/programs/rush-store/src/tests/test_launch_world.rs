@@ -0,0 +1,165 @@
+use rush_ecs_core::blueprint::{Component, ComponentValue};
+use rush_ecs_svm::{
+    client::{
+        ix_create_world, ix_despawn_entity, ix_launch_world, ix_pause_world, ix_spawn_entity,
+        ix_update_entity,
+    },
+    error::RushStoreError,
+    pda::{InstancePDA, WorldPDA},
+    state::World,
+};
+use solana_program_test::*;
+use solana_sdk::{
+    account::Account,
+    pubkey::Pubkey,
+    signature::{Keypair, Signer},
+    system_program,
+};
+use std::collections::BTreeMap;
+
+use super::{assert_custom_error, send};
+
+/// Test Launch and Pause lifecycle
+#[tokio::test]
+async fn test_launch_world() {
+    let program_id = Pubkey::new_unique();
+    let mut program_test = ProgramTest::new(
+        // .so fixture is  retrieved from /target/deploy
+        "rush_ecs_store",
+        program_id,
+        // shank is incompatible with instantiating the BuiltInFunction
+        None,
+    );
+
+    // player isn't the World Authority
+    let player = Keypair::new();
+    program_test.add_account(
+        player.pubkey(),
+        Account {
+            lamports: 10_000_000_000,
+            owner: system_program::ID,
+            ..Account::default()
+        },
+    );
+    let mut ctx = program_test.start_with_context().await;
+    let world_authority = ctx.payer.insecure_clone();
+
+    let name = String::from("Sonic's World");
+    let description = String::from("This is Sonic's World");
+    let region = String::from("region1");
+    let entity = String::from("entity1");
+
+    let (world_pda, world_bump) = WorldPDA::find_pda(&program_id, &name, &description);
+    let ix = ix_create_world(
+        &program_id,
+        name,
+        description,
+        vec![region.clone()],
+        vec![entity.clone()],
+        world_bump,
+        &world_pda,
+        &world_authority.pubkey(),
+        &world_authority.pubkey(),
+    );
+    send(&mut ctx, &[ix], &[]).await.unwrap();
+
+    let mut components: BTreeMap<Component, ComponentValue> = BTreeMap::new();
+    components.insert(String::from("x"), ComponentValue::Integer(143));
+
+    let spawn = |nonce: u64, authority: &Pubkey| {
+        let (instance_pda, instance_bump) =
+            InstancePDA::find_pda(&program_id, &world_pda, &region, &entity, nonce);
+        let ix = ix_spawn_entity(
+            &program_id,
+            region.clone(),
+            entity.clone(),
+            components.clone(),
+            nonce,
+            instance_bump,
+            &instance_pda,
+            authority,
+            &world_pda,
+        );
+        (ix, instance_pda)
+    };
+    let update = |instance: &Pubkey, authority: &Pubkey| {
+        ix_update_entity(
+            &program_id,
+            "x".to_string(),
+            ComponentValue::Integer(1337),
+            instance,
+            authority,
+            &world_pda,
+        )
+    };
+
+    // only World Authority can spawn before launch
+    let (ix, _) = spawn(1, &player.pubkey());
+    assert_custom_error(
+        send(&mut ctx, &[ix], &[&player]).await,
+        RushStoreError::WorldNotLaunched,
+    );
+
+    let (ix, authority_instance) = spawn(1, &world_authority.pubkey());
+    send(&mut ctx, &[ix], &[]).await.unwrap();
+
+    // only World Authority can launch
+    let ix = ix_launch_world(&program_id, &world_pda, &player.pubkey());
+    assert_custom_error(
+        send(&mut ctx, &[ix], &[&player]).await,
+        RushStoreError::InvalidWorldAuthority,
+    );
+
+    let ix = ix_launch_world(&program_id, &world_pda, &world_authority.pubkey());
+    send(&mut ctx, &[ix], &[]).await.unwrap();
+
+    let world = ctx
+        .banks_client
+        .get_account_data_with_borsh::<World>(world_pda)
+        .await
+        .unwrap();
+    assert!(world.is_launched);
+
+    // launched, open to everyone
+    let (ix, player_instance) = spawn(2, &player.pubkey());
+    let ix2 = update(&player_instance, &player.pubkey());
+    send(&mut ctx, &[ix, ix2], &[&player]).await.unwrap();
+
+    let ix = ix_pause_world(&program_id, &world_pda, &world_authority.pubkey());
+    send(&mut ctx, &[ix], &[]).await.unwrap();
+
+    let world = ctx
+        .banks_client
+        .get_account_data_with_borsh::<World>(world_pda)
+        .await
+        .unwrap();
+    assert!(!world.is_launched);
+
+    // paused, closed to everyone but World Authority
+    let ix = update(&player_instance, &player.pubkey());
+    assert_custom_error(
+        send(&mut ctx, &[ix], &[&player]).await,
+        RushStoreError::WorldNotLaunched,
+    );
+
+    let ix = ix_despawn_entity(
+        &program_id,
+        region.clone(),
+        entity.clone(),
+        &player_instance,
+        &player.pubkey(),
+        &world_pda,
+    );
+    assert_custom_error(
+        send(&mut ctx, &[ix], &[&player]).await,
+        RushStoreError::WorldNotLaunched,
+    );
+
+    let ix = update(&authority_instance, &world_authority.pubkey());
+    send(&mut ctx, &[ix], &[]).await.unwrap();
+
+    // launching again resumes the World
+    let ix = ix_launch_world(&program_id, &world_pda, &world_authority.pubkey());
+    let ix2 = update(&player_instance, &player.pubkey());
+    send(&mut ctx, &[ix, ix2], &[&player]).await.unwrap();
+}
@@ -0,0 +1,276 @@
+use rush_ecs_core::{
+    blueprint::{Component, ComponentValue},
+    numeric::ComponentOp,
+};
+use rush_ecs_svm::{
+    client::{
+        ix_create_world, ix_launch_world, ix_modify_component, ix_pause_world, ix_spawn_entity,
+    },
+    error::RushStoreError,
+    pda::{InstancePDA, WorldPDA},
+    state::Instance,
+};
+use solana_program_test::*;
+use solana_sdk::{
+    account::Account,
+    pubkey::Pubkey,
+    signature::{Keypair, Signer},
+    system_program,
+};
+use std::collections::BTreeMap;
+
+use super::{assert_custom_error, send};
+
+/// Test Happy Path and rejected operations
+#[tokio::test]
+async fn test_modify_component() {
+    let program_id = Pubkey::new_unique();
+    let program_test = ProgramTest::new(
+        // .so fixture is  retrieved from /target/deploy
+        "rush_ecs_store",
+        program_id,
+        // shank is incompatible with instantiating the BuiltInFunction
+        None,
+    );
+    let mut ctx = program_test.start_with_context().await;
+    let authority = ctx.payer.pubkey();
+
+    let name = String::from("Sonic's World");
+    let description = String::from("This is Sonic's World");
+    let region = String::from("region1");
+    let entity = String::from("entity1");
+
+    let (world_pda, world_bump) = WorldPDA::find_pda(&program_id, &name, &description);
+    let ix = ix_create_world(
+        &program_id,
+        name,
+        description,
+        vec![region.clone()],
+        vec![entity.clone()],
+        world_bump,
+        &world_pda,
+        &authority,
+        &authority,
+    );
+
+    let mut components: BTreeMap<Component, ComponentValue> = BTreeMap::new();
+    components.insert(String::from("hp"), ComponentValue::Integer(10));
+    components.insert(String::from("lives"), ComponentValue::U8(1));
+    components.insert(String::from("name"), ComponentValue::String("Bob".into()));
+
+    let (instance_pda, instance_bump) =
+        InstancePDA::find_pda(&program_id, &world_pda, &region, &entity, 1);
+    let ix2 = ix_spawn_entity(
+        &program_id,
+        region,
+        entity,
+        components,
+        1,
+        instance_bump,
+        &instance_pda,
+        &authority,
+        &world_pda,
+    );
+    send(&mut ctx, &[ix, ix2], &[]).await.unwrap();
+
+    let modify = |component: &str, op: ComponentOp| {
+        ix_modify_component(
+            &program_id,
+            component.to_string(),
+            op,
+            &instance_pda,
+            &authority,
+            &world_pda,
+        )
+    };
+
+    // each operation reads the value left by the previous one
+    let ixs = [
+        modify("hp", ComponentOp::Add(ComponentValue::Integer(5))),
+        modify("hp", ComponentOp::Max(ComponentValue::Integer(20))),
+        modify(
+            "hp",
+            ComponentOp::Clamp {
+                delta: ComponentValue::Integer(-100),
+                min: ComponentValue::Integer(0),
+                max: ComponentValue::Integer(50),
+            },
+        ),
+        modify("lives", ComponentOp::Add(ComponentValue::Integer(2))),
+        // deltas are signed, unsigned Components go down too
+        modify("lives", ComponentOp::Add(ComponentValue::Integer(-1))),
+        modify(
+            "lives",
+            ComponentOp::Clamp {
+                delta: ComponentValue::Integer(-10),
+                min: ComponentValue::U8(1),
+                max: ComponentValue::U8(5),
+            },
+        ),
+    ];
+    send(&mut ctx, &ixs, &[]).await.unwrap();
+
+    let instance = ctx
+        .banks_client
+        .get_account_data_with_borsh::<Instance>(instance_pda)
+        .await
+        .unwrap();
+    assert_eq!(instance.components["hp"], ComponentValue::Integer(0));
+    assert_eq!(instance.components["lives"], ComponentValue::U8(1));
+    assert_eq!(instance.version, 6);
+
+    // out of range of u8
+    let ix = modify("lives", ComponentOp::Sub(ComponentValue::Integer(2)));
+    assert_custom_error(
+        send(&mut ctx, &[ix], &[]).await,
+        RushStoreError::InvalidOperation,
+    );
+
+    // deltas must be i64, bounds of the Component's type
+    let ix = modify("hp", ComponentOp::Add(ComponentValue::U8(1)));
+    assert_custom_error(
+        send(&mut ctx, &[ix], &[]).await,
+        RushStoreError::TypeMismatch,
+    );
+
+    let ix = modify("lives", ComponentOp::Add(ComponentValue::U8(1)));
+    assert_custom_error(
+        send(&mut ctx, &[ix], &[]).await,
+        RushStoreError::TypeMismatch,
+    );
+
+    let ix = modify("lives", ComponentOp::Max(ComponentValue::Integer(2)));
+    assert_custom_error(
+        send(&mut ctx, &[ix], &[]).await,
+        RushStoreError::TypeMismatch,
+    );
+
+    // not a number
+    let ix = modify("name", ComponentOp::Add(ComponentValue::String("!".into())));
+    assert_custom_error(
+        send(&mut ctx, &[ix], &[]).await,
+        RushStoreError::TypeMismatch,
+    );
+
+    let ix = modify("mp", ComponentOp::Add(ComponentValue::Integer(1)));
+    assert_custom_error(
+        send(&mut ctx, &[ix], &[]).await,
+        RushStoreError::ComponentNotFound,
+    );
+}
+
+/// Test operations on another authority's Instance and
+/// in a paused World
+#[tokio::test]
+async fn test_modify_component_unauthorized() {
+    let program_id = Pubkey::new_unique();
+    let mut program_test = ProgramTest::new(
+        // .so fixture is  retrieved from /target/deploy
+        "rush_ecs_store",
+        program_id,
+        // shank is incompatible with instantiating the BuiltInFunction
+        None,
+    );
+
+    // player isn't the World Authority
+    let player = Keypair::new();
+    program_test.add_account(
+        player.pubkey(),
+        Account {
+            lamports: 10_000_000_000,
+            owner: system_program::ID,
+            ..Account::default()
+        },
+    );
+    let mut ctx = program_test.start_with_context().await;
+    let world_authority = ctx.payer.insecure_clone();
+
+    let name = String::from("Sonic's World");
+    let description = String::from("This is Sonic's World");
+    let region = String::from("region1");
+    let entity = String::from("entity1");
+
+    let (world_pda, world_bump) = WorldPDA::find_pda(&program_id, &name, &description);
+    let ix = ix_create_world(
+        &program_id,
+        name,
+        description,
+        vec![region.clone()],
+        vec![entity.clone()],
+        world_bump,
+        &world_pda,
+        &world_authority.pubkey(),
+        &world_authority.pubkey(),
+    );
+    let ix2 = ix_launch_world(&program_id, &world_pda, &world_authority.pubkey());
+    send(&mut ctx, &[ix, ix2], &[]).await.unwrap();
+
+    let mut components: BTreeMap<Component, ComponentValue> = BTreeMap::new();
+    components.insert(String::from("hp"), ComponentValue::Integer(10));
+
+    let spawn = |nonce: u64, authority: &Pubkey| {
+        let (instance_pda, instance_bump) =
+            InstancePDA::find_pda(&program_id, &world_pda, &region, &entity, nonce);
+        let ix = ix_spawn_entity(
+            &program_id,
+            region.clone(),
+            entity.clone(),
+            components.clone(),
+            nonce,
+            instance_bump,
+            &instance_pda,
+            authority,
+            &world_pda,
+        );
+        (ix, instance_pda)
+    };
+    let modify = |instance: &Pubkey, authority: &Pubkey| {
+        ix_modify_component(
+            &program_id,
+            "hp".to_string(),
+            ComponentOp::Add(ComponentValue::Integer(1)),
+            instance,
+            authority,
+            &world_pda,
+        )
+    };
+
+    let (ix, authority_instance) = spawn(1, &world_authority.pubkey());
+    send(&mut ctx, &[ix], &[]).await.unwrap();
+    let (ix, player_instance) = spawn(2, &player.pubkey());
+    send(&mut ctx, &[ix], &[&player]).await.unwrap();
+
+    // only the Instance Authority can modify its Instance
+    let ix = modify(&authority_instance, &player.pubkey());
+    assert_custom_error(
+        send(&mut ctx, &[ix], &[&player]).await,
+        RushStoreError::InvalidInstanceAuthority,
+    );
+
+    let ix = modify(&player_instance, &world_authority.pubkey());
+    assert_custom_error(
+        send(&mut ctx, &[ix], &[]).await,
+        RushStoreError::InvalidInstanceAuthority,
+    );
+
+    let ix = ix_pause_world(&program_id, &world_pda, &world_authority.pubkey());
+    send(&mut ctx, &[ix], &[]).await.unwrap();
+
+    // paused, closed to everyone but World Authority
+    let ix = modify(&player_instance, &player.pubkey());
+    assert_custom_error(
+        send(&mut ctx, &[ix], &[&player]).await,
+        RushStoreError::WorldNotLaunched,
+    );
+
+    let ix = modify(&authority_instance, &world_authority.pubkey());
+    send(&mut ctx, &[ix], &[]).await.unwrap();
+
+    let instance = ctx
+        .banks_client
+        .get_account_data_with_borsh::<Instance>(player_instance)
+        .await
+        .unwrap();
+    assert_eq!(instance.components["hp"], ComponentValue::Integer(10));
+    assert_eq!(instance.version, 0);
+}
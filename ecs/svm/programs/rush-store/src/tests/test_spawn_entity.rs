@@ -3,12 +3,15 @@ use std::collections::BTreeMap;
 use rush_ecs_core::blueprint::{Component, ComponentValue};
 use rush_ecs_svm::{
     client::{ix_create_world, ix_spawn_entity},
+    error::RushStoreError,
     pda::{InstancePDA, WorldPDA},
     state::{Instance, World},
 };
 use solana_program_test::*;
 use solana_sdk::{pubkey::Pubkey, signature::Signer, transaction::Transaction};
 
+use super::{assert_custom_error, send};
+
 /// Spawn Entity
 ///
 /// - Creates a new account for Instance state
@@ -128,3 +131,67 @@ async fn test_spawn_entity() {
     assert_eq!(instance_state.instance_authority, ctx.payer.pubkey());
     assert_eq!(instance_state.bump, instance_bump);
 }
+
+/// Test unknown Region and Entity
+#[tokio::test]
+async fn test_spawn_entity_not_found() {
+    let program_id = Pubkey::new_unique();
+    let program_test = ProgramTest::new(
+        // .so fixture is  retrieved from /target/deploy
+        "rush_ecs_store",
+        program_id,
+        // shank is incompatible with instantiating the BuiltInFunction
+        None,
+    );
+    let mut ctx = program_test.start_with_context().await;
+
+    let name = String::from("Sonic's World");
+    let description = String::from("This is Sonic's World");
+    let (world_pda, world_bump) = WorldPDA::find_pda(&program_id, &name, &description);
+
+    let ix = ix_create_world(
+        &program_id,
+        name,
+        description,
+        vec!["region1".to_string()],
+        vec!["entity1".to_string()],
+        world_bump,
+        &world_pda,
+        &ctx.payer.pubkey(),
+        &ctx.payer.pubkey(),
+    );
+
+    let transaction = Transaction::new_signed_with_payer(
+        &[ix],
+        Some(&ctx.payer.pubkey()),
+        &[&ctx.payer.insecure_clone()],
+        ctx.last_blockhash,
+    );
+
+    ctx.banks_client
+        .process_transaction(transaction)
+        .await
+        .unwrap();
+
+    for (region, entity, error) in [
+        ("region2", "entity1", RushStoreError::RegionNotFound),
+        ("region1", "entity2", RushStoreError::EntityNotFound),
+    ] {
+        let (instance_pda, instance_bump) =
+            InstancePDA::find_pda(&program_id, &world_pda, region, entity, 1);
+
+        let ix = ix_spawn_entity(
+            &program_id,
+            region.to_string(),
+            entity.to_string(),
+            BTreeMap::new(),
+            1,
+            instance_bump,
+            &instance_pda,
+            &ctx.payer.pubkey(),
+            &world_pda,
+        );
+
+        assert_custom_error(send(&mut ctx, &[ix], &[]).await, error);
+    }
+}
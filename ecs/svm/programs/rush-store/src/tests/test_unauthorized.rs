@@ -0,0 +1,560 @@
+use rush_ecs_core::{
+    blueprint::{Component, ComponentValue},
+    numeric::ComponentOp,
+};
+use rush_ecs_svm::{
+    client::{
+        ix_batch_update, ix_create_world, ix_delete_world, ix_despawn_entity, ix_modify_component,
+        ix_spawn_entity, ix_update_entity, ix_update_world,
+    },
+    error::RushStoreError,
+    instruction::ComponentUpdate,
+    pda::{InstancePDA, WorldPDA},
+    state::{Instance, World},
+};
+use solana_program_test::*;
+use solana_sdk::{
+    account::Account,
+    instruction::Instruction,
+    pubkey::Pubkey,
+    signature::{Keypair, Signer},
+    system_program,
+    transaction::Transaction,
+};
+use std::collections::BTreeMap;
+
+use super::{assert_custom_error, send};
+
+/// World with a single spawned Instance, both owned by `authority`
+struct Fixture {
+    ctx: ProgramTestContext,
+    program_id: Pubkey,
+    authority: Keypair,
+    world_pda: Pubkey,
+    instance_pda: Pubkey,
+}
+
+fn get_program_test(program_id: Pubkey) -> ProgramTest {
+    ProgramTest::new(
+        // .so fixture is  retrieved from /target/deploy
+        "rush_ecs_store",
+        program_id,
+        // shank is incompatible with instantiating the BuiltInFunction
+        None,
+    )
+}
+
+fn get_components() -> BTreeMap<Component, ComponentValue> {
+    let mut components = BTreeMap::new();
+    components.insert(String::from("x"), ComponentValue::Integer(143));
+    components
+}
+
+async fn setup(mut program_test: ProgramTest, program_id: Pubkey) -> Fixture {
+    // authority isn't the payer, so it only signs when asked to
+    let authority = Keypair::new();
+    program_test.add_account(
+        authority.pubkey(),
+        Account {
+            lamports: 10_000_000_000,
+            owner: system_program::ID,
+            ..Account::default()
+        },
+    );
+    let ctx = program_test.start_with_context().await;
+
+    let name = String::from("Sonic's World");
+    let description = String::from("This is Sonic's World");
+    let (world_pda, world_bump) = WorldPDA::find_pda(&program_id, &name, &description);
+
+    let ix = ix_create_world(
+        &program_id,
+        name,
+        description,
+        vec!["region1".to_string()],
+        vec!["entity1".to_string()],
+        world_bump,
+        &world_pda,
+        &authority.pubkey(),
+        &ctx.payer.pubkey(),
+    );
+
+    let (instance_pda, instance_bump) =
+        InstancePDA::find_pda(&program_id, &world_pda, "region1", "entity1", 1);
+
+    let ix2 = ix_spawn_entity(
+        &program_id,
+        "region1".to_string(),
+        "entity1".to_string(),
+        get_components(),
+        1,
+        instance_bump,
+        &instance_pda,
+        &authority.pubkey(),
+        &world_pda,
+    );
+
+    let transaction = Transaction::new_signed_with_payer(
+        &[ix, ix2],
+        Some(&ctx.payer.pubkey()),
+        &[&ctx.payer.insecure_clone(), &authority],
+        ctx.last_blockhash,
+    );
+
+    ctx.banks_client
+        .process_transaction(transaction)
+        .await
+        .unwrap();
+
+    Fixture {
+        ctx,
+        program_id,
+        authority,
+        world_pda,
+        instance_pda,
+    }
+}
+
+/// Sends the instruction paid by the payer and asserts it fails with `error`
+async fn assert_rejected(
+    ctx: &mut ProgramTestContext,
+    ix: Instruction,
+    signers: &[&Keypair],
+    error: RushStoreError,
+) {
+    assert_custom_error(send(ctx, &[ix], signers).await, error);
+}
+
+/// Removes the signer flag from the authority account
+fn unsigned(mut ix: Instruction) -> Instruction {
+    ix.accounts[0].is_signer = false;
+    ix
+}
+
+#[tokio::test]
+async fn test_create_world_wrong_pda() {
+    let program_id = Pubkey::new_unique();
+    let mut ctx = get_program_test(program_id).start_with_context().await;
+
+    let name = String::from("Sonic's World");
+    let description = String::from("This is Sonic's World");
+    let (_, world_bump) = WorldPDA::find_pda(&program_id, &name, &description);
+    // PDA of another World
+    let (other_pda, _) = WorldPDA::find_pda(&program_id, "Other World", &description);
+
+    let ix = ix_create_world(
+        &program_id,
+        name,
+        description,
+        vec![],
+        vec![],
+        world_bump,
+        &other_pda,
+        &ctx.payer.pubkey(),
+        &ctx.payer.pubkey(),
+    );
+
+    assert_rejected(&mut ctx, ix, &[], RushStoreError::InvalidWorldPDA).await;
+}
+
+#[tokio::test]
+async fn test_update_world_unauthorized() {
+    let program_id = Pubkey::new_unique();
+    let mut f = setup(get_program_test(program_id), program_id).await;
+    let attacker = Keypair::new();
+
+    // authority didn't sign
+    let ix = ix_update_world(
+        &f.program_id,
+        vec![],
+        vec![],
+        vec![],
+        vec![],
+        false,
+        &f.world_pda,
+        &f.authority.pubkey(),
+    );
+    assert_rejected(
+        &mut f.ctx,
+        unsigned(ix),
+        &[],
+        RushStoreError::MissingSignature,
+    )
+    .await;
+
+    // signer isn't the World authority
+    let ix = ix_update_world(
+        &f.program_id,
+        vec![],
+        vec![],
+        vec![],
+        vec![],
+        false,
+        &f.world_pda,
+        &attacker.pubkey(),
+    );
+    assert_rejected(
+        &mut f.ctx,
+        ix,
+        &[&attacker],
+        RushStoreError::InvalidWorldAuthority,
+    )
+    .await;
+
+    // wrong System Program
+    let mut ix = ix_update_world(
+        &f.program_id,
+        vec![],
+        vec![],
+        vec![],
+        vec![],
+        false,
+        &f.world_pda,
+        &f.authority.pubkey(),
+    );
+    ix.accounts[2].pubkey = Pubkey::new_unique();
+    assert_rejected(
+        &mut f.ctx,
+        ix,
+        &[&f.authority],
+        RushStoreError::InvalidSystemProgram,
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn test_delete_world_unauthorized() {
+    let program_id = Pubkey::new_unique();
+    let mut f = setup(get_program_test(program_id), program_id).await;
+    let attacker = Keypair::new();
+
+    let ix = ix_delete_world(&f.program_id, &f.world_pda, &f.authority.pubkey());
+    assert_rejected(
+        &mut f.ctx,
+        unsigned(ix),
+        &[],
+        RushStoreError::MissingSignature,
+    )
+    .await;
+
+    let ix = ix_delete_world(&f.program_id, &f.world_pda, &attacker.pubkey());
+    assert_rejected(
+        &mut f.ctx,
+        ix,
+        &[&attacker],
+        RushStoreError::InvalidWorldAuthority,
+    )
+    .await;
+
+    // account not owned by the program
+    let ix = ix_delete_world(&f.program_id, &attacker.pubkey(), &f.authority.pubkey());
+    assert_rejected(
+        &mut f.ctx,
+        ix,
+        &[&f.authority],
+        RushStoreError::InvalidAccountOwner,
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn test_spawn_entity_unauthorized() {
+    let program_id = Pubkey::new_unique();
+    let mut f = setup(get_program_test(program_id), program_id).await;
+
+    let (instance_pda, instance_bump) =
+        InstancePDA::find_pda(&f.program_id, &f.world_pda, "region1", "entity1", 2);
+    let spawn = |instance: &Pubkey, world: &Pubkey, authority: &Pubkey| {
+        ix_spawn_entity(
+            &program_id,
+            "region1".to_string(),
+            "entity1".to_string(),
+            get_components(),
+            2,
+            instance_bump,
+            instance,
+            authority,
+            world,
+        )
+    };
+
+    let ix = spawn(&instance_pda, &f.world_pda, &f.authority.pubkey());
+    assert_rejected(
+        &mut f.ctx,
+        unsigned(ix),
+        &[],
+        RushStoreError::MissingSignature,
+    )
+    .await;
+
+    // Instance PDA of another nonce
+    let ix = spawn(&f.instance_pda, &f.world_pda, &f.authority.pubkey());
+    assert_rejected(
+        &mut f.ctx,
+        ix,
+        &[&f.authority],
+        RushStoreError::InvalidInstancePDA,
+    )
+    .await;
+
+    // World not owned by the program
+    let fake_world = Pubkey::new_unique();
+    let (fake_instance_pda, _) =
+        InstancePDA::find_pda(&f.program_id, &fake_world, "region1", "entity1", 2);
+    let ix = spawn(&fake_instance_pda, &fake_world, &f.authority.pubkey());
+    assert_rejected(
+        &mut f.ctx,
+        ix,
+        &[&f.authority],
+        RushStoreError::InvalidAccountOwner,
+    )
+    .await;
+
+    let mut ix = spawn(&instance_pda, &f.world_pda, &f.authority.pubkey());
+    ix.accounts[3].pubkey = Pubkey::new_unique();
+    assert_rejected(
+        &mut f.ctx,
+        ix,
+        &[&f.authority],
+        RushStoreError::InvalidSystemProgram,
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn test_update_entity_unauthorized() {
+    let program_id = Pubkey::new_unique();
+    let attacker = Keypair::new();
+
+    // Instance look-alike owned by the attacker
+    let mut program_test = get_program_test(program_id);
+    let (world_pda, _) = WorldPDA::find_pda(&program_id, "Sonic's World", "This is Sonic's World");
+    let fake_instance = Pubkey::new_unique();
+    let fake_data = borsh::to_vec(&Instance::new(
+        get_components(),
+        1,
+        attacker.pubkey(),
+        0,
+        world_pda,
+    ))
+    .unwrap();
+    program_test.add_account(
+        fake_instance,
+        Account {
+            lamports: 1_000_000_000,
+            data: fake_data,
+            owner: system_program::ID,
+            ..Account::default()
+        },
+    );
+
+    let mut f = setup(program_test, program_id).await;
+    let update = |instance: &Pubkey, authority: &Pubkey| {
+        ix_update_entity(
+            &program_id,
+            "x".to_string(),
+            ComponentValue::Integer(0),
+            instance,
+            authority,
+            &world_pda,
+        )
+    };
+
+    let ix = update(&f.instance_pda, &f.authority.pubkey());
+    assert_rejected(
+        &mut f.ctx,
+        unsigned(ix),
+        &[],
+        RushStoreError::MissingSignature,
+    )
+    .await;
+
+    let ix = update(&f.instance_pda, &attacker.pubkey());
+    assert_rejected(
+        &mut f.ctx,
+        ix,
+        &[&attacker],
+        RushStoreError::InvalidInstanceAuthority,
+    )
+    .await;
+
+    let ix = update(&fake_instance, &attacker.pubkey());
+    assert_rejected(
+        &mut f.ctx,
+        ix,
+        &[&attacker],
+        RushStoreError::InvalidAccountOwner,
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn test_despawn_entity_unauthorized() {
+    let program_id = Pubkey::new_unique();
+    let mut f = setup(get_program_test(program_id), program_id).await;
+    let attacker = Keypair::new();
+
+    let despawn = |instance: &Pubkey, world: &Pubkey, authority: &Pubkey| {
+        ix_despawn_entity(
+            &program_id,
+            "region1".to_string(),
+            "entity1".to_string(),
+            instance,
+            authority,
+            world,
+        )
+    };
+
+    let ix = despawn(&f.instance_pda, &f.world_pda, &f.authority.pubkey());
+    assert_rejected(
+        &mut f.ctx,
+        unsigned(ix),
+        &[],
+        RushStoreError::MissingSignature,
+    )
+    .await;
+
+    let ix = despawn(&f.instance_pda, &f.world_pda, &attacker.pubkey());
+    assert_rejected(
+        &mut f.ctx,
+        ix,
+        &[&attacker],
+        RushStoreError::InvalidInstanceAuthority,
+    )
+    .await;
+
+    // Instance isn't in this World, pass the Instance as its own World
+    let ix = despawn(&f.instance_pda, &f.instance_pda, &f.authority.pubkey());
+    assert_rejected(
+        &mut f.ctx,
+        ix,
+        &[&f.authority],
+        RushStoreError::InvalidInstancePDA,
+    )
+    .await;
+
+    let ix = despawn(&f.instance_pda, &attacker.pubkey(), &f.authority.pubkey());
+    assert_rejected(
+        &mut f.ctx,
+        ix,
+        &[&f.authority],
+        RushStoreError::InvalidAccountOwner,
+    )
+    .await;
+}
+
+/// A program owned copy of a World at another address
+/// passes the owner check, but not the PDA check
+#[tokio::test]
+async fn test_world_wrong_pda() {
+    let program_id = Pubkey::new_unique();
+    let mut program_test = get_program_test(program_id);
+
+    let authority = Keypair::new();
+    program_test.add_account(
+        authority.pubkey(),
+        Account {
+            lamports: 10_000_000_000,
+            owner: system_program::ID,
+            ..Account::default()
+        },
+    );
+
+    // seeds of a valid World, stored at another address
+    let (_, world_bump) = WorldPDA::find_pda(&program_id, "Sonic's World", "This is Sonic's World");
+    let world = World::new(
+        "Sonic's World".to_string(),
+        "This is Sonic's World".to_string(),
+        authority.pubkey(),
+        vec!["region1".to_string()],
+        vec!["entity1".to_string()],
+        world_bump,
+        true,
+    );
+    let fake_world = Pubkey::new_unique();
+    program_test.add_account(
+        fake_world,
+        Account {
+            lamports: 10_000_000_000,
+            data: borsh::to_vec(&world).unwrap(),
+            owner: program_id,
+            ..Account::default()
+        },
+    );
+
+    // Instance of the copy, at its PDA
+    let (instance_pda, instance_bump) =
+        InstancePDA::find_pda(&program_id, &fake_world, "region1", "entity1", 1);
+    let instance = Instance::new(
+        get_components(),
+        1,
+        authority.pubkey(),
+        instance_bump,
+        fake_world,
+    );
+    program_test.add_account(
+        instance_pda,
+        Account {
+            lamports: 10_000_000_000,
+            data: borsh::to_vec(&instance).unwrap(),
+            owner: program_id,
+            ..Account::default()
+        },
+    );
+    let mut ctx = program_test.start_with_context().await;
+
+    let (spawn_pda, spawn_bump) =
+        InstancePDA::find_pda(&program_id, &fake_world, "region1", "entity1", 2);
+    let ixs = [
+        ix_spawn_entity(
+            &program_id,
+            "region1".to_string(),
+            "entity1".to_string(),
+            get_components(),
+            2,
+            spawn_bump,
+            &spawn_pda,
+            &authority.pubkey(),
+            &fake_world,
+        ),
+        ix_update_entity(
+            &program_id,
+            "x".to_string(),
+            ComponentValue::Integer(1),
+            &instance_pda,
+            &authority.pubkey(),
+            &fake_world,
+        ),
+        ix_modify_component(
+            &program_id,
+            "x".to_string(),
+            ComponentOp::Add(ComponentValue::Integer(1)),
+            &instance_pda,
+            &authority.pubkey(),
+            &fake_world,
+        ),
+        ix_batch_update(
+            &program_id,
+            vec![ComponentUpdate {
+                instance: 0,
+                component: "x".to_string(),
+                value: ComponentValue::Integer(1),
+            }],
+            &[instance_pda],
+            &authority.pubkey(),
+            &fake_world,
+        ),
+        ix_despawn_entity(
+            &program_id,
+            "region1".to_string(),
+            "entity1".to_string(),
+            &instance_pda,
+            &authority.pubkey(),
+            &fake_world,
+        ),
+    ];
+
+    for ix in ixs {
+        assert_rejected(&mut ctx, ix, &[&authority], RushStoreError::InvalidWorldPDA).await;
+    }
+}
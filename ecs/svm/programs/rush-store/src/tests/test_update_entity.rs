@@ -2,13 +2,16 @@ use std::collections::BTreeMap;
 
 use rush_ecs_core::blueprint::{Component, ComponentValue};
 use rush_ecs_svm::{
-    client::{ix_create_world, ix_spawn_entity, ix_update_entity},
+    client::{ix_create_world, ix_spawn_entity, ix_update_entity, ix_update_entity_versioned},
+    error::RushStoreError,
     pda::{InstancePDA, WorldPDA},
     state::Instance,
 };
 use solana_program_test::*;
 use solana_sdk::{pubkey::Pubkey, signature::Signer, transaction::Transaction};
 
+use super::{assert_custom_error, send};
+
 /// Update Entity
 ///
 /// - Try data slice with Borsh1 Unchecked
@@ -54,7 +57,7 @@ async fn test_update_entity() {
     );
 
     let region = String::from("region1");
-    let entity = String::from("entity");
+    let entity = String::from("entity1");
     let mut components: BTreeMap<Component, ComponentValue> = BTreeMap::new();
     let value = ComponentValue::Integer(143);
     let new_value = ComponentValue::Integer(1337);
@@ -85,6 +88,7 @@ async fn test_update_entity() {
         new_value.clone(),
         &instance_pda,
         &ctx.payer.pubkey(),
+        &world_pda,
     );
 
     let transaction = Transaction::new_signed_with_payer(
@@ -116,4 +120,438 @@ async fn test_update_entity() {
     assert_eq!(state.nonce, nonce);
     assert_eq!(state.instance_authority, ctx.payer.pubkey());
     assert_eq!(state.bump, instance_bump);
+    assert_eq!(state.version, 1);
+}
+
+/// Test unknown Component and mismatched value type
+#[tokio::test]
+async fn test_update_entity_invalid_component() {
+    let program_id = Pubkey::new_unique();
+    let program_test = ProgramTest::new(
+        // .so fixture is  retrieved from /target/deploy
+        "rush_ecs_store",
+        program_id,
+        // shank is incompatible with instantiating the BuiltInFunction
+        None,
+    );
+    let mut ctx = program_test.start_with_context().await;
+
+    let name = String::from("Sonic's World");
+    let description = String::from("This is Sonic's World");
+    let region = String::from("region1");
+    let entity = String::from("entity1");
+
+    let (world_pda, world_bump) = WorldPDA::find_pda(&program_id, &name, &description);
+    let ix = ix_create_world(
+        &program_id,
+        name,
+        description,
+        vec![region.clone()],
+        vec![entity.clone()],
+        world_bump,
+        &world_pda,
+        &ctx.payer.pubkey(),
+        &ctx.payer.pubkey(),
+    );
+
+    let mut components: BTreeMap<Component, ComponentValue> = BTreeMap::new();
+    components.insert(String::from("x"), ComponentValue::Integer(143));
+    let nonce = 1;
+
+    let (instance_pda, instance_bump) =
+        InstancePDA::find_pda(&program_id, &world_pda, &region, &entity, nonce);
+
+    let ix2 = ix_spawn_entity(
+        &program_id,
+        region,
+        entity,
+        components,
+        nonce,
+        instance_bump,
+        &instance_pda,
+        &ctx.payer.pubkey(),
+        &world_pda,
+    );
+
+    let transaction = Transaction::new_signed_with_payer(
+        &[ix, ix2],
+        Some(&ctx.payer.pubkey()),
+        &[&ctx.payer.insecure_clone()],
+        ctx.last_blockhash,
+    );
+
+    ctx.banks_client
+        .process_transaction(transaction)
+        .await
+        .unwrap();
+
+    for (component, value, error) in [
+        (
+            "z",
+            ComponentValue::Integer(1),
+            RushStoreError::ComponentNotFound,
+        ),
+        (
+            "x",
+            ComponentValue::String("1337".to_string()),
+            RushStoreError::TypeMismatch,
+        ),
+        ("x", ComponentValue::U64(1337), RushStoreError::TypeMismatch),
+    ] {
+        let ix = ix_update_entity(
+            &program_id,
+            component.to_string(),
+            value,
+            &instance_pda,
+            &ctx.payer.pubkey(),
+            &world_pda,
+        );
+
+        assert_custom_error(send(&mut ctx, &[ix], &[]).await, error);
+    }
+}
+
+/// Test Structs holding empty collections and mixed Lists
+#[tokio::test]
+async fn test_update_entity_nested_types() {
+    let program_id = Pubkey::new_unique();
+    let program_test = ProgramTest::new(
+        // .so fixture is  retrieved from /target/deploy
+        "rush_ecs_store",
+        program_id,
+        // shank is incompatible with instantiating the BuiltInFunction
+        None,
+    );
+    let mut ctx = program_test.start_with_context().await;
+
+    let name = String::from("Sonic's World");
+    let description = String::from("This is Sonic's World");
+    let region = String::from("region1");
+    let entity = String::from("entity1");
+
+    let (world_pda, world_bump) = WorldPDA::find_pda(&program_id, &name, &description);
+    let ix = ix_create_world(
+        &program_id,
+        name,
+        description,
+        vec![region.clone()],
+        vec![entity.clone()],
+        world_bump,
+        &world_pda,
+        &ctx.payer.pubkey(),
+        &ctx.payer.pubkey(),
+    );
+
+    let profile = |name: &str, tags: Vec<ComponentValue>| {
+        ComponentValue::Struct(BTreeMap::from([
+            ("name".to_string(), ComponentValue::String(name.to_string())),
+            ("tags".to_string(), ComponentValue::List(tags)),
+        ]))
+    };
+
+    // neither infers a type, their collections are empty
+    let mut components: BTreeMap<Component, ComponentValue> = BTreeMap::new();
+    components.insert(String::from("profile"), profile("a", vec![]));
+    components.insert(String::from("items"), ComponentValue::List(vec![]));
+    let nonce = 1;
+
+    let (instance_pda, instance_bump) =
+        InstancePDA::find_pda(&program_id, &world_pda, &region, &entity, nonce);
+    let ix2 = ix_spawn_entity(
+        &program_id,
+        region,
+        entity,
+        components,
+        nonce,
+        instance_bump,
+        &instance_pda,
+        &ctx.payer.pubkey(),
+        &world_pda,
+    );
+    send(&mut ctx, &[ix, ix2], &[]).await.unwrap();
+
+    let payer = ctx.payer.pubkey();
+    let update = |component: &str, value: ComponentValue| {
+        ix_update_entity(
+            &program_id,
+            component.to_string(),
+            value,
+            &instance_pda,
+            &payer,
+            &world_pda,
+        )
+    };
+    let mixed = ComponentValue::List(vec![
+        ComponentValue::Integer(1),
+        ComponentValue::String("1".to_string()),
+    ]);
+
+    for (component, value) in [
+        // other fields
+        (
+            "profile",
+            ComponentValue::Struct(BTreeMap::from([(
+                "level".to_string(),
+                ComponentValue::Integer(1),
+            )])),
+        ),
+        // name keeps its type
+        (
+            "profile",
+            ComponentValue::Struct(BTreeMap::from([
+                ("name".to_string(), ComponentValue::Integer(1)),
+                ("tags".to_string(), ComponentValue::List(vec![])),
+            ])),
+        ),
+        ("profile", profile("a", mixed.clone().unwrap_list())),
+        ("items", mixed.clone()),
+        ("items", ComponentValue::Integer(1)),
+    ] {
+        let ix = update(component, value);
+        assert_custom_error(
+            send(&mut ctx, &[ix], &[]).await,
+            RushStoreError::TypeMismatch,
+        );
+    }
+
+    // empty collections take elements of a single type
+    let tags = vec![ComponentValue::String("fast".to_string())];
+    let items = ComponentValue::List(vec![ComponentValue::Integer(1), ComponentValue::Integer(2)]);
+    let ixs = [
+        update("profile", profile("b", tags.clone())),
+        update("items", items.clone()),
+    ];
+    send(&mut ctx, &ixs, &[]).await.unwrap();
+
+    let state = ctx
+        .banks_client
+        .get_account_data_with_borsh::<Instance>(instance_pda)
+        .await
+        .unwrap();
+    assert_eq!(state.components["profile"], profile("b", tags));
+    assert_eq!(state.components["items"], items);
+}
+
+/// Test growing and shrinking the Instance account
+#[tokio::test]
+async fn test_update_entity_realloc() {
+    let program_id = Pubkey::new_unique();
+    let program_test = ProgramTest::new(
+        // .so fixture is  retrieved from /target/deploy
+        "rush_ecs_store",
+        program_id,
+        // shank is incompatible with instantiating the BuiltInFunction
+        None,
+    );
+    let mut ctx = program_test.start_with_context().await;
+
+    let name = String::from("Sonic's World");
+    let description = String::from("This is Sonic's World");
+    let region = String::from("region1");
+    let entity = String::from("entity1");
+
+    let (world_pda, world_bump) = WorldPDA::find_pda(&program_id, &name, &description);
+    let ix = ix_create_world(
+        &program_id,
+        name,
+        description,
+        vec![region.clone()],
+        vec![entity.clone()],
+        world_bump,
+        &world_pda,
+        &ctx.payer.pubkey(),
+        &ctx.payer.pubkey(),
+    );
+
+    let name_comp = String::from("name");
+    let mut components: BTreeMap<Component, ComponentValue> = BTreeMap::new();
+    components.insert(name_comp.clone(), ComponentValue::String("Bob".to_string()));
+    let nonce = 1;
+
+    let (instance_pda, instance_bump) =
+        InstancePDA::find_pda(&program_id, &world_pda, &region, &entity, nonce);
+
+    let ix2 = ix_spawn_entity(
+        &program_id,
+        region,
+        entity,
+        components,
+        nonce,
+        instance_bump,
+        &instance_pda,
+        &ctx.payer.pubkey(),
+        &world_pda,
+    );
+
+    let transaction = Transaction::new_signed_with_payer(
+        &[ix, ix2],
+        Some(&ctx.payer.pubkey()),
+        &[&ctx.payer.insecure_clone()],
+        ctx.last_blockhash,
+    );
+
+    ctx.banks_client
+        .process_transaction(transaction)
+        .await
+        .unwrap();
+
+    let rent = ctx.banks_client.get_rent().await.unwrap();
+    let spawned_len = ctx
+        .banks_client
+        .get_account(instance_pda)
+        .await
+        .unwrap()
+        .unwrap()
+        .data
+        .len();
+
+    // grow name from 3 to 200 bytes, then shrink it back
+    for (value, len) in [
+        ("a".repeat(200), spawned_len + 197),
+        ("Bob".to_string(), spawned_len),
+    ] {
+        let ix = ix_update_entity(
+            &program_id,
+            name_comp.clone(),
+            ComponentValue::String(value.clone()),
+            &instance_pda,
+            &ctx.payer.pubkey(),
+            &world_pda,
+        );
+
+        let transaction = Transaction::new_signed_with_payer(
+            &[ix],
+            Some(&ctx.payer.pubkey()),
+            &[&ctx.payer.insecure_clone()],
+            ctx.get_new_latest_blockhash().await.unwrap(),
+        );
+
+        ctx.banks_client
+            .process_transaction(transaction)
+            .await
+            .unwrap();
+
+        let account = ctx
+            .banks_client
+            .get_account(instance_pda)
+            .await
+            .unwrap()
+            .unwrap();
+
+        // resized and rent-exempt with no excess
+        assert_eq!(account.data.len(), len);
+        assert_eq!(account.lamports, rent.minimum_balance(len));
+
+        let state = ctx
+            .banks_client
+            .get_account_data_with_borsh::<Instance>(instance_pda)
+            .await
+            .unwrap();
+
+        assert_eq!(state.components[&name_comp], ComponentValue::String(value));
+    }
+}
+
+/// Test compare-and-set with the Instance version
+#[tokio::test]
+async fn test_update_entity_versioned() {
+    let program_id = Pubkey::new_unique();
+    let program_test = ProgramTest::new(
+        // .so fixture is  retrieved from /target/deploy
+        "rush_ecs_store",
+        program_id,
+        // shank is incompatible with instantiating the BuiltInFunction
+        None,
+    );
+    let mut ctx = program_test.start_with_context().await;
+
+    let name = String::from("Sonic's World");
+    let description = String::from("This is Sonic's World");
+    let region = String::from("region1");
+    let entity = String::from("entity1");
+
+    let (world_pda, world_bump) = WorldPDA::find_pda(&program_id, &name, &description);
+    let ix = ix_create_world(
+        &program_id,
+        name,
+        description,
+        vec![region.clone()],
+        vec![entity.clone()],
+        world_bump,
+        &world_pda,
+        &ctx.payer.pubkey(),
+        &ctx.payer.pubkey(),
+    );
+
+    let mut components: BTreeMap<Component, ComponentValue> = BTreeMap::new();
+    components.insert(String::from("x"), ComponentValue::Integer(143));
+    let nonce = 1;
+
+    let (instance_pda, instance_bump) =
+        InstancePDA::find_pda(&program_id, &world_pda, &region, &entity, nonce);
+
+    let ix2 = ix_spawn_entity(
+        &program_id,
+        region,
+        entity,
+        components,
+        nonce,
+        instance_bump,
+        &instance_pda,
+        &ctx.payer.pubkey(),
+        &world_pda,
+    );
+
+    let transaction = Transaction::new_signed_with_payer(
+        &[ix, ix2],
+        Some(&ctx.payer.pubkey()),
+        &[&ctx.payer.insecure_clone()],
+        ctx.last_blockhash,
+    );
+
+    ctx.banks_client
+        .process_transaction(transaction)
+        .await
+        .unwrap();
+
+    let state = ctx
+        .banks_client
+        .get_account_data_with_borsh::<Instance>(instance_pda)
+        .await
+        .unwrap();
+    assert_eq!(state.version, 0);
+
+    ctx.warp_to_slot(100).unwrap();
+
+    let update = |value: i64, expected_version: u64| {
+        ix_update_entity_versioned(
+            &program_id,
+            "x".to_string(),
+            ComponentValue::Integer(value),
+            expected_version,
+            &instance_pda,
+            &ctx.payer.pubkey(),
+            &world_pda,
+        )
+    };
+
+    // both clients read version 0, only the first one wins
+    let ix = update(1, 0);
+    let ix2 = update(2, 0);
+
+    send(&mut ctx, &[ix], &[]).await.unwrap();
+
+    let state = ctx
+        .banks_client
+        .get_account_data_with_borsh::<Instance>(instance_pda)
+        .await
+        .unwrap();
+    assert_eq!(state.components["x"], ComponentValue::Integer(1));
+    assert_eq!(state.version, 1);
+    assert!(state.last_update_slot >= 100);
+
+    assert_custom_error(
+        send(&mut ctx, &[ix2], &[]).await,
+        RushStoreError::VersionMismatch,
+    );
 }
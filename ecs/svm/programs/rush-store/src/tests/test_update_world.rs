@@ -1,26 +1,36 @@
+use rush_ecs_core::blueprint::ComponentValue;
 use rush_ecs_svm::{
-    client::{ix_create_world, ix_update_world},
-    pda::WorldPDA,
+    client::{ix_create_world, ix_despawn_entity, ix_spawn_entity, ix_update_world},
+    error::RushStoreError,
+    pda::{InstancePDA, WorldPDA},
     state::World,
 };
 use solana_program_test::*;
 use solana_sdk::{pubkey::Pubkey, signature::Signer, transaction::Transaction};
+use std::collections::BTreeMap;
+
+use super::{assert_custom_error, send};
 
 /// Update World
 ///
 /// - Try data slice with Borsh1 Unchecked
 /// - Update World State PDA with new entities / regions
+/// - Start tracking Instances of new entities / regions
 ///
 /// Accounts
 /// 0. `[SIGNER]`       World Authority
 /// 1. `[WRITE]`        World PDA
 ///
 /// Instruction Data
-/// - regions: Vec<Region>,
-/// - entities: Vec<Entity>
+/// - add_regions: Vec<Region>,
+/// - remove_regions: Vec<Region>,
+/// - add_entities: Vec<Entity>,
+/// - remove_entities: Vec<Entity>,
+/// - force: bool
 ///
 /// Data Validations
-/// -
+/// - Removed regions / entities have no live Instances,
+///   unless `force` is set
 ///
 
 /// Test Happy Path
@@ -56,22 +66,14 @@ async fn test_update_world() {
         &ctx.payer.pubkey(),
     );
 
-    let regions2 = vec![
-        "region1".to_string(),
-        "region2".to_string(),
-        "region3".to_string(),
-    ];
-    let entities2 = vec![
-        "entity1".to_string(),
-        "entity2".to_string(),
-        "entity3".to_string(),
-    ];
-
     // UpdateWorld
     let ix2 = ix_update_world(
         &program_id,
-        regions2.clone(),
-        entities2.clone(),
+        vec!["region3".to_string()],
+        vec!["region2".to_string()],
+        vec!["entity3".to_string()],
+        vec![],
+        false,
         &world_pda,
         &ctx.payer.pubkey(),
     );
@@ -98,9 +100,341 @@ async fn test_update_world() {
 
     assert_eq!(state.name, name);
     assert_eq!(state.description, description);
-    assert_eq!(state.regions, regions2);
-    assert_eq!(state.entities, entities2);
+    assert_eq!(state.regions, vec!["region1", "region3"]);
+    assert_eq!(state.entities, vec!["entity1", "entity2", "entity3"]);
+    // new regions and entities can spawn instances
+    assert_eq!(state.instances["region3"]["entity1"], 0);
+    assert_eq!(state.instances["region1"]["entity3"], 0);
+    assert_eq!(state.live["region3"]["entity3"], 0);
+    // removed regions keep their last nonce, nothing is live
+    assert_eq!(state.instances["region2"]["entity1"], 0);
+    assert!(!state.live.contains_key("region2"));
     assert_eq!(state.world_authority, ctx.payer.pubkey());
     assert_eq!(state.bump, world_bump);
     assert!(!state.is_launched);
 }
+
+/// Test removing regions and entities with live Instances
+#[tokio::test]
+async fn test_update_world_live_instances() {
+    let program_id = Pubkey::new_unique();
+    let program_test = ProgramTest::new(
+        // .so fixture is  retrieved from /target/deploy
+        "rush_ecs_store",
+        program_id,
+        // shank is incompatible with instantiating the BuiltInFunction
+        None,
+    );
+    let mut ctx = program_test.start_with_context().await;
+
+    let name = String::from("Sonic's World");
+    let description = String::from("This is Sonic's World");
+    let (region1, region2) = ("region1".to_string(), "region2".to_string());
+    let (entity1, entity2) = ("entity1".to_string(), "entity2".to_string());
+
+    let (world_pda, world_bump) = WorldPDA::find_pda(&program_id, &name, &description);
+    let ix = ix_create_world(
+        &program_id,
+        name,
+        description,
+        vec![region1.clone(), region2.clone()],
+        vec![entity1.clone(), entity2.clone()],
+        world_bump,
+        &world_pda,
+        &ctx.payer.pubkey(),
+        &ctx.payer.pubkey(),
+    );
+    send(&mut ctx, &[ix], &[]).await.unwrap();
+
+    // live Instances in region1 and region2
+    let mut components = BTreeMap::new();
+    components.insert("x".to_string(), ComponentValue::Integer(143));
+    let mut instance_pdas = Vec::new();
+    for region in [&region1, &region2] {
+        let (instance_pda, instance_bump) =
+            InstancePDA::find_pda(&program_id, &world_pda, region, &entity1, 1);
+        let ix = ix_spawn_entity(
+            &program_id,
+            region.clone(),
+            entity1.clone(),
+            components.clone(),
+            1,
+            instance_bump,
+            &instance_pda,
+            &ctx.payer.pubkey(),
+            &world_pda,
+        );
+        send(&mut ctx, &[ix], &[]).await.unwrap();
+        instance_pdas.push(instance_pda);
+    }
+
+    let payer = ctx.payer.pubkey();
+    let update = |remove_regions: Vec<String>, remove_entities: Vec<String>, force: bool| {
+        ix_update_world(
+            &program_id,
+            vec![],
+            remove_regions,
+            vec![],
+            remove_entities,
+            force,
+            &world_pda,
+            &payer,
+        )
+    };
+
+    for (ix, error) in [
+        (
+            update(vec![region1.clone()], vec![], false),
+            RushStoreError::RegionHasInstances,
+        ),
+        (
+            update(vec![], vec![entity1.clone()], false),
+            RushStoreError::EntityHasInstances,
+        ),
+        (
+            update(vec!["region3".to_string()], vec![], false),
+            RushStoreError::RegionNotFound,
+        ),
+    ] {
+        assert_custom_error(send(&mut ctx, &[ix], &[]).await, error);
+    }
+
+    // entity2 has no Instances
+    send(
+        &mut ctx,
+        &[update(vec![], vec![entity2.clone()], false)],
+        &[],
+    )
+    .await
+    .unwrap();
+
+    // despawned Instances aren't live
+    let ix = ix_despawn_entity(
+        &program_id,
+        region2.clone(),
+        entity1.clone(),
+        &instance_pdas[1],
+        &ctx.payer.pubkey(),
+        &world_pda,
+    );
+    send(&mut ctx, &[ix], &[]).await.unwrap();
+    send(
+        &mut ctx,
+        &[update(vec![region2.clone()], vec![], false)],
+        &[],
+    )
+    .await
+    .unwrap();
+
+    // forced, the Instance account is left behind
+    send(
+        &mut ctx,
+        &[update(vec![region1.clone()], vec![], true)],
+        &[],
+    )
+    .await
+    .unwrap();
+
+    let state = ctx
+        .banks_client
+        .get_account_data_with_borsh::<World>(world_pda)
+        .await
+        .unwrap();
+
+    assert!(state.regions.is_empty());
+    assert_eq!(state.entities, vec![entity1.clone()]);
+    // last nonces are kept, nothing is live
+    assert_eq!(state.instances[&region1][&entity1], 1);
+    assert_eq!(state.instances[&region2][&entity1], 1);
+    assert_eq!(state.despawned[&region2][&entity1], 1);
+    assert!(state.live.is_empty());
+
+    // despawning the left behind Instance doesn't track it again
+    let ix = ix_despawn_entity(
+        &program_id,
+        region1.clone(),
+        entity1.clone(),
+        &instance_pdas[0],
+        &ctx.payer.pubkey(),
+        &world_pda,
+    );
+    send(&mut ctx, &[ix], &[]).await.unwrap();
+
+    let world_len = ctx
+        .banks_client
+        .get_account(world_pda)
+        .await
+        .unwrap()
+        .unwrap()
+        .data
+        .len();
+    let mut expected = state;
+    expected
+        .despawned
+        .entry(region1.clone())
+        .or_default()
+        .insert(entity1.clone(), 1);
+    assert_eq!(world_len, borsh::to_vec(&expected).unwrap().len());
+
+    let state = ctx
+        .banks_client
+        .get_account_data_with_borsh::<World>(world_pda)
+        .await
+        .unwrap();
+    assert_eq!(state.despawned[&region1][&entity1], 1);
+    assert!(state.live.is_empty());
+}
+
+/// Test re-adding removed regions and entities doesn't reuse nonces
+#[tokio::test]
+async fn test_update_world_keeps_nonces() {
+    let program_id = Pubkey::new_unique();
+    let program_test = ProgramTest::new(
+        // .so fixture is  retrieved from /target/deploy
+        "rush_ecs_store",
+        program_id,
+        // shank is incompatible with instantiating the BuiltInFunction
+        None,
+    );
+    let mut ctx = program_test.start_with_context().await;
+
+    let name = String::from("Sonic's World");
+    let description = String::from("This is Sonic's World");
+    let region = "region1".to_string();
+    let entity = "entity1".to_string();
+
+    let (world_pda, world_bump) = WorldPDA::find_pda(&program_id, &name, &description);
+    let ix = ix_create_world(
+        &program_id,
+        name,
+        description,
+        vec![region.clone()],
+        vec![entity.clone()],
+        world_bump,
+        &world_pda,
+        &ctx.payer.pubkey(),
+        &ctx.payer.pubkey(),
+    );
+    send(&mut ctx, &[ix], &[]).await.unwrap();
+
+    let mut components = BTreeMap::new();
+    components.insert("x".to_string(), ComponentValue::Integer(143));
+    let payer = ctx.payer.pubkey();
+    let spawn = |nonce: u64| {
+        let (instance_pda, instance_bump) =
+            InstancePDA::find_pda(&program_id, &world_pda, &region, &entity, nonce);
+        ix_spawn_entity(
+            &program_id,
+            region.clone(),
+            entity.clone(),
+            components.clone(),
+            nonce,
+            instance_bump,
+            &instance_pda,
+            &payer,
+            &world_pda,
+        )
+    };
+    let despawn = |nonce: u64| {
+        let (instance_pda, _) =
+            InstancePDA::find_pda(&program_id, &world_pda, &region, &entity, nonce);
+        ix_despawn_entity(
+            &program_id,
+            region.clone(),
+            entity.clone(),
+            &instance_pda,
+            &payer,
+            &world_pda,
+        )
+    };
+    let update = |add_regions: Vec<String>,
+                  remove_regions: Vec<String>,
+                  add_entities: Vec<String>,
+                  remove_entities: Vec<String>| {
+        ix_update_world(
+            &program_id,
+            add_regions,
+            remove_regions,
+            add_entities,
+            remove_entities,
+            false,
+            &world_pda,
+            &payer,
+        )
+    };
+
+    // spawn and despawn #1 and #2, then remove the region
+    for nonce in [1, 2] {
+        send(&mut ctx, &[spawn(nonce)], &[]).await.unwrap();
+        send(&mut ctx, &[despawn(nonce)], &[]).await.unwrap();
+    }
+    send(
+        &mut ctx,
+        &[update(vec![], vec![region.clone()], vec![], vec![])],
+        &[],
+    )
+    .await
+    .unwrap();
+
+    // removed regions can't spawn
+    assert_custom_error(
+        send(&mut ctx, &[spawn(3)], &[]).await,
+        RushStoreError::RegionNotFound,
+    );
+
+    // re-added, the region keeps its last nonce
+    send(
+        &mut ctx,
+        &[update(vec![region.clone()], vec![], vec![], vec![])],
+        &[],
+    )
+    .await
+    .unwrap();
+    let state = ctx
+        .banks_client
+        .get_account_data_with_borsh::<World>(world_pda)
+        .await
+        .unwrap();
+    assert_eq!(state.instances[&region][&entity], 2);
+    assert_eq!(state.live[&region][&entity], 0);
+
+    assert_custom_error(
+        send(&mut ctx, &[spawn(1)], &[]).await,
+        RushStoreError::NonceAlreadyUsed,
+    );
+    send(&mut ctx, &[spawn(3)], &[]).await.unwrap();
+
+    // same for entities
+    send(&mut ctx, &[despawn(3)], &[]).await.unwrap();
+    send(
+        &mut ctx,
+        &[update(vec![], vec![], vec![], vec![entity.clone()])],
+        &[],
+    )
+    .await
+    .unwrap();
+    assert_custom_error(
+        send(&mut ctx, &[spawn(4)], &[]).await,
+        RushStoreError::EntityNotFound,
+    );
+    send(
+        &mut ctx,
+        &[update(vec![], vec![], vec![entity.clone()], vec![])],
+        &[],
+    )
+    .await
+    .unwrap();
+    assert_custom_error(
+        send(&mut ctx, &[spawn(3)], &[]).await,
+        RushStoreError::NonceAlreadyUsed,
+    );
+    send(&mut ctx, &[spawn(4)], &[]).await.unwrap();
+
+    let state = ctx
+        .banks_client
+        .get_account_data_with_borsh::<World>(world_pda)
+        .await
+        .unwrap();
+    assert_eq!(state.instances[&region][&entity], 4);
+    assert_eq!(state.live[&region][&entity], 1);
+}
@@ -1,5 +1,4 @@
 mod proxy;
 mod store;
 
-pub use proxy::*;
 pub use store::*;
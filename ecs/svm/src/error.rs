@@ -1,4 +1,5 @@
 use num_derive::FromPrimitive;
+use num_traits::FromPrimitive;
 use solana_program::{
     decode_error::DecodeError,
     msg,
@@ -10,6 +11,55 @@ use thiserror::Error;
 pub enum RushStoreError {
     #[error("invalid account data length")]
     InvalidAccountDataLength, // 0
+    #[error("authority must sign the transaction")]
+    MissingSignature, // 1
+    #[error("signer is not the World authority")]
+    InvalidWorldAuthority, // 2
+    #[error("signer is not the Instance authority")]
+    InvalidInstanceAuthority, // 3
+    #[error("account is not owned by the store program")]
+    InvalidAccountOwner, // 4
+    #[error("World account doesn't match its PDA seeds")]
+    InvalidWorldPDA, // 5
+    #[error("Instance account doesn't match its PDA seeds")]
+    InvalidInstancePDA, // 6
+    #[error("invalid System Program account")]
+    InvalidSystemProgram, // 7
+    #[error("region not found in World")]
+    RegionNotFound, // 8
+    #[error("entity not found in World")]
+    EntityNotFound, // 9
+    #[error("component not found in Instance")]
+    ComponentNotFound, // 10
+    #[error("value doesn't match the component's type")]
+    TypeMismatch, // 11
+    #[error("World is not launched yet")]
+    WorldNotLaunched, // 12
+    #[error("World not found or deleted")]
+    WorldNotFound, // 13
+    #[error("Instance not found or despawned")]
+    InstanceNotFound, // 14
+    #[error("nonce must be greater than the last nonce spawned")]
+    NonceAlreadyUsed, // 15
+    #[error("Instance wasn't spawned in this World")]
+    InstanceNotInWorld, // 16
+    #[error("region still has live Instances")]
+    RegionHasInstances, // 17
+    #[error("entity still has live Instances")]
+    EntityHasInstances, // 18
+    #[error("operation overflowed or isn't valid for the component")]
+    InvalidOperation, // 19
+    #[error("Instance version doesn't match the expected version")]
+    VersionMismatch, // 20
+}
+
+impl RushStoreError {
+    /// Decode the code of a [`ProgramError::Custom`]
+    ///
+    /// Returns `None` if the code isn't a [`RushStoreError`]
+    pub fn from_code(code: u32) -> Option<Self> {
+        Self::from_u32(code)
+    }
 }
 
 // allow .into() for Custom Error to ProgramError conversion
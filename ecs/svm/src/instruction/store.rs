@@ -1,19 +1,24 @@
 use std::collections::BTreeMap;
 
 use borsh::{BorshDeserialize, BorshSerialize};
-use rush_ecs_core::blueprint::{Component, ComponentValue, Entity, Region};
+use rush_ecs_core::{
+    blueprint::{Component, ComponentValue, Entity, Region},
+    numeric::ComponentOp,
+};
 use shank::{ShankContext, ShankInstruction};
 
 /// RushStore Instruction List
 ///
 /// For World Authority:
-/// - CreateWorld, UpdateWorld, DeleteWorld
+/// - CreateWorld, UpdateWorld, DeleteWorld, LaunchWorld, PauseWorld
 ///
 /// For Region Authority:
-/// - UpdateEntity, DespawnEntity
+/// - UpdateEntity, UpdateEntityVersioned, ModifyComponent, BatchUpdate,
+///   DespawnEntity
 ///
 /// For Entity Authority:
-/// - SpawnEntity, UpdateEntity, DespawnEntity
+/// - SpawnEntity, UpdateEntity, UpdateEntityVersioned, ModifyComponent,
+///   BatchUpdate, DespawnEntity
 ///
 #[derive(
     BorshDeserialize, BorshSerialize, Clone, Debug, Eq, PartialEq, ShankContext, ShankInstruction,
@@ -42,6 +47,7 @@ pub enum RushStoreInstruction {
 
     #[account(
         0,
+        writable,
         signer,
         name = "world_authority",
         desc = "World authority who has access to World state changing operations"
@@ -49,8 +55,12 @@ pub enum RushStoreInstruction {
     #[account(1, writable, name = "world", desc = "World State PDA")]
     #[account(2, name = "system_program", desc = "System Program")]
     UpdateWorld {
-        regions: Vec<Region>,
-        entities: Vec<Entity>,
+        add_regions: Vec<Region>,
+        remove_regions: Vec<Region>,
+        add_entities: Vec<Entity>,
+        remove_entities: Vec<Entity>,
+        /// Remove regions and entities that still have live Instances
+        force: bool,
     },
 
     #[account(
@@ -81,11 +91,14 @@ pub enum RushStoreInstruction {
 
     #[account(
         0,
+        writable,
         signer,
         name = "instance_authority",
         desc = "Instance authority who has access to Instance state changing operations"
     )]
     #[account(1, writable, name = "instance", desc = "Instance State PDA")]
+    #[account(2, name = "system_program", desc = "System Program")]
+    #[account(3, name = "world", desc = "World State PDA")]
     UpdateEntity {
         component: Component,
         value: ComponentValue,
@@ -93,10 +106,79 @@ pub enum RushStoreInstruction {
 
     #[account(
         0,
+        writable,
         signer,
         name = "instance_authority",
         desc = "Instance authority who has access to Instance state changing operations"
     )]
     #[account(1, writable, name = "instance", desc = "Instance State PDA")]
-    DespawnEntity,
+    #[account(2, writable, name = "world", desc = "World State PDA")]
+    DespawnEntity { region: Region, entity: Entity },
+
+    #[account(
+        0,
+        signer,
+        name = "world_authority",
+        desc = "World authority who has access to World state changing operations"
+    )]
+    #[account(1, writable, name = "world", desc = "World State PDA")]
+    LaunchWorld,
+
+    #[account(
+        0,
+        signer,
+        name = "world_authority",
+        desc = "World authority who has access to World state changing operations"
+    )]
+    #[account(1, writable, name = "world", desc = "World State PDA")]
+    PauseWorld,
+
+    #[account(
+        0,
+        signer,
+        name = "instance_authority",
+        desc = "Instance authority who has access to Instance state changing operations"
+    )]
+    #[account(1, writable, name = "instance", desc = "Instance State PDA")]
+    #[account(2, name = "world", desc = "World State PDA")]
+    ModifyComponent {
+        component: Component,
+        op: ComponentOp,
+    },
+
+    #[account(
+        0,
+        writable,
+        signer,
+        name = "instance_authority",
+        desc = "Instance authority who has access to Instance state changing operations"
+    )]
+    #[account(1, writable, name = "instance", desc = "Instance State PDA")]
+    #[account(2, name = "system_program", desc = "System Program")]
+    #[account(3, name = "world", desc = "World State PDA")]
+    UpdateEntityVersioned {
+        component: Component,
+        value: ComponentValue,
+        expected_version: u64,
+    },
+
+    #[account(
+        0,
+        writable,
+        signer,
+        name = "instance_authority",
+        desc = "Instance authority who has access to Instance state changing operations"
+    )]
+    #[account(1, name = "system_program", desc = "System Program")]
+    #[account(2, name = "world", desc = "World State PDA")]
+    BatchUpdate { updates: Vec<ComponentUpdate> },
+}
+
+/// Component value set by [`RushStoreInstruction::BatchUpdate`]
+#[derive(BorshDeserialize, BorshSerialize, Clone, Debug, Eq, PartialEq)]
+pub struct ComponentUpdate {
+    /// Index of the Instance account in the remaining accounts
+    pub instance: u8,
+    pub component: Component,
+    pub value: ComponentValue,
 }
@@ -35,6 +35,15 @@ pub struct Instance {
 
     /// Canonical bump for Instances
     pub bump: u8,
+
+    /// World PDA the Instance was spawned in
+    pub world: Pubkey,
+
+    /// Number of updates to the components since spawn,
+    /// compare to detect stale copies of the Instance
+    pub version: u64,
+    /// Slot of the spawn or of the last update
+    pub last_update_slot: u64,
 }
 
 impl Instance {
@@ -44,16 +53,26 @@ impl Instance {
         nonce: u64,
         instance_authority: Pubkey,
         bump: u8,
+        world: Pubkey,
     ) -> Self {
         Self {
             components,
             nonce,
             instance_authority,
             bump,
+            world,
+            version: 0,
+            last_update_slot: 0,
             discriminator: Self::SPL_DISCRIMINATOR.into(),
         }
     }
 
+    /// Record an update to the components made at `slot`
+    pub fn touch(&mut self, slot: u64) {
+        self.version = self.version.saturating_add(1);
+        self.last_update_slot = slot;
+    }
+
     /// Is `true` if Instances is initialized
     pub fn is_initialized(&self) -> bool {
         self.discriminator.as_slice() == Instance::SPL_DISCRIMINATOR_SLICE
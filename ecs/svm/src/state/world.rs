@@ -1,7 +1,8 @@
+use crate::error::RushStoreError;
 use borsh::{BorshDeserialize, BorshSerialize};
 use rush_ecs_core::blueprint::{Entity, Region};
 use shank::ShankAccount;
-use solana_program::{msg, pubkey::Pubkey};
+use solana_program::pubkey::Pubkey;
 use spl_discriminator::{ArrayDiscriminator, SplDiscriminate};
 use std::collections::BTreeMap;
 
@@ -34,10 +35,17 @@ pub struct World {
     /// Onchain record of what Regions exist in the world
     pub regions: Vec<Region>,
     /// Source of truth for what Instances exist in the world
+    ///
+    /// Holds the last nonce spawned per Region and Entity,
+    /// nonces of despawned Instances are never reused. Kept
+    /// for removed Regions and Entities, so re-adding one
+    /// doesn't reuse its nonces either
     pub instances: BTreeMap<Region, BTreeMap<Entity, u64>>,
     /// Determines if the World is already launched and
     /// instances can now be Created, Updated, and Deleted
-    /// outside of the CreateWorld (Initialization) Instruction
+    /// by signers other than the World authority
+    ///
+    /// Set by LaunchWorld and cleared by PauseWorld
     pub is_launched: bool,
     /// Overaching authority who has access to state changing
     /// operations
@@ -45,6 +53,12 @@ pub struct World {
 
     /// Canonical bump for World
     pub bump: u8,
+
+    /// Number of despawned Instances per Region and Entity
+    pub despawned: BTreeMap<Region, BTreeMap<Entity, u64>>,
+    /// Number of live Instances per Region and Entity,
+    /// spawned and not despawned yet
+    pub live: BTreeMap<Region, BTreeMap<Entity, u64>>,
 }
 
 impl World {
@@ -58,6 +72,15 @@ impl World {
         self.discriminator.as_slice() == ArrayDiscriminator::UNINITIALIZED.as_slice()
     }
 
+    /// Is `true` if the signer can Spawn, Update and Despawn
+    /// Instances in the World
+    ///
+    /// Only the World Authority can before the World is
+    /// launched and while it's paused
+    pub fn is_open_to(&self, signer: &Pubkey) -> bool {
+        self.is_launched || self.world_authority == *signer
+    }
+
     /// Create new World state
     ///
     /// # Arguments
@@ -93,6 +116,8 @@ impl World {
                 }
             }
         }
+        // no Instances are live yet
+        let live = instances.clone();
 
         Self {
             name,
@@ -104,6 +129,110 @@ impl World {
             discriminator: World::SPL_DISCRIMINATOR.into(),
             is_launched: false,
             instances,
+            despawned: BTreeMap::new(),
+            live,
         }
     }
+
+    /// Is `true` if the Region and Entity are in the World,
+    /// and not removed
+    pub fn is_tracking(&self, region: &Region, entity: &Entity) -> bool {
+        self.regions.contains(region) && self.entities.contains(entity)
+    }
+
+    /// Number of live Instances of an Entity in a Region
+    pub fn live_instances(&self, region: &Region, entity: &Entity) -> u64 {
+        self.live
+            .get(region)
+            .and_then(|entities| entities.get(entity))
+            .copied()
+            .unwrap_or_default()
+    }
+
+    /// Add a Region and start tracking its Instances
+    ///
+    /// Does nothing if the Region already exists. A Region
+    /// that was removed keeps its last nonces
+    pub fn add_region(&mut self, region: Region) {
+        if self.regions.contains(&region) {
+            return;
+        }
+
+        for map in [&mut self.instances, &mut self.live] {
+            let region_mut = map.entry(region.clone()).or_default();
+            for entity in self.entities.iter() {
+                region_mut.entry(entity.clone()).or_insert(0);
+            }
+        }
+        self.regions.push(region);
+    }
+
+    /// Add an Entity and start tracking its Instances
+    ///
+    /// Does nothing if the Entity already exists. An Entity
+    /// that was removed keeps its last nonces
+    pub fn add_entity(&mut self, entity: Entity) {
+        if self.entities.contains(&entity) {
+            return;
+        }
+
+        for map in [&mut self.instances, &mut self.live] {
+            for region in self.regions.iter() {
+                map.entry(region.clone())
+                    .or_default()
+                    .entry(entity.clone())
+                    .or_insert(0);
+            }
+        }
+        self.entities.push(entity);
+    }
+
+    /// Remove a Region and stop tracking its live Instances
+    ///
+    /// Fails if the Region still has live Instances, unless
+    /// `force` is set. Forcing leaves their accounts behind.
+    /// Its last nonces are kept, they are never reused
+    pub fn remove_region(&mut self, region: &Region, force: bool) -> Result<(), RushStoreError> {
+        let Some(index) = self.regions.iter().position(|r| r == region) else {
+            return Err(RushStoreError::RegionNotFound);
+        };
+        let has_instances = self
+            .entities
+            .iter()
+            .any(|entity| self.live_instances(region, entity) > 0);
+        if has_instances && !force {
+            return Err(RushStoreError::RegionHasInstances);
+        }
+
+        self.regions.remove(index);
+        self.live.remove(region);
+
+        Ok(())
+    }
+
+    /// Remove an Entity and stop tracking its live Instances
+    ///
+    /// Fails if the Entity still has live Instances in any
+    /// Region, unless `force` is set. Forcing leaves their
+    /// accounts behind. Its last nonces are kept, they are
+    /// never reused
+    pub fn remove_entity(&mut self, entity: &Entity, force: bool) -> Result<(), RushStoreError> {
+        let Some(index) = self.entities.iter().position(|e| e == entity) else {
+            return Err(RushStoreError::EntityNotFound);
+        };
+        let has_instances = self
+            .regions
+            .iter()
+            .any(|region| self.live_instances(region, entity) > 0);
+        if has_instances && !force {
+            return Err(RushStoreError::EntityHasInstances);
+        }
+
+        self.entities.remove(index);
+        for entities in self.live.values_mut() {
+            entities.remove(entity);
+        }
+
+        Ok(())
+    }
 }